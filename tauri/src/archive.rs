@@ -0,0 +1,293 @@
+use std::{
+	collections::{HashMap, HashSet},
+	fs::File,
+	io::{self, BufReader, Read},
+	path::Path,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
+use czkawka_core::common::model::HashType;
+use flate2::read::GzDecoder;
+
+use crate::file_hash::hash_reader;
+
+// Shown between the archive path and the path of a member inside it
+pub const MEMBER_SEPARATOR: &str = "!/";
+
+pub struct ArchiveMember {
+	// `archive.zip!/path/in/archive`
+	pub path: String,
+	pub size: u64,
+	pub crc: u32,
+}
+
+#[derive(Clone, Copy)]
+enum ArchiveKind {
+	Zip,
+	Tar,
+	TarGz,
+	SevenZ,
+}
+
+fn get_archive_kind(path: &Path) -> Option<ArchiveKind> {
+	let name = path.file_name()?.to_string_lossy().to_lowercase();
+	if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+		return Some(ArchiveKind::TarGz);
+	}
+	match path.extension()?.to_string_lossy().to_lowercase().as_ref() {
+		"zip" => Some(ArchiveKind::Zip),
+		"tar" => Some(ArchiveKind::Tar),
+		"7z" => Some(ArchiveKind::SevenZ),
+		_ => None,
+	}
+}
+
+pub fn is_archive(path: &Path) -> bool {
+	get_archive_kind(path).is_some()
+}
+
+// Zip and 7z keep the CRC of every member in their index, members of tar
+// archives are read to compute it. Nested archives are not opened
+pub fn read_archive_members(
+	path: &Path,
+	stop_flag: &Arc<AtomicBool>,
+) -> Result<Vec<ArchiveMember>, String> {
+	let kind = get_archive_kind(path).ok_or("not an archive")?;
+	let file = File::open(path).map_err(|err| err.to_string())?;
+	let prefix = format!("{}{}", path.to_string_lossy(), MEMBER_SEPARATOR);
+
+	match kind {
+		ArchiveKind::Zip => read_zip_members(file, &prefix),
+		ArchiveKind::Tar => {
+			read_tar_members(BufReader::new(file), &prefix, stop_flag)
+		}
+		ArchiveKind::TarGz => read_tar_members(
+			GzDecoder::new(BufReader::new(file)),
+			&prefix,
+			stop_flag,
+		),
+		ArchiveKind::SevenZ => read_seven_z_members(file, &prefix),
+	}
+}
+
+fn read_zip_members(
+	file: File,
+	prefix: &str,
+) -> Result<Vec<ArchiveMember>, String> {
+	let mut archive = zip::ZipArchive::new(BufReader::new(file))
+		.map_err(|err| err.to_string())?;
+	let mut members = vec![];
+
+	for i in 0..archive.len() {
+		let member = archive.by_index_raw(i).map_err(|err| err.to_string())?;
+		if member.is_dir() {
+			continue;
+		}
+		members.push(ArchiveMember {
+			path: format!("{}{}", prefix, member.name()),
+			size: member.size(),
+			crc: member.crc32(),
+		});
+	}
+
+	Ok(members)
+}
+
+fn read_tar_members<R: Read>(
+	reader: R,
+	prefix: &str,
+	stop_flag: &Arc<AtomicBool>,
+) -> Result<Vec<ArchiveMember>, String> {
+	let mut archive = tar::Archive::new(reader);
+	let mut members = vec![];
+
+	for entry in archive.entries().map_err(|err| err.to_string())? {
+		if stop_flag.load(Ordering::Relaxed) {
+			return Ok(vec![]);
+		}
+		let mut entry = entry.map_err(|err| err.to_string())?;
+		if !entry.header().entry_type().is_file() {
+			continue;
+		}
+		let name = entry
+			.path()
+			.map_err(|err| err.to_string())?
+			.to_string_lossy()
+			.to_string();
+		let size = entry.size();
+		let crc = get_crc(&mut entry).map_err(|err| err.to_string())?;
+		members.push(ArchiveMember {
+			path: format!("{}{}", prefix, name),
+			size,
+			crc,
+		});
+	}
+
+	Ok(members)
+}
+
+// Members without a stored CRC are skipped, they can't be matched without
+// decompressing the whole archive
+fn read_seven_z_members(
+	mut file: File,
+	prefix: &str,
+) -> Result<Vec<ArchiveMember>, String> {
+	let len = file.metadata().map_err(|err| err.to_string())?.len();
+	let archive = sevenz_rust::Archive::read(&mut file, len, &[])
+		.map_err(|err| err.to_string())?;
+
+	Ok(archive
+		.files
+		.iter()
+		.filter(|entry| {
+			!entry.is_directory && entry.has_stream && entry.has_crc
+		})
+		.map(|entry| ArchiveMember {
+			path: format!("{}{}", prefix, entry.name),
+			size: entry.size,
+			crc: entry.crc as u32,
+		})
+		.collect())
+}
+
+// Hashes the members at `member_paths` the same way loose files are hashed,
+// so a match with a loose file doesn't rely on the size and CRC alone
+pub fn hash_archive_members(
+	path: &Path,
+	member_paths: &HashSet<String>,
+	hash_type: HashType,
+	stop_flag: &Arc<AtomicBool>,
+) -> Result<HashMap<String, String>, String> {
+	let kind = get_archive_kind(path).ok_or("not an archive")?;
+	let file = File::open(path).map_err(|err| err.to_string())?;
+	let prefix = format!("{}{}", path.to_string_lossy(), MEMBER_SEPARATOR);
+
+	let mut hashes = HashMap::new();
+	let mut hash_member = |name: &str, reader: &mut dyn Read| {
+		let member_path = format!("{}{}", prefix, name);
+		if !member_paths.contains(&member_path) {
+			return;
+		}
+		match hash_reader(reader, hash_type, None, stop_flag) {
+			Ok(hash) => {
+				hashes.insert(member_path, hash);
+			}
+			Err(err) => {
+				log::info!("Failed to hash `{}`: {}", member_path, err);
+			}
+		}
+	};
+
+	match kind {
+		ArchiveKind::Zip => {
+			let mut archive = zip::ZipArchive::new(BufReader::new(file))
+				.map_err(|err| err.to_string())?;
+			for i in 0..archive.len() {
+				if stop_flag.load(Ordering::Relaxed) {
+					break;
+				}
+				let mut member =
+					archive.by_index(i).map_err(|err| err.to_string())?;
+				if member.is_file() {
+					let name = member.name().to_string();
+					hash_member(&name, &mut member);
+				}
+			}
+		}
+		ArchiveKind::Tar => {
+			hash_tar_members(BufReader::new(file), hash_member, stop_flag)?
+		}
+		ArchiveKind::TarGz => hash_tar_members(
+			GzDecoder::new(BufReader::new(file)),
+			hash_member,
+			stop_flag,
+		)?,
+		ArchiveKind::SevenZ => {
+			let len = file.metadata().map_err(|err| err.to_string())?.len();
+			let mut archive = sevenz_rust::SevenZReader::new(
+				file,
+				len,
+				sevenz_rust::Password::empty(),
+			)
+			.map_err(|err| err.to_string())?;
+			archive
+				.for_each_entries(|entry, reader| {
+					if stop_flag.load(Ordering::Relaxed) {
+						return Ok(false);
+					}
+					if !entry.is_directory && entry.has_stream {
+						hash_member(&entry.name, reader);
+					}
+					// Members of a solid block are decompressed one after
+					// another, whatever was not hashed is skipped
+					let _ = io::copy(reader, &mut io::sink());
+					Ok(true)
+				})
+				.map_err(|err| err.to_string())?;
+		}
+	}
+
+	Ok(hashes)
+}
+
+fn hash_tar_members<R: Read>(
+	reader: R,
+	mut hash_member: impl FnMut(&str, &mut dyn Read),
+	stop_flag: &Arc<AtomicBool>,
+) -> Result<(), String> {
+	let mut archive = tar::Archive::new(reader);
+
+	for entry in archive.entries().map_err(|err| err.to_string())? {
+		if stop_flag.load(Ordering::Relaxed) {
+			break;
+		}
+		let mut entry = entry.map_err(|err| err.to_string())?;
+		if !entry.header().entry_type().is_file() {
+			continue;
+		}
+		let name = entry
+			.path()
+			.map_err(|err| err.to_string())?
+			.to_string_lossy()
+			.to_string();
+		hash_member(&name, &mut entry);
+	}
+
+	Ok(())
+}
+
+fn get_crc<R: Read>(reader: &mut R) -> std::io::Result<u32> {
+	let mut hasher = crc32fast::Hasher::new();
+	let mut buf = vec![0; 64 * 1024];
+	loop {
+		let len = reader.read(&mut buf)?;
+		if len == 0 {
+			break;
+		}
+		hasher.update(&buf[..len]);
+	}
+	Ok(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn archive_kinds_are_recognized() {
+		let kind_of = |name: &str| get_archive_kind(Path::new(name));
+
+		assert!(matches!(kind_of("a.zip"), Some(ArchiveKind::Zip)));
+		assert!(matches!(kind_of("A.ZIP"), Some(ArchiveKind::Zip)));
+		assert!(matches!(kind_of("a.tar"), Some(ArchiveKind::Tar)));
+		assert!(matches!(kind_of("a.tar.gz"), Some(ArchiveKind::TarGz)));
+		assert!(matches!(kind_of("a.TGZ"), Some(ArchiveKind::TarGz)));
+		assert!(matches!(kind_of("a.7z"), Some(ArchiveKind::SevenZ)));
+		assert!(kind_of("a.gz").is_none());
+		assert!(kind_of("zip").is_none());
+		assert!(kind_of("a.zip.part").is_none());
+	}
+}
@@ -1,3 +1,11 @@
+use std::{
+	collections::{HashMap, HashSet},
+	fs::File,
+	io::Read,
+	path::Path,
+	sync::Mutex,
+};
+
 use czkawka_core::{
 	common::{split_path_compare, tool_data::CommonData, traits::Search},
 	tools::bad_extensions::{
@@ -5,44 +13,176 @@ use czkawka_core::{
 	},
 };
 use rayon::prelude::*;
-use serde::Serialize;
-use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
 
 use crate::{
+	file_walker::FileWalker,
+	progress::ProgressReporter,
+	rename_ext::{ConflictOptions, Item, RenameExtResult, rename_ext_impl},
 	scaner::{set_scaner_common_settings, spawn_scaner_thread},
 	settings::Settings,
-	state::get_stop_flag_and_progress_tx,
+	state::{AppState, get_stop_flag_and_progress_tx},
 };
 
+// Enough for `infer` to recognize the formats it knows
+const HEADER_SIZE: usize = 8192;
+
+#[derive(Serialize, Clone)]
+pub struct CustomBadFileEntry {
+	path: String,
+	modified_date: u64,
+	size: u64,
+	current_extension: String,
+	proper_extensions_group: String,
+	proper_extension: String,
+}
+
+impl CustomBadFileEntry {
+	pub fn to_line(&self) -> String {
+		format!("{:?} ----- {}", self.path, self.proper_extensions_group)
+	}
+}
+
 #[derive(Serialize, Clone)]
 struct ScanResult {
 	cmd: &'static str,
-	list: Vec<BadFileEntry>,
+	list: Vec<CustomBadFileEntry>,
 	message: String,
 }
 
+// Magic bytes of a format czkawka doesn't know, with the extensions the
+// format is allowed to have. `infer` only takes plain `fn` matchers, so
+// signatures from the settings are matched here
+struct Signature {
+	offset: usize,
+	// `None` matches any byte
+	bytes: Vec<Option<u8>>,
+	extensions: Vec<String>,
+}
+
+impl Signature {
+	fn matches(&self, header: &[u8]) -> bool {
+		header.len() >= self.offset + self.bytes.len()
+			&& header[self.offset..]
+				.iter()
+				.zip(&self.bytes)
+				.all(|(byte, expected)| expected.is_none_or(|b| b == *byte))
+	}
+}
+
+// Extra rules on top of the table of czkawka
+struct CustomRules {
+	signatures: Vec<Signature>,
+	// MIME type to the extensions that are also fine for it
+	mime_extensions: HashMap<String, Vec<String>>,
+	infer: infer::Infer,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixOptions {
+	// Every entry of the last scan when `None`
+	paths: Option<Vec<String>>,
+	// Groups of extensions that are used for the same type, like
+	// `jpg, jpeg`, one group per line
+	equivalent_extensions: String,
+	#[serde(flatten)]
+	conflict_options: ConflictOptions,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct FixBadExtensionsResult {
+	#[serde(flatten)]
+	result: RenameExtResult,
+	// Entries left as they are, their extension is equivalent to the
+	// recommended one
+	equivalent_paths: Vec<String>,
+}
+
 pub fn scan_bad_extensions(app: AppHandle, settings: Settings) {
 	spawn_scaner_thread(move || {
 		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
 
+		let (signatures, invalid_signatures) =
+			parse_signatures(&settings.bad_extensions_sub_custom_signatures);
+		let rules = CustomRules {
+			signatures,
+			mime_extensions: parse_mime_extensions(
+				&settings.bad_extensions_sub_mime_extensions,
+			),
+			infer: infer::Infer::new(),
+		};
+		let walker = FileWalker::from_settings(&settings);
+
 		let mut scaner = BadExtensions::new(BadExtensionsParameters::new());
 
 		set_scaner_common_settings(&mut scaner, settings);
 
 		scaner.search(&stop_flag, Some(&progress_tx));
 
-		let mut list = scaner.get_bad_extensions_files().clone();
 		let mut message = scaner.get_text_messages().create_messages_text();
+		let found = scaner.get_bad_extensions_files();
+		let mut list: Vec<_> = found
+			.par_iter()
+			.filter_map(|entry| rules.apply(entry))
+			.collect();
+		let allowed_count = found.len() - list.len();
+
+		// Files of custom formats are fine for czkawka, they are looked for
+		// separately
+		if !rules.signatures.is_empty() {
+			let found_paths: HashSet<&Path> =
+				found.iter().map(|entry| entry.path.as_path()).collect();
+			let progress = ProgressReporter::new(&progress_tx, 0);
+			let files = walker.walk(&stop_flag, Some(&progress));
+			drop(progress);
+			list.extend(
+				files
+					.par_iter()
+					.filter(|file| !found_paths.contains(file.path.as_path()))
+					.filter_map(|file| {
+						let header =
+							read_header(&file.path, rules.header_size())?;
+						let signature = rules.find_signature(&header)?;
+						let current = get_extension(&file.path);
+						if signature.extensions.contains(&current) {
+							return None;
+						}
+						Some(CustomBadFileEntry {
+							path: file.path.to_string_lossy().to_string(),
+							modified_date: file.modified_date,
+							size: file.size,
+							current_extension: current,
+							proper_extensions_group: signature
+								.extensions
+								.join(","),
+							proper_extension: signature.extensions[0].clone(),
+						})
+					})
+					.collect::<Vec<_>>(),
+			);
+		}
 
 		list.par_sort_unstable_by(|a, b| {
-			split_path_compare(a.path.as_path(), b.path.as_path())
+			split_path_compare(Path::new(&a.path), Path::new(&b.path))
 		});
 
 		message = format!(
-			"Found {} files with bad extensions\n{}",
+			"Found {} files with bad extensions, {} allowed by custom rules\n{}",
 			list.len(),
+			allowed_count,
 			message
 		);
+		for rule in invalid_signatures {
+			message.push_str(&format!(
+				"\nInvalid custom signature `{}`, skipped",
+				rule
+			));
+		}
+
+		set_bad_extensions_entries(&app, list.clone());
 
 		app.emit(
 			"scan-result",
@@ -58,7 +198,280 @@ pub fn scan_bad_extensions(app: AppHandle, settings: Settings) {
 	});
 }
 
+// Applies the recommended extension of the entries found by the last scan
+pub fn fix_bad_extensions(app: AppHandle, options: FixOptions) {
+	std::thread::spawn(move || {
+		let FixOptions {
+			paths,
+			equivalent_extensions,
+			conflict_options,
+		} = options;
+
+		let entries = {
+			let state_mutex = app.state::<Mutex<AppState>>();
+			let state = state_mutex.lock().unwrap();
+			state.bad_extensions_entries.clone().unwrap_or_default()
+		};
+		let paths: Option<HashSet<String>> =
+			paths.map(|paths| paths.into_iter().collect());
+		let groups = parse_equivalent_extensions(&equivalent_extensions);
+
+		let mut items = vec![];
+		let mut equivalent_paths = vec![];
+		for entry in entries {
+			if paths
+				.as_ref()
+				.is_some_and(|paths| !paths.contains(&entry.path))
+			{
+				continue;
+			}
+			if are_equivalent(
+				Path::new(&entry.path),
+				&entry.proper_extension,
+				&groups,
+			) {
+				equivalent_paths.push(entry.path);
+				continue;
+			}
+			items.push(Item {
+				path: entry.path,
+				ext: entry.proper_extension,
+			});
+		}
+
+		let result = FixBadExtensionsResult {
+			result: rename_ext_impl(items, &conflict_options),
+			equivalent_paths,
+		};
+		remove_bad_extensions_entries(&app, &result.result.success_paths);
+		app.emit("fix-bad-extensions-result", result).unwrap();
+	});
+}
+
+impl CustomRules {
+	fn header_size(&self) -> usize {
+		self.signatures
+			.iter()
+			.map(|signature| signature.offset + signature.bytes.len())
+			.max()
+			.unwrap_or_default()
+			.max(HEADER_SIZE)
+	}
+
+	fn find_signature(&self, header: &[u8]) -> Option<&Signature> {
+		self.signatures
+			.iter()
+			.find(|signature| signature.matches(header))
+	}
+
+	// `None` when the custom rules allow the current extension
+	fn apply(&self, entry: &BadFileEntry) -> Option<CustomBadFileEntry> {
+		let mut custom_entry = CustomBadFileEntry {
+			path: entry.path.to_string_lossy().to_string(),
+			modified_date: entry.modified_date,
+			size: entry.size,
+			current_extension: entry.current_extension.clone(),
+			proper_extensions_group: entry.proper_extensions_group.clone(),
+			proper_extension: entry.proper_extension.clone(),
+		};
+		if self.signatures.is_empty() && self.mime_extensions.is_empty() {
+			return Some(custom_entry);
+		}
+
+		let Some(header) = read_header(&entry.path, self.header_size()) else {
+			return Some(custom_entry);
+		};
+		let current = get_extension(&entry.path);
+
+		if let Some(signature) = self.find_signature(&header) {
+			if signature.extensions.contains(&current) {
+				return None;
+			}
+			custom_entry.proper_extensions_group =
+				signature.extensions.join(",");
+			custom_entry.proper_extension = signature.extensions[0].clone();
+			return Some(custom_entry);
+		}
+
+		let allowed = self
+			.infer
+			.get(&header)
+			.and_then(|kind| self.mime_extensions.get(kind.mime_type()))
+			.is_some_and(|extensions| extensions.contains(&current));
+		(!allowed).then_some(custom_entry)
+	}
+}
+
+// One signature per line, like `acp, acpx: 41 43 4D 45` or
+// `m4b: 66 74 79 70 4D 34 42 @4`, `??` matches any byte. Returns the
+// signatures and the lines that are not valid ones
+fn parse_signatures(rules: &str) -> (Vec<Signature>, Vec<String>) {
+	let mut signatures = vec![];
+	let mut invalid = vec![];
+
+	for rule in rules.lines().map(str::trim).filter(|rule| !rule.is_empty()) {
+		match parse_signature(rule) {
+			Some(signature) => signatures.push(signature),
+			None => invalid.push(rule.to_string()),
+		}
+	}
+
+	(signatures, invalid)
+}
+
+fn parse_signature(rule: &str) -> Option<Signature> {
+	let (extensions, bytes) = rule.split_once(':')?;
+	let (bytes, offset) = match bytes.split_once('@') {
+		Some((bytes, offset)) => (bytes, offset.trim().parse().ok()?),
+		None => (bytes, 0),
+	};
+	let hex: Vec<u8> = bytes
+		.bytes()
+		.filter(|byte| !byte.is_ascii_whitespace())
+		.collect();
+	if hex.is_empty() || hex.len() % 2 != 0 {
+		return None;
+	}
+	let bytes = hex
+		.chunks(2)
+		.map(|pair| match pair {
+			b"??" => Some(None),
+			[high, low] => {
+				Some(Some((hex_value(*high)? << 4) | hex_value(*low)?))
+			}
+			_ => None,
+		})
+		.collect::<Option<Vec<_>>>()?;
+	let extensions = parse_extensions(extensions);
+	(!extensions.is_empty()).then_some(Signature {
+		offset,
+		bytes,
+		extensions,
+	})
+}
+
+// Works on single bytes, so text with other characters never gets cut
+// inside a character
+fn hex_value(byte: u8) -> Option<u8> {
+	(byte as char).to_digit(16).map(|value| value as u8)
+}
+
+// One MIME type per line, like `application/zip: docm, apk`
+fn parse_mime_extensions(rules: &str) -> HashMap<String, Vec<String>> {
+	rules
+		.lines()
+		.filter_map(|rule| {
+			let (mime, extensions) = rule.split_once(':')?;
+			let extensions = parse_extensions(extensions);
+			(!extensions.is_empty())
+				.then(|| (mime.trim().to_lowercase(), extensions))
+		})
+		.collect()
+}
+
+fn parse_extensions(extensions: &str) -> Vec<String> {
+	extensions
+		.split(',')
+		.map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+		.filter(|ext| !ext.is_empty())
+		.collect()
+}
+
+fn get_extension(path: &Path) -> String {
+	path.extension()
+		.map(|ext| ext.to_string_lossy().to_lowercase())
+		.unwrap_or_default()
+}
+
+fn read_header(path: &Path, size: usize) -> Option<Vec<u8>> {
+	let mut header = vec![];
+	File::open(path)
+		.ok()?
+		.take(size as u64)
+		.read_to_end(&mut header)
+		.ok()?;
+	Some(header)
+}
+
+fn set_bad_extensions_entries(
+	app: &AppHandle,
+	entries: Vec<CustomBadFileEntry>,
+) {
+	let state_mutex = app.state::<Mutex<AppState>>();
+	let mut state = state_mutex.lock().unwrap();
+	state.bad_extensions_entries = Some(entries);
+}
+
+// Renamed files are gone from the result table, so they are not fixed again
+fn remove_bad_extensions_entries(app: &AppHandle, paths: &[String]) {
+	let paths: HashSet<_> = paths.iter().collect();
+	let state_mutex = app.state::<Mutex<AppState>>();
+	let mut state = state_mutex.lock().unwrap();
+	if let Some(entries) = &mut state.bad_extensions_entries {
+		entries.retain(|entry| !paths.contains(&entry.path));
+	}
+}
+
+fn parse_equivalent_extensions(groups: &str) -> Vec<HashSet<String>> {
+	groups
+		.split(['\n', ';'])
+		.map(|group| {
+			parse_extensions(group).into_iter().collect::<HashSet<_>>()
+		})
+		.filter(|group| group.len() > 1)
+		.collect()
+}
+
+fn are_equivalent(
+	path: &Path,
+	proper: &str,
+	groups: &[HashSet<String>],
+) -> bool {
+	let Some(current) = path.extension() else {
+		return false;
+	};
+	let current = current.to_string_lossy().to_lowercase();
+	let proper = proper.to_lowercase();
+	current == proper
+		|| groups
+			.iter()
+			.any(|group| group.contains(&current) && group.contains(&proper))
+}
+
 crate::gen_set_scaner_state_fn!(
 	bad_extensions_state,
 	czkawka_core::tools::bad_extensions::BadExtensions
 );
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn signatures_are_parsed() {
+		let (signatures, invalid) = parse_signatures(
+			"acp, .ACPX: 41 43 4d 45\n\n m4b: 66 74 ?? 70 @4 \n",
+		);
+		assert!(invalid.is_empty());
+		assert_eq!(signatures.len(), 2);
+		assert_eq!(signatures[0].offset, 0);
+		assert_eq!(
+			signatures[0].bytes,
+			vec![Some(0x41), Some(0x43), Some(0x4D), Some(0x45)]
+		);
+		assert_eq!(signatures[0].extensions, vec!["acp", "acpx"]);
+		assert_eq!(signatures[1].offset, 4);
+		assert_eq!(
+			signatures[1].bytes,
+			vec![Some(0x66), Some(0x74), None, Some(0x70)]
+		);
+	}
+
+	#[test]
+	fn invalid_signatures_are_skipped() {
+		let rules = "a: aéb\nb: 4\nc: zz\nd: 41 @x\n: 41\ne 41\nf: é1";
+		let (signatures, invalid) = parse_signatures(rules);
+		assert!(signatures.is_empty());
+		assert_eq!(invalid.len(), 7);
+	}
+}
@@ -1,26 +1,102 @@
+use std::{
+	collections::HashSet,
+	fs::{self, File},
+	io::{self, BufReader, Cursor, Read, Seek},
+	path::Path,
+	process::{Command, Stdio},
+	sync::{
+		Arc, Mutex,
+		atomic::{AtomicBool, AtomicUsize, Ordering},
+	},
+	thread,
+	time::{Duration, Instant},
+};
+
 use czkawka_core::{
-	common::{split_path_compare, tool_data::CommonData, traits::Search},
+	common::{
+		progress_data::CurrentStage, split_path_compare, tool_data::CommonData,
+		traits::Search,
+	},
 	tools::broken_files::{
 		BrokenEntry, BrokenFiles, BrokenFilesParameters, CheckedTypes,
 	},
 };
+use flate2::read::GzDecoder;
+use quick_xml::events::Event;
 use rayon::prelude::*;
 use serde::Serialize;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 
 use crate::{
+	ffmpeg::get_ffmpeg_exe,
+	file_walker::{FileWalker, WalkedFile},
+	progress::ProgressReporter,
 	scaner::{set_scaner_common_settings, spawn_scaner_thread},
 	settings::Settings,
-	state::get_stop_flag_and_progress_tx,
+	state::{AppState, get_stop_flag_and_progress_tx},
+	video_info::VIDEO_EXTENSIONS,
 };
 
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc"];
+
+// Nested archives are opened up to this depth
+const MAX_ARCHIVE_DEPTH: usize = 4;
+
+// Nested archives are read into memory, bigger ones are not opened
+const MAX_NESTED_ARCHIVE_SIZE: u64 = 256 * 1024 * 1024;
+
+// Also the extensions of TypeScript sources, files with them are only
+// checked when they start like an MPEG transport stream
+const TRANSPORT_STREAM_EXTENSIONS: &[&str] = &["ts", "mts"];
+
+// Plain and Blu-ray transport streams, the sync byte starts every packet
+const TS_SYNC_BYTE: u8 = 0x47;
+const TS_PACKET_SIZES: &[(usize, usize)] = &[(0, 188), (4, 192)];
+const TS_HEAD_SIZE: u64 = 4 + 192 + 1;
+
+// Videos that take longer to decode are skipped
+const VIDEO_CHECK_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const VIDEO_CHECK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Serialize, Clone)]
+pub struct CustomBrokenEntry {
+	path: String,
+	modified_date: u64,
+	size: u64,
+	error_string: String,
+}
+
+impl CustomBrokenEntry {
+	pub fn to_line(&self) -> String {
+		format!("{:?} - {}", self.path, self.error_string)
+	}
+}
+
 #[derive(Serialize, Clone)]
 struct ScanResult {
 	cmd: &'static str,
-	list: Vec<BrokenEntry>,
+	list: Vec<CustomBrokenEntry>,
 	message: String,
 }
 
+#[derive(Default)]
+struct CustomChecksResult {
+	entries: Vec<CustomBrokenEntry>,
+	ffmpeg_missing: bool,
+	// Videos that took too long to decode
+	skipped_videos: usize,
+	// Archives with nested archives too big to be checked
+	skipped_archives: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CustomCheck {
+	Video,
+	Document,
+	Font,
+	DeepArchive,
+}
+
 pub fn scan_broken_files(app: AppHandle, settings: Settings) {
 	spawn_scaner_thread(move || {
 		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
@@ -35,29 +111,107 @@ pub fn scan_broken_files(app: AppHandle, settings: Settings) {
 		if settings.broken_files_sub_image {
 			checked_types |= CheckedTypes::IMAGE;
 		}
-		if settings.broken_files_sub_archive {
+		// The deep check covers everything the archive check of czkawka does
+		if settings.broken_files_sub_archive
+			&& !settings.broken_files_sub_deep_archive
+		{
 			checked_types |= CheckedTypes::ARCHIVE;
 		}
-		if checked_types == CheckedTypes::NONE {
+
+		let mut custom_checks = vec![];
+		if settings.broken_files_sub_video {
+			custom_checks.push(CustomCheck::Video);
+		}
+		if settings.broken_files_sub_document {
+			custom_checks.push(CustomCheck::Document);
+		}
+		if settings.broken_files_sub_font {
+			custom_checks.push(CustomCheck::Font);
+		}
+		if settings.broken_files_sub_archive
+			&& settings.broken_files_sub_deep_archive
+		{
+			custom_checks.push(CustomCheck::DeepArchive);
+		}
+
+		if checked_types == CheckedTypes::NONE && custom_checks.is_empty() {
 			checked_types = CheckedTypes::AUDIO;
 		}
 
-		let mut scaner =
-			BrokenFiles::new(BrokenFilesParameters::new(checked_types));
+		let ffmpeg = get_ffmpeg_exe(&settings);
+		let walker = FileWalker::from_settings(&settings);
+
+		let mut list = vec![];
+		let mut message = String::new();
+		let mut scaner = None;
 
-		set_scaner_common_settings(&mut scaner, settings);
+		if checked_types != CheckedTypes::NONE {
+			let mut czkawka_scaner =
+				BrokenFiles::new(BrokenFilesParameters::new(checked_types));
 
-		scaner.search(&stop_flag, Some(&progress_tx));
+			set_scaner_common_settings(&mut czkawka_scaner, settings);
 
-		let mut list = scaner.get_broken_files().clone();
-		let mut message = scaner.get_text_messages().create_messages_text();
+			czkawka_scaner.search(&stop_flag, Some(&progress_tx));
+
+			list.extend(
+				czkawka_scaner
+					.get_broken_files()
+					.iter()
+					.map(convert_broken_entry),
+			);
+			message = czkawka_scaner.get_text_messages().create_messages_text();
+			scaner = Some(czkawka_scaner);
+		}
+
+		if !custom_checks.is_empty() {
+			// Collecting and checking the files czkawka doesn't check
+			let progress = ProgressReporter::new(&progress_tx, 1);
+			let files = walker.walk(&stop_flag, Some(&progress));
+			let result = run_custom_checks(
+				&files,
+				&custom_checks,
+				&ffmpeg,
+				&stop_flag,
+				&progress,
+			);
+			drop(progress);
+			let found: HashSet<_> =
+				list.iter().map(|entry| entry.path.clone()).collect();
+			list.extend(
+				result
+					.entries
+					.into_iter()
+					.filter(|entry| !found.contains(&entry.path)),
+			);
+			if result.ffmpeg_missing {
+				message.push_str(
+					"\nffmpeg was not found, video files were not checked",
+				);
+			}
+			if result.skipped_videos > 0 {
+				message.push_str(&format!(
+					"\n{} video files took longer than {} minutes to check and were skipped",
+					result.skipped_videos,
+					VIDEO_CHECK_TIMEOUT.as_secs() / 60
+				));
+			}
+			if result.skipped_archives > 0 {
+				message.push_str(&format!(
+					"\n{} archives contain nested archives bigger than {} MB, those were skipped",
+					result.skipped_archives,
+					MAX_NESTED_ARCHIVE_SIZE / 1024 / 1024
+				));
+			}
+		}
 
 		list.par_sort_unstable_by(|a, b| {
-			split_path_compare(a.path.as_path(), b.path.as_path())
+			split_path_compare(Path::new(&a.path), Path::new(&b.path))
 		});
 
 		message = format!("Found {} files\n{}", list.len(), message);
 
+		set_broken_files_entries(&app, list.clone());
+
 		app.emit(
 			"scan-result",
 			ScanResult {
@@ -68,11 +222,432 @@ pub fn scan_broken_files(app: AppHandle, settings: Settings) {
 		)
 		.unwrap();
 
-		set_scaner_state(app, scaner);
+		if let Some(scaner) = scaner {
+			set_scaner_state(app, scaner);
+		}
+	});
+}
+
+fn convert_broken_entry(entry: &BrokenEntry) -> CustomBrokenEntry {
+	CustomBrokenEntry {
+		path: entry.path.to_string_lossy().to_string(),
+		modified_date: entry.modified_date,
+		size: entry.size,
+		error_string: entry.error_string.clone(),
+	}
+}
+
+fn run_custom_checks(
+	files: &[WalkedFile],
+	checks: &[CustomCheck],
+	ffmpeg: &str,
+	stop_flag: &Arc<AtomicBool>,
+	progress: &ProgressReporter,
+) -> CustomChecksResult {
+	let ffmpeg_missing = AtomicBool::new(false);
+	let skipped_videos = AtomicUsize::new(0);
+	let skipped_archives = AtomicUsize::new(0);
+
+	let to_check: Vec<_> = files
+		.iter()
+		.filter_map(|file| Some((file, get_check(&file.path, checks)?)))
+		.collect();
+	progress.next_stage(
+		CurrentStage::BrokenFilesChecking,
+		to_check.len(),
+		to_check.iter().map(|(file, _)| file.size).sum(),
+	);
+
+	let entries = to_check
+		.into_par_iter()
+		.filter_map(|(file, check)| {
+			if stop_flag.load(Ordering::Relaxed) {
+				return None;
+			}
+			progress.add_entries(1);
+			progress.add_bytes(file.size);
+			let result = match check {
+				CustomCheck::Video => {
+					if ffmpeg_missing.load(Ordering::Relaxed)
+						|| !is_checked_video(&file.path)
+					{
+						return None;
+					}
+					match check_video(&file.path, ffmpeg, stop_flag) {
+						Err(err) if err.kind() == io::ErrorKind::NotFound => {
+							ffmpeg_missing.store(true, Ordering::Relaxed);
+							return None;
+						}
+						Err(err) => Err(err.to_string()),
+						Ok(Some(result)) => result,
+						Ok(None) => {
+							if !stop_flag.load(Ordering::Relaxed) {
+								skipped_videos.fetch_add(1, Ordering::Relaxed);
+							}
+							return None;
+						}
+					}
+				}
+				CustomCheck::Document => check_document(&file.path),
+				CustomCheck::Font => check_font(&file.path),
+				CustomCheck::DeepArchive => {
+					check_archive(&file.path).map(|skipped| {
+						if skipped > 0 {
+							skipped_archives.fetch_add(1, Ordering::Relaxed);
+						}
+					})
+				}
+			};
+			let error_string = result.err()?;
+			Some(CustomBrokenEntry {
+				path: file.path.to_string_lossy().to_string(),
+				modified_date: file.modified_date,
+				size: file.size,
+				error_string,
+			})
+		})
+		.collect();
+
+	CustomChecksResult {
+		entries,
+		ffmpeg_missing: ffmpeg_missing.load(Ordering::Relaxed),
+		skipped_videos: skipped_videos.load(Ordering::Relaxed),
+		skipped_archives: skipped_archives.load(Ordering::Relaxed),
+	}
+}
+
+fn get_check(path: &Path, checks: &[CustomCheck]) -> Option<CustomCheck> {
+	let ext = path.extension()?.to_string_lossy().to_lowercase();
+	let check = if VIDEO_EXTENSIONS.contains(&ext.as_ref()) {
+		CustomCheck::Video
+	} else if FONT_EXTENSIONS.contains(&ext.as_ref()) {
+		CustomCheck::Font
+	} else if get_document_parts(&ext).is_some() {
+		CustomCheck::Document
+	} else if get_archive_kind(path).is_some() {
+		CustomCheck::DeepArchive
+	} else {
+		return None;
+	};
+	checks.contains(&check).then_some(check)
+}
+
+fn is_checked_video(path: &Path) -> bool {
+	let ext = path
+		.extension()
+		.map(|ext| ext.to_string_lossy().to_lowercase())
+		.unwrap_or_default();
+	!TRANSPORT_STREAM_EXTENSIONS.contains(&ext.as_ref())
+		|| is_transport_stream(path)
+}
+
+// The first two packets have to start with the sync byte
+fn is_transport_stream(path: &Path) -> bool {
+	let mut head = vec![];
+	let read = File::open(path)
+		.and_then(|file| file.take(TS_HEAD_SIZE).read_to_end(&mut head));
+	read.is_ok() && is_transport_stream_head(&head)
+}
+
+fn is_transport_stream_head(head: &[u8]) -> bool {
+	TS_PACKET_SIZES.iter().any(|&(offset, packet_size)| {
+		head.get(offset) == Some(&TS_SYNC_BYTE)
+			&& head.get(offset + packet_size) == Some(&TS_SYNC_BYTE)
+	})
+}
+
+// Decodes every frame until the first decoding error. The outer error is
+// only set when ffmpeg can't be run, `None` means the check was stopped or
+// took too long and ffmpeg was killed
+fn check_video(
+	path: &Path,
+	ffmpeg: &str,
+	stop_flag: &AtomicBool,
+) -> io::Result<Option<Result<(), String>>> {
+	let mut child = Command::new(ffmpeg)
+		.args(["-v", "error", "-xerror", "-nostdin", "-i"])
+		.arg(path)
+		.args(["-f", "null", "-"])
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	// Read on its own thread, so ffmpeg never waits on a full pipe
+	let mut stderr = child.stderr.take().unwrap();
+	let stderr_reader = thread::spawn(move || {
+		let mut output = String::new();
+		let _ = stderr.read_to_string(&mut output);
+		output
 	});
+
+	let deadline = Instant::now() + VIDEO_CHECK_TIMEOUT;
+	let status = loop {
+		match child.try_wait() {
+			Ok(Some(status)) => break Some(status),
+			Ok(None)
+				if !stop_flag.load(Ordering::Relaxed)
+					&& Instant::now() < deadline =>
+			{
+				thread::sleep(VIDEO_CHECK_POLL_INTERVAL);
+			}
+			_ => {
+				let _ = child.kill();
+				let _ = child.wait();
+				break None;
+			}
+		}
+	};
+	let stderr = stderr_reader.join().unwrap_or_default();
+	let Some(status) = status else {
+		return Ok(None);
+	};
+
+	let first_error = stderr.lines().find(|line| !line.trim().is_empty());
+
+	Ok(Some(match (status.success(), first_error) {
+		(true, None) => Ok(()),
+		(_, Some(error)) => Err(error.trim().to_string()),
+		(false, None) => Err(format!("ffmpeg exited with status: {}", status)),
+	}))
+}
+
+// The parts every OOXML or ODF document of the type must have
+fn get_document_parts(ext: &str) -> Option<&'static [&'static str]> {
+	let parts: &[&str] = match ext {
+		"docx" | "docm" | "dotx" | "dotm" => {
+			&["[Content_Types].xml", "word/document.xml"]
+		}
+		"xlsx" | "xlsm" | "xltx" | "xltm" => {
+			&["[Content_Types].xml", "xl/workbook.xml"]
+		}
+		"pptx" | "pptm" | "potx" | "potm" => {
+			&["[Content_Types].xml", "ppt/presentation.xml"]
+		}
+		"odt" | "ods" | "odp" | "odg" | "ott" | "ots" | "otp" => {
+			&["mimetype", "content.xml", "META-INF/manifest.xml"]
+		}
+		_ => return None,
+	};
+	Some(parts)
+}
+
+fn check_document(path: &Path) -> Result<(), String> {
+	let ext = path
+		.extension()
+		.map(|ext| ext.to_string_lossy().to_lowercase())
+		.unwrap_or_default();
+	let parts = get_document_parts(&ext).unwrap_or_default();
+
+	let file = File::open(path).map_err(|err| err.to_string())?;
+	let mut archive = zip::ZipArchive::new(BufReader::new(file))
+		.map_err(|err| format!("Not a valid zip container: {}", err))?;
+
+	for part in parts {
+		let entry = archive
+			.by_name(part)
+			.map_err(|_| format!("Missing required part `{}`", part))?;
+		if part.ends_with(".xml") {
+			check_xml(BufReader::new(entry))
+				.map_err(|err| format!("Invalid part `{}`: {}", part, err))?;
+		}
+	}
+
+	Ok(())
+}
+
+fn check_xml<R: io::BufRead>(reader: R) -> Result<(), String> {
+	let mut reader = quick_xml::Reader::from_reader(reader);
+	let mut buf = vec![];
+	loop {
+		match reader.read_event_into(&mut buf) {
+			Ok(Event::Eof) => return Ok(()),
+			Ok(_) => buf.clear(),
+			Err(err) => return Err(err.to_string()),
+		}
+	}
+}
+
+// Every font of a collection has to parse
+fn check_font(path: &Path) -> Result<(), String> {
+	let data = fs::read(path).map_err(|err| err.to_string())?;
+	let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+	for index in 0..count {
+		ttf_parser::Face::parse(&data, index)
+			.map_err(|err| format!("Invalid font {}: {}", index, err))?;
+	}
+	Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum ArchiveKind {
+	Zip,
+	Tar,
+	TarGz,
+}
+
+fn get_archive_kind(path: &Path) -> Option<ArchiveKind> {
+	let name = path.file_name()?.to_string_lossy().to_lowercase();
+	if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+		Some(ArchiveKind::TarGz)
+	} else if name.ends_with(".tar") {
+		Some(ArchiveKind::Tar)
+	} else if name.ends_with(".zip") {
+		Some(ArchiveKind::Zip)
+	} else {
+		None
+	}
+}
+
+// Reads every member, so checksums are verified, and opens nested archives.
+// Returns how many nested archives were too big to be checked
+fn check_archive(path: &Path) -> Result<usize, String> {
+	let kind = get_archive_kind(path).ok_or("not an archive")?;
+	let file = File::open(path).map_err(|err| err.to_string())?;
+	check_archive_reader(BufReader::new(file), kind, 0)
+}
+
+fn check_archive_reader<R: Read + Seek>(
+	reader: R,
+	kind: ArchiveKind,
+	depth: usize,
+) -> Result<usize, String> {
+	match kind {
+		ArchiveKind::Zip => check_zip(reader, depth),
+		ArchiveKind::Tar => check_tar(reader, depth),
+		ArchiveKind::TarGz => check_tar(GzDecoder::new(reader), depth),
+	}
+}
+
+fn check_zip<R: Read + Seek>(reader: R, depth: usize) -> Result<usize, String> {
+	let mut archive =
+		zip::ZipArchive::new(reader).map_err(|err| err.to_string())?;
+	let mut skipped = 0;
+
+	for i in 0..archive.len() {
+		let name = {
+			let entry =
+				archive.by_index_raw(i).map_err(|err| err.to_string())?;
+			if entry.is_dir() {
+				continue;
+			}
+			if entry.encrypted() {
+				return Err(format!(
+					"Password-protected entry `{}`",
+					entry.name()
+				));
+			}
+			entry.name().to_string()
+		};
+		// Compression methods the zip crate is built without are not breakage
+		let entry = match archive.by_index(i) {
+			Ok(entry) => entry,
+			Err(zip::result::ZipError::UnsupportedArchive(_)) => continue,
+			Err(err) => return Err(err.to_string()),
+		};
+		skipped += check_member(entry, &name, depth)?;
+	}
+
+	Ok(skipped)
+}
+
+fn check_tar<R: Read>(reader: R, depth: usize) -> Result<usize, String> {
+	let mut archive = tar::Archive::new(reader);
+	let mut skipped = 0;
+
+	for entry in archive.entries().map_err(|err| err.to_string())? {
+		let entry = entry.map_err(|err| err.to_string())?;
+		if !entry.header().entry_type().is_file() {
+			continue;
+		}
+		let name = entry
+			.path()
+			.map_err(|err| err.to_string())?
+			.to_string_lossy()
+			.to_string();
+		skipped += check_member(entry, &name, depth)?;
+	}
+
+	Ok(skipped)
+}
+
+// Nested archives are read into memory and checked, other members are read
+// to the end. Returns how many nested archives were too big to be checked
+fn check_member<R: Read>(
+	mut member: R,
+	name: &str,
+	depth: usize,
+) -> Result<usize, String> {
+	let nested =
+		get_archive_kind(Path::new(name)).filter(|_| depth < MAX_ARCHIVE_DEPTH);
+
+	let Some(kind) = nested else {
+		return io::copy(&mut member, &mut io::sink())
+			.map(|_| 0)
+			.map_err(|err| format!("Entry `{}`: {}", name, err));
+	};
+
+	let mut data = vec![];
+	member
+		.take(MAX_NESTED_ARCHIVE_SIZE + 1)
+		.read_to_end(&mut data)
+		.map_err(|err| format!("Entry `{}`: {}", name, err))?;
+	if data.len() as u64 > MAX_NESTED_ARCHIVE_SIZE {
+		return Ok(1);
+	}
+	check_archive_reader(Cursor::new(data), kind, depth + 1)
+		.map_err(|err| format!("Nested archive `{}`: {}", name, err))
+}
+
+fn set_broken_files_entries(app: &AppHandle, entries: Vec<CustomBrokenEntry>) {
+	let state_mutex = app.state::<Mutex<AppState>>();
+	let mut state = state_mutex.lock().unwrap();
+	state.broken_files_entries = Some(entries);
 }
 
 crate::gen_set_scaner_state_fn!(
 	broken_files_state,
 	czkawka_core::tools::broken_files::BrokenFiles
 );
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn transport_stream_head() {
+		let mut ts = vec![0; 400];
+		ts[0] = TS_SYNC_BYTE;
+		ts[188] = TS_SYNC_BYTE;
+		assert!(is_transport_stream_head(&ts));
+
+		let mut m2ts = vec![0; 400];
+		m2ts[4] = TS_SYNC_BYTE;
+		m2ts[196] = TS_SYNC_BYTE;
+		assert!(is_transport_stream_head(&m2ts));
+
+		let typescript = b"export const answer: number = 42;\n".repeat(10);
+		assert!(!is_transport_stream_head(&typescript));
+		assert!(!is_transport_stream_head(&[TS_SYNC_BYTE]));
+	}
+
+	#[test]
+	fn plain_tar_has_nothing_skipped() {
+		let mut data = vec![];
+		{
+			let mut builder = tar::Builder::new(&mut data);
+			let content = b"hello";
+			let mut header = tar::Header::new_gnu();
+			header.set_size(content.len() as u64);
+			header.set_cksum();
+			builder
+				.append_data(&mut header, "hello.txt", &content[..])
+				.unwrap();
+			builder.finish().unwrap();
+		}
+		assert_eq!(
+			check_archive_reader(Cursor::new(data), ArchiveKind::Tar, 0),
+			Ok(0)
+		);
+	}
+}
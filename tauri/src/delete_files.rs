@@ -1,16 +1,35 @@
-use std::{fs, path::Path};
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+};
 
 use czkawka_core::common::remove_folder_if_contains_only_empty_folders;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
 
+use crate::{
+	duplicate_folders::check_folder_fingerprint,
+	empty_folders::JunkFiles,
+	file_walker::FileWalker,
+	orphaned_sidecars::{SidecarMap, get_orphaned_sidecars_of},
+};
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Options {
 	paths: Vec<String>,
 	move_deleted_files_to_trash: bool,
 	is_empty_folders_tool: bool,
+	delete_sidecars: bool,
+	sidecar_map: String,
+	ignorable_files: String,
+	ignore_zero_byte_files: bool,
+	excluded_directories: Vec<String>,
+	excluded_items: String,
+	// Duplicate folders by path, with their fingerprint from the scan
+	folder_fingerprints: HashMap<String, String>,
 }
 
 #[derive(Serialize, Clone, Default)]
@@ -32,9 +51,19 @@ fn delete_files_impl(options: Options) -> DeleteFilesResult {
 		paths,
 		move_deleted_files_to_trash,
 		is_empty_folders_tool,
+		delete_sidecars,
+		sidecar_map,
+		ignorable_files,
+		ignore_zero_byte_files,
+		excluded_directories,
+		excluded_items,
+		folder_fingerprints,
 	} = options;
+	let junk_files = JunkFiles::parse(&ignorable_files, ignore_zero_byte_files);
+	let walker =
+		FileWalker::from_exclusions(excluded_directories, &excluded_items);
 
-	paths
+	let mut result = paths
 		.par_iter()
 		.fold(DeleteFilesResult::default, |mut result, path_str| {
 			let path = Path::new(path_str);
@@ -44,13 +73,47 @@ fn delete_files_impl(options: Options) -> DeleteFilesResult {
 				return result;
 			}
 
+			// Folders are only deleted with their content when they are
+			// duplicate folders that didn't change since the scan
+			let is_folder = !is_empty_folders_tool
+				&& fs::symlink_metadata(path).is_ok_and(|meta| meta.is_dir());
+			let checked_folder = match (
+				is_folder,
+				folder_fingerprints.get(path_str),
+			) {
+				(false, _) => Ok(()),
+				(true, Some(fingerprint)) => {
+					check_folder_fingerprint(path, fingerprint)
+				}
+				(true, None) => Err(
+					"Folders are only deleted from the duplicate folders tool"
+						.to_string(),
+				),
+			};
+			if let Err(err) = checked_folder {
+				result
+					.errors
+					.push(format!("`{}` Failed, reason: {}", path_str, err));
+				return result;
+			}
+
 			let fs_result = if is_empty_folders_tool {
-				remove_folder_if_contains_only_empty_folders(
+				remove_junk_files(
 					path,
+					&junk_files,
+					&walker,
 					move_deleted_files_to_trash,
 				)
+				.and_then(|_| {
+					remove_folder_if_contains_only_empty_folders(
+						path,
+						move_deleted_files_to_trash,
+					)
+				})
 			} else if move_deleted_files_to_trash {
 				trash::delete(path).map_err(|err| err.to_string())
+			} else if is_folder {
+				fs::remove_dir_all(path).map_err(|err| err.to_string())
 			} else {
 				fs::remove_file(path).map_err(|err| err.to_string())
 			};
@@ -68,5 +131,60 @@ fn delete_files_impl(options: Options) -> DeleteFilesResult {
 			acc.success_paths.append(&mut x.success_paths);
 			acc.errors.append(&mut x.errors);
 			acc
-		})
+		});
+
+	// Sidecars are looked for once their files are gone, so a sidecar still
+	// used by another file is kept
+	if delete_sidecars && !is_empty_folders_tool {
+		let deleted: Vec<_> =
+			result.success_paths.iter().map(PathBuf::from).collect();
+		let sidecars = get_orphaned_sidecars_of(
+			&deleted,
+			&SidecarMap::parse(&sidecar_map),
+		);
+
+		for sidecar in sidecars {
+			let fs_result = if move_deleted_files_to_trash {
+				trash::delete(&sidecar).map_err(|err| err.to_string())
+			} else {
+				fs::remove_file(&sidecar).map_err(|err| err.to_string())
+			};
+			let path_str = sidecar.to_string_lossy().to_string();
+
+			match fs_result {
+				Ok(_) => result.success_paths.push(path_str),
+				Err(err) => result
+					.errors
+					.push(format!("`{}` Failed, reason: {}", path_str, err)),
+			}
+		}
+	}
+
+	result
+}
+
+// Junk files are only removed when the folder holds nothing else, so a
+// folder that got real content since the scan is left untouched
+fn remove_junk_files(
+	path: &Path,
+	junk_files: &JunkFiles,
+	walker: &FileWalker,
+	move_deleted_files_to_trash: bool,
+) -> Result<(), String> {
+	if junk_files.is_empty() {
+		return Ok(());
+	}
+	let Some(junk) = junk_files.get_junk_files_in(path, walker) else {
+		return Ok(());
+	};
+
+	for file in junk {
+		if move_deleted_files_to_trash {
+			trash::delete(&file).map_err(|err| err.to_string())?;
+		} else {
+			fs::remove_file(&file).map_err(|err| err.to_string())?;
+		}
+	}
+
+	Ok(())
 }
@@ -0,0 +1,269 @@
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+	file_walker::{FileWalker, WalkedFile, get_modified_date},
+	progress::ProgressReporter,
+	scaner::spawn_scaner_thread,
+	settings::Settings,
+	state::{AppState, get_stop_flag_and_progress_tx},
+};
+
+// Children beyond this are only counted in `rest_size` and `rest_count`
+// of their parent, large flat folders would otherwise flood the view
+const MAX_CHILDREN: usize = 500;
+
+// A node has the `name`, `size` and `children` a treemap or sunburst view
+// expects, `children` is only filled up to the requested depth
+#[derive(Serialize, Clone)]
+pub struct DiskUsageNode {
+	path: String,
+	name: String,
+	is_dir: bool,
+	size: u64,
+	file_count: usize,
+	modified_date: u64,
+	has_children: bool,
+	children: Vec<DiskUsageNode>,
+	rest_size: u64,
+	rest_count: usize,
+}
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<DiskUsageNode>,
+	message: String,
+}
+
+#[derive(Default)]
+struct FolderUsage {
+	size: u64,
+	file_count: usize,
+	files: Vec<usize>,
+	folders: Vec<PathBuf>,
+}
+
+pub struct DiskUsageTree {
+	roots: Vec<PathBuf>,
+	folders: HashMap<PathBuf, FolderUsage>,
+	files: Vec<WalkedFile>,
+}
+
+pub fn scan_disk_usage(app: AppHandle, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+
+		// The size limits of the settings are for finding files, every file
+		// takes up space
+		let walker = FileWalker::from_settings(&settings).ignore_file_size();
+		let progress = ProgressReporter::new(&progress_tx, 0);
+		let files = walker.walk(&stop_flag, Some(&progress));
+		drop(progress);
+		let tree = DiskUsageTree::new(files, walker.included_directories());
+
+		let list = tree.get_nodes(None, 1).unwrap_or_default();
+		let message = format!(
+			"Found {} files taking {} bytes in {} folders",
+			tree.files.len(),
+			tree.files.iter().map(|file| file.size).sum::<u64>(),
+			tree.folders.len()
+		);
+
+		set_disk_usage_tree(&app, Some(tree));
+
+		app.emit(
+			"scan-result",
+			ScanResult {
+				cmd: "scan_disk_usage",
+				list,
+				message,
+			},
+		)
+		.unwrap();
+	});
+}
+
+// Returns the node of `path` with its children expanded `depth` levels,
+// or the included directories when `path` is `None`
+pub fn get_disk_usage_tree(
+	app: AppHandle,
+	path: Option<String>,
+	depth: usize,
+) -> Result<Vec<DiskUsageNode>, String> {
+	let tree = {
+		let state_mutex = app.state::<Mutex<AppState>>();
+		let state = state_mutex.lock().unwrap();
+		state.disk_usage_tree.clone()
+	};
+	let Some(tree) = tree else {
+		return Err("No disk usage in memory, scan first".to_string());
+	};
+
+	tree.get_nodes(path.as_deref().map(Path::new), depth)
+}
+
+impl DiskUsageTree {
+	fn new(files: Vec<WalkedFile>, included_directories: &[PathBuf]) -> Self {
+		let mut folders: HashMap<PathBuf, FolderUsage> = HashMap::new();
+		let mut roots: Vec<PathBuf> = included_directories
+			.iter()
+			.filter(|dir| {
+				!included_directories
+					.iter()
+					.any(|other| other != *dir && dir.starts_with(other))
+			})
+			.cloned()
+			.collect();
+		roots.sort();
+		roots.dedup();
+
+		for (i, file) in files.iter().enumerate() {
+			let Some(parent) = file.path.parent() else {
+				continue;
+			};
+			folders
+				.entry(parent.to_path_buf())
+				.or_default()
+				.files
+				.push(i);
+
+			let mut current = parent;
+			loop {
+				let folder = folders.entry(current.to_path_buf()).or_default();
+				folder.size += file.size;
+				folder.file_count += 1;
+
+				if roots.iter().any(|root| root == current) {
+					break;
+				}
+				let Some(next) = current.parent() else {
+					break;
+				};
+				if folders[current].file_count == 1 {
+					folders
+						.entry(next.to_path_buf())
+						.or_default()
+						.folders
+						.push(current.to_path_buf());
+				}
+				current = next;
+			}
+		}
+
+		DiskUsageTree {
+			roots,
+			folders,
+			files,
+		}
+	}
+
+	fn get_nodes(
+		&self,
+		path: Option<&Path>,
+		depth: usize,
+	) -> Result<Vec<DiskUsageNode>, String> {
+		let Some(path) = path else {
+			return Ok(self
+				.roots
+				.iter()
+				.map(|root| self.folder_node(root, depth))
+				.collect());
+		};
+
+		if self.folders.contains_key(path) {
+			return Ok(vec![self.folder_node(path, depth)]);
+		}
+		self.files
+			.iter()
+			.find(|file| file.path == path)
+			.map(|file| vec![file_node(file)])
+			.ok_or_else(|| {
+				format!("`{}` is not in the scanned tree", path.display())
+			})
+	}
+
+	fn folder_node(&self, path: &Path, depth: usize) -> DiskUsageNode {
+		let folder = self.folders.get(path);
+		let mut node = DiskUsageNode {
+			path: path.to_string_lossy().to_string(),
+			name: path
+				.file_name()
+				.map(|name| name.to_string_lossy().to_string())
+				.unwrap_or_else(|| path.to_string_lossy().to_string()),
+			is_dir: true,
+			size: folder.map(|folder| folder.size).unwrap_or_default(),
+			file_count: folder
+				.map(|folder| folder.file_count)
+				.unwrap_or_default(),
+			modified_date: fs::metadata(path)
+				.map(|metadata| get_modified_date(&metadata))
+				.unwrap_or_default(),
+			has_children: folder.is_some_and(|folder| {
+				!folder.files.is_empty() || !folder.folders.is_empty()
+			}),
+			children: vec![],
+			rest_size: 0,
+			rest_count: 0,
+		};
+
+		let Some(folder) = folder else {
+			return node;
+		};
+		if depth == 0 {
+			return node;
+		}
+
+		let mut children: Vec<_> = folder
+			.folders
+			.iter()
+			.map(|child| self.folder_node(child, depth - 1))
+			.chain(folder.files.iter().map(|i| file_node(&self.files[*i])))
+			.collect();
+		children.sort_by(|a, b| {
+			b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name))
+		});
+
+		if children.len() > MAX_CHILDREN {
+			for rest in children.drain(MAX_CHILDREN..) {
+				node.rest_size += rest.size;
+				node.rest_count += 1;
+			}
+		}
+		node.children = children;
+
+		node
+	}
+}
+
+fn file_node(file: &WalkedFile) -> DiskUsageNode {
+	DiskUsageNode {
+		path: file.path.to_string_lossy().to_string(),
+		name: file
+			.path
+			.file_name()
+			.map(|name| name.to_string_lossy().to_string())
+			.unwrap_or_default(),
+		is_dir: false,
+		size: file.size,
+		file_count: 1,
+		modified_date: file.modified_date,
+		has_children: false,
+		children: vec![],
+		rest_size: 0,
+		rest_count: 0,
+	}
+}
+
+fn set_disk_usage_tree(app: &AppHandle, tree: Option<DiskUsageTree>) {
+	let state_mutex = app.state::<Mutex<AppState>>();
+	let mut state = state_mutex.lock().unwrap();
+	state.disk_usage_tree = tree.map(Arc::new);
+}
@@ -1,3 +1,10 @@
+use std::{
+	collections::{HashMap, HashSet},
+	ops::RangeInclusive,
+	path::{Path, PathBuf},
+	sync::{Arc, atomic::AtomicBool},
+};
+
 use czkawka_core::{
 	common::{
 		model::{CheckingMethod, HashType},
@@ -14,15 +21,40 @@ use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 
 use crate::{
+	archive::{hash_archive_members, is_archive, read_archive_members},
+	file_hash::{get_full_hashes, get_hash_type},
+	file_walker::{FileWalker, WalkedFile},
+	progress::ProgressReporter,
 	scaner::{set_scaner_common_settings, spawn_scaner_thread},
 	settings::Settings,
 	state::get_stop_flag_and_progress_tx,
 };
 
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum ArchiveStatus {
+	None,
+	// A virtual entry for a file inside an archive, it can't be deleted
+	Member,
+	// A loose file that is also kept in an archive
+	Redundant,
+}
+
+#[derive(Serialize, Clone)]
+struct CustomDuplicateEntry {
+	path: String,
+	modified_date: u64,
+	size: u64,
+	hash: String,
+	archive_status: ArchiveStatus,
+}
+
+type DuplicateGroup = (Option<CustomDuplicateEntry>, Vec<CustomDuplicateEntry>);
+
 #[derive(Serialize, Clone)]
 struct ScanResult {
 	cmd: &'static str,
-	list: Vec<(Option<DuplicateEntry>, Vec<DuplicateEntry>)>,
+	list: Vec<DuplicateGroup>,
 	message: String,
 }
 
@@ -30,18 +62,21 @@ pub fn scan_duplicate_files(app: AppHandle, settings: Settings) {
 	spawn_scaner_thread(move || {
 		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
 
-		let hash_type =
-			match settings.duplicates_sub_available_hash_type.as_ref() {
-				"CRC32" => HashType::Crc32,
-				"XXH3" => HashType::Xxh3,
-				_ => HashType::Blake3,
-			};
+		let hash_type = get_hash_type(&settings);
 		let check_method = match settings.duplicates_sub_check_method.as_ref() {
 			"Size" => CheckingMethod::Size,
 			"Name" => CheckingMethod::Name,
 			"SizeAndName" => CheckingMethod::SizeName,
 			_ => CheckingMethod::Hash,
 		};
+		// Archive members are matched by their content, which only makes
+		// sense when duplicates are compared by content
+		let archive_walker = (settings.duplicates_sub_scan_archives
+			&& matches!(check_method, CheckingMethod::Hash))
+		.then(|| FileWalker::from_settings(&settings).ignore_file_size());
+		let size_range = settings.minimum_file_size as u64 * 1000
+			..=settings.maximum_file_size as u64 * 1000;
+
 		let mut scaner = DuplicateFinder::new(DuplicateFinderParameters::new(
 			check_method,
 			hash_type,
@@ -144,9 +179,32 @@ pub fn scan_duplicate_files(app: AppHandle, settings: Settings) {
 			}
 		}
 
+		let mut list: Vec<DuplicateGroup> = list
+			.into_iter()
+			.map(|(original, other)| {
+				(
+					original.as_ref().map(convert_duplicate_entry),
+					other.iter().map(convert_duplicate_entry).collect(),
+				)
+			})
+			.collect();
+
+		let redundant_count = archive_walker.map(|walker| {
+			let progress = ProgressReporter::new(&progress_tx, 1);
+			let files = walker.walk(&stop_flag, Some(&progress));
+			add_archive_members(
+				&mut list,
+				&files,
+				&size_range,
+				hash_type,
+				&stop_flag,
+				&progress,
+			)
+		});
+
 		for (_, vec) in &mut list {
 			vec.par_sort_unstable_by(|a, b| {
-				split_path_compare(a.path.as_path(), b.path.as_path())
+				split_path_compare(Path::new(&a.path), Path::new(&b.path))
 			});
 		}
 
@@ -155,6 +213,12 @@ pub fn scan_duplicate_files(app: AppHandle, settings: Settings) {
 			list.len(),
 			message
 		);
+		if let Some(redundant_count) = redundant_count {
+			message.push_str(&format!(
+				"\n{} loose files are also kept in archives",
+				redundant_count
+			));
+		}
 
 		app.emit(
 			"scan-result",
@@ -170,6 +234,190 @@ pub fn scan_duplicate_files(app: AppHandle, settings: Settings) {
 	});
 }
 
+fn convert_duplicate_entry(entry: &DuplicateEntry) -> CustomDuplicateEntry {
+	CustomDuplicateEntry {
+		path: entry.path.to_string_lossy().to_string(),
+		modified_date: entry.modified_date,
+		size: entry.size,
+		hash: entry.hash.clone(),
+		archive_status: ArchiveStatus::None,
+	}
+}
+
+// Adds the members of the archives among `files` to the groups of loose
+// files with the same content, members without a matching group form new
+// groups. Members with the size of a loose file are hashed like it, the
+// others are only grouped with each other by their size and CRC. Returns
+// the number of loose files that are also in an archive
+fn add_archive_members(
+	list: &mut Vec<DuplicateGroup>,
+	files: &[WalkedFile],
+	size_range: &RangeInclusive<u64>,
+	hash_type: HashType,
+	stop_flag: &Arc<AtomicBool>,
+	progress: &ProgressReporter,
+) -> usize {
+	let archive_members: Vec<_> = files
+		.par_iter()
+		.filter(|file| is_archive(&file.path))
+		.flat_map_iter(|archive| {
+			read_archive_members(&archive.path, stop_flag)
+				.unwrap_or_default()
+				.into_iter()
+				.filter(|member| {
+					member.size > 0 && size_range.contains(&member.size)
+				})
+				.map(move |member| (archive, member))
+		})
+		.collect();
+	if archive_members.is_empty() {
+		return 0;
+	}
+
+	// Files czkawka grouped keep their hash, the others are looked up in
+	// its hash cache before they are read
+	let member_sizes: HashSet<u64> = archive_members
+		.iter()
+		.map(|(_, member)| member.size)
+		.collect();
+	let known_hashes: HashMap<&str, &str> = list
+		.iter()
+		.flat_map(|(original, other)| original.iter().chain(other))
+		.map(|entry| (entry.path.as_str(), entry.hash.as_str()))
+		.collect();
+	let (known, unknown): (Vec<_>, Vec<_>) = files
+		.iter()
+		.filter(|file| {
+			size_range.contains(&file.size)
+				&& member_sizes.contains(&file.size)
+				&& !is_archive(&file.path)
+		})
+		.partition(|file| {
+			known_hashes.contains_key(&*file.path.to_string_lossy())
+		});
+	let unknown_hashes =
+		get_full_hashes(&unknown, hash_type, stop_flag, Some(progress));
+
+	let mut loose_keys: HashMap<&PathBuf, (u64, String)> = HashMap::new();
+	for file in known {
+		let hash = known_hashes[&*file.path.to_string_lossy()];
+		loose_keys.insert(&file.path, (file.size, hash.to_string()));
+	}
+	for (file, hash) in unknown.into_iter().zip(unknown_hashes) {
+		if let Some(hash) = hash {
+			loose_keys.insert(&file.path, (file.size, hash));
+		}
+	}
+	let loose_sizes: HashSet<u64> =
+		loose_keys.values().map(|(size, _)| *size).collect();
+
+	let mut to_hash: HashMap<&Path, HashSet<String>> = HashMap::new();
+	for (archive, member) in &archive_members {
+		if loose_sizes.contains(&member.size) {
+			to_hash
+				.entry(archive.path.as_path())
+				.or_default()
+				.insert(member.path.clone());
+		}
+	}
+	let member_hashes: HashMap<String, String> = to_hash
+		.into_par_iter()
+		.flat_map_iter(|(archive_path, member_paths)| {
+			hash_archive_members(
+				archive_path,
+				&member_paths,
+				hash_type,
+				stop_flag,
+			)
+			.unwrap_or_default()
+		})
+		.collect();
+
+	let mut members: HashMap<(u64, String), Vec<CustomDuplicateEntry>> =
+		HashMap::new();
+	for (archive, member) in archive_members {
+		let hash = if loose_sizes.contains(&member.size) {
+			// Not hashed, so it can't be confirmed
+			let Some(hash) = member_hashes.get(&member.path) else {
+				continue;
+			};
+			hash.clone()
+		} else {
+			format!("{:08x}", member.crc)
+		};
+		members
+			.entry((member.size, hash.clone()))
+			.or_default()
+			.push(CustomDuplicateEntry {
+				path: member.path,
+				modified_date: archive.modified_date,
+				size: member.size,
+				hash,
+				archive_status: ArchiveStatus::Member,
+			});
+	}
+
+	let mut redundant_count = 0;
+	let mut grouped: HashSet<&Path> = HashSet::new();
+	for (original, other) in list.iter_mut() {
+		let key = original
+			.iter()
+			.chain(other.iter())
+			.find_map(|entry| loose_keys.get(&PathBuf::from(&entry.path)));
+		let Some(group_members) = key.and_then(|key| members.remove(key))
+		else {
+			continue;
+		};
+		for entry in original.iter_mut().chain(other.iter_mut()) {
+			entry.archive_status = ArchiveStatus::Redundant;
+			redundant_count += 1;
+		}
+		other.extend(group_members);
+	}
+	for (original, other) in list.iter() {
+		for entry in original.iter().chain(other.iter()) {
+			grouped.insert(Path::new(&entry.path));
+		}
+	}
+
+	let mut loose_by_key: HashMap<&(u64, String), Vec<&WalkedFile>> =
+		HashMap::new();
+	for file in files
+		.iter()
+		.filter(|file| !grouped.contains(file.path.as_path()))
+	{
+		if let Some(key) = loose_keys.get(&file.path) {
+			loose_by_key.entry(key).or_default().push(file);
+		}
+	}
+
+	let mut new_groups = vec![];
+	for (key, group_members) in members {
+		let loose: Vec<_> = loose_by_key
+			.remove(&key)
+			.unwrap_or_default()
+			.into_iter()
+			.map(|file| CustomDuplicateEntry {
+				path: file.path.to_string_lossy().to_string(),
+				modified_date: file.modified_date,
+				size: file.size,
+				hash: key.1.clone(),
+				archive_status: ArchiveStatus::Redundant,
+			})
+			.collect();
+		if loose.len() + group_members.len() < 2 {
+			continue;
+		}
+		redundant_count += loose.len();
+		let mut items = loose;
+		items.extend(group_members);
+		new_groups.push((None, items));
+	}
+	list.extend(new_groups);
+
+	redundant_count
+}
+
 crate::gen_set_scaner_state_fn!(
 	duplication_state,
 	czkawka_core::tools::duplicate::DuplicateFinder
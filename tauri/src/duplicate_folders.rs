@@ -0,0 +1,637 @@
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	fs,
+	path::{Path, PathBuf},
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
+use czkawka_core::common::{model::HashType, split_path_compare};
+use rayon::prelude::*;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+	file_hash::{
+		get_full_hashes, get_hash_type, get_hash_type_name, get_prehashes,
+		hash_file, parse_hash_type,
+	},
+	file_walker::{FileWalker, Tree, WalkedFile, get_modified_date, walk_tree},
+	progress::ProgressReporter,
+	scaner::{ScanScope, spawn_scaner_thread},
+	settings::Settings,
+	state::get_stop_flag_and_progress_tx,
+};
+
+#[derive(Serialize, Clone)]
+struct CustomFolderEntry {
+	path: String,
+	size: u64,
+	file_count: usize,
+	modified_date: u64,
+	// Checked again before the folder is deleted or moved
+	fingerprint: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<(Option<CustomFolderEntry>, Vec<CustomFolderEntry>)>,
+	message: String,
+}
+
+#[derive(Default)]
+struct FolderInfo {
+	size: u64,
+	file_count: usize,
+	// Direct children by name, a file maps to its content token and a
+	// folder to its own path
+	files: Vec<(String, String)>,
+	folders: Vec<(String, PathBuf)>,
+}
+
+// Folders are compared by the names and content of everything in them,
+// the folder name itself is not part of it. Only folders the settings
+// include are reported
+pub fn scan_duplicate_folders(app: AppHandle, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let hash_type = get_hash_type(&settings);
+		let include_subsets = settings.duplicate_folders_sub_include_subsets;
+		let scope = ScanScope::from_settings(&settings);
+
+		let walker = FileWalker::from_settings(&settings);
+		let roots = walker.included_directories();
+		// Collecting the scanned folders and every entry below them, then
+		// prehashing and hashing
+		let progress = ProgressReporter::new(&progress_tx, 3);
+		let (scanned_folders, _) =
+			walker.walk_with_folders(&stop_flag, Some(&progress));
+		let tree = walk_tree(roots, &stop_flag, Some(&progress));
+		let tokens =
+			get_content_tokens(&tree.files, hash_type, &stop_flag, &progress);
+		drop(progress);
+
+		let folders = build_folders(&tree, &tokens);
+		let folder_ids = get_folder_ids(&folders);
+
+		let candidates: HashSet<&Path> = scanned_folders
+			.iter()
+			.chain(roots)
+			.map(PathBuf::as_path)
+			.filter(|path| {
+				folders
+					.get(*path)
+					.is_some_and(|folder| folder.file_count > 0)
+			})
+			.collect();
+
+		let mut by_id: HashMap<usize, Vec<&Path>> = HashMap::new();
+		for path in &candidates {
+			by_id.entry(folder_ids[*path]).or_default().push(path);
+		}
+		let is_duplicated = |path: &Path| {
+			candidates.contains(path) && by_id[&folder_ids[path]].len() > 1
+		};
+
+		// Children of duplicated folders are duplicated as well, only the
+		// outermost folders are reported
+		let mut raw_list: Vec<(Option<&Path>, Vec<&Path>)> = by_id
+			.values()
+			.filter(|paths| paths.len() > 1)
+			.filter_map(|paths| {
+				let paths: Vec<_> = paths
+					.iter()
+					.copied()
+					.filter(|path| !path.parent().is_some_and(is_duplicated))
+					.collect();
+				(paths.len() > 1).then_some(paths)
+			})
+			.filter_map(|paths| {
+				if !scope.use_reference() {
+					return Some((None, paths));
+				}
+				let (mut refs, others): (Vec<_>, Vec<_>) = paths
+					.into_iter()
+					.partition(|path| scope.is_referenced(path));
+				if refs.is_empty() || others.is_empty() {
+					return None;
+				}
+				Some((Some(refs.swap_remove(0)), others))
+			})
+			.collect();
+
+		let identical_count = raw_list.len();
+
+		if include_subsets {
+			raw_list.extend(
+				find_subsets(
+					&folders,
+					&folder_ids,
+					&|path| candidates.contains(path),
+					&is_duplicated,
+				)
+				.into_iter()
+				.map(|(superset, subsets)| (Some(superset), subsets)),
+			);
+		}
+
+		for (_, paths) in &mut raw_list {
+			paths.par_sort_unstable_by(|a, b| split_path_compare(a, b));
+		}
+
+		let subset_count = raw_list.len() - identical_count;
+		let message = if include_subsets {
+			format!(
+				"Found {} groups of identical folders and {} folders containing other ones",
+				identical_count, subset_count
+			)
+		} else {
+			format!("Found {} groups of identical folders", identical_count)
+		};
+
+		// Hashes of the scan are reused for the fingerprints, files that
+		// were never hashed are hashed now
+		let known_hashes: HashMap<&Path, &str> = tree
+			.files
+			.iter()
+			.zip(&tokens)
+			.filter(|(_, token)| !token.starts_with("unique:"))
+			.filter_map(|(file, token)| {
+				Some((file.path.as_path(), token.split_once(':')?.1))
+			})
+			.collect();
+
+		let to_custom = |path: &Path| {
+			let folder = &folders[path];
+			CustomFolderEntry {
+				path: path.to_string_lossy().to_string(),
+				size: folder.size,
+				file_count: folder.file_count,
+				modified_date: fs::metadata(path)
+					.map(|metadata| get_modified_date(&metadata))
+					.unwrap_or_default(),
+				fingerprint: get_folder_fingerprint(
+					path,
+					hash_type,
+					&known_hashes,
+					&stop_flag,
+				)
+				.unwrap_or_else(|err| {
+					log::info!(
+						"Failed to fingerprint `{}`: {}",
+						path.display(),
+						err
+					);
+					String::new()
+				}),
+			}
+		};
+		let list = raw_list
+			.into_par_iter()
+			.map(|(ref_item, items)| {
+				(
+					ref_item.map(to_custom),
+					items.into_iter().map(to_custom).collect(),
+				)
+			})
+			.collect::<Vec<_>>();
+
+		app.emit(
+			"scan-result",
+			ScanResult {
+				cmd: "scan_duplicate_folders",
+				list,
+				message,
+			},
+		)
+		.unwrap();
+	});
+}
+
+// Folders are only deleted or moved when everything in them is still what
+// the scan found
+pub fn check_folder_fingerprint(
+	path: &Path,
+	fingerprint: &str,
+) -> Result<(), String> {
+	let Some((hash_name, _)) = fingerprint.split_once(':') else {
+		return Err("No fingerprint from the scan, scan again".to_string());
+	};
+	let current = get_folder_fingerprint(
+		path,
+		parse_hash_type(hash_name),
+		&HashMap::new(),
+		&Arc::new(AtomicBool::new(false)),
+	)?;
+
+	if current != fingerprint {
+		return Err("The folder changed since the scan, scan again".to_string());
+	}
+
+	Ok(())
+}
+
+// A blake3 hash of the sorted relative path, kind, size and content hash of
+// every entry below `path`, prefixed with the name of the hash type
+fn get_folder_fingerprint(
+	path: &Path,
+	hash_type: HashType,
+	known_hashes: &HashMap<&Path, &str>,
+	stop_flag: &Arc<AtomicBool>,
+) -> Result<String, String> {
+	let tree = walk_tree(&[path.to_path_buf()], stop_flag, None);
+	if let Some(dir) = tree.unreadable.first() {
+		return Err(format!("Failed to read `{}`", dir.display()));
+	}
+	let relative = |entry: &Path| {
+		entry
+			.strip_prefix(path)
+			.unwrap_or(entry)
+			.to_string_lossy()
+			.to_string()
+	};
+
+	let hashes: Vec<_> = tree
+		.files
+		.par_iter()
+		.map(|file| match known_hashes.get(file.path.as_path()) {
+			Some(hash) => Ok(hash.to_string()),
+			None => hash_file(&file.path, hash_type, None, stop_flag),
+		})
+		.collect();
+
+	let mut lines = vec![];
+	for folder in &tree.folders {
+		if folder != path {
+			lines.push(format!("d\0{}", relative(folder)));
+		}
+	}
+	for (other, token) in &tree.others {
+		lines.push(format!("o\0{}\0{}", relative(other), token));
+	}
+	for (file, hash) in tree.files.iter().zip(hashes) {
+		let hash = hash.map_err(|err| {
+			format!("Failed to hash `{}`: {}", file.path.display(), err)
+		})?;
+		lines.push(format!(
+			"f\0{}\0{}\0{}",
+			relative(&file.path),
+			file.size,
+			hash
+		));
+	}
+	lines.sort_unstable();
+
+	let mut hasher = blake3::Hasher::new();
+	for line in &lines {
+		hasher.update(line.as_bytes());
+		hasher.update(b"\n");
+	}
+
+	Ok(format!(
+		"{}:{}",
+		get_hash_type_name(hash_type),
+		hasher.finalize().to_hex()
+	))
+}
+
+// Only files sharing size and prehash with another file are fully hashed,
+// every other file gets a token no other file can match
+fn get_content_tokens(
+	files: &[WalkedFile],
+	hash_type: HashType,
+	stop_flag: &Arc<AtomicBool>,
+	progress: &ProgressReporter,
+) -> Vec<String> {
+	let mut tokens: Vec<String> = files
+		.iter()
+		.map(|file| format!("unique:{}", file.path.display()))
+		.collect();
+
+	let mut by_size: BTreeMap<u64, Vec<usize>> = BTreeMap::new();
+	for (i, file) in files.iter().enumerate() {
+		by_size.entry(file.size).or_default().push(i);
+	}
+	let same_size: Vec<_> = by_size
+		.into_values()
+		.filter(|indexes| indexes.len() > 1)
+		.flatten()
+		.collect();
+
+	let prehashes = get_prehashes(
+		&same_size.iter().map(|i| &files[*i]).collect::<Vec<_>>(),
+		hash_type,
+		stop_flag,
+		Some(progress),
+	);
+	let mut by_prehash: HashMap<(u64, String), Vec<usize>> = HashMap::new();
+	for (i, prehash) in same_size.into_iter().zip(prehashes) {
+		if let Some(prehash) = prehash {
+			by_prehash
+				.entry((files[i].size, prehash))
+				.or_default()
+				.push(i);
+		}
+	}
+	let same_prehash: Vec<_> = by_prehash
+		.into_values()
+		.filter(|indexes| indexes.len() > 1)
+		.flatten()
+		.collect();
+
+	let hashes = get_full_hashes(
+		&same_prehash.iter().map(|i| &files[*i]).collect::<Vec<_>>(),
+		hash_type,
+		stop_flag,
+		Some(progress),
+	);
+	for (i, hash) in same_prehash.into_iter().zip(hashes) {
+		if let Some(hash) = hash {
+			tokens[i] = format!("{}:{}", files[i].size, hash);
+		}
+	}
+
+	tokens
+}
+
+fn build_folders(
+	tree: &Tree,
+	tokens: &[String],
+) -> HashMap<PathBuf, FolderInfo> {
+	let mut folders: HashMap<PathBuf, FolderInfo> = tree
+		.folders
+		.iter()
+		.map(|path| (path.clone(), FolderInfo::default()))
+		.collect();
+
+	for path in &tree.folders {
+		if let Some(parent) = path.parent()
+			&& let Some(folder) = folders.get_mut(parent)
+		{
+			folder.folders.push((file_name(path), path.clone()));
+		}
+	}
+	// A folder that can't be read never matches another one
+	for path in &tree.unreadable {
+		if let Some(folder) = folders.get_mut(path) {
+			folder.files.push((
+				String::new(),
+				format!("unreadable:{}", path.display()),
+			));
+		}
+	}
+	for (path, token) in &tree.others {
+		if let Some(parent) = path.parent()
+			&& let Some(folder) = folders.get_mut(parent)
+		{
+			folder.files.push((file_name(path), token.clone()));
+		}
+	}
+
+	for (file, token) in tree.files.iter().zip(tokens) {
+		if let Some(parent) = file.path.parent()
+			&& let Some(folder) = folders.get_mut(parent)
+		{
+			folder.files.push((file_name(&file.path), token.clone()));
+		}
+		for ancestor in file.path.ancestors().skip(1) {
+			let Some(folder) = folders.get_mut(ancestor) else {
+				break;
+			};
+			folder.size += file.size;
+			folder.file_count += 1;
+		}
+	}
+
+	folders
+}
+
+// Folders get the same id only when they hold the same tree. The children
+// lists are compared as a whole, so there are no hash collisions
+fn get_folder_ids(
+	folders: &HashMap<PathBuf, FolderInfo>,
+) -> HashMap<PathBuf, usize> {
+	let mut paths: Vec<_> = folders.keys().collect();
+	paths.sort_by_key(|path| std::cmp::Reverse(path.components().count()));
+
+	let mut interned: HashMap<Vec<(String, String)>, usize> = HashMap::new();
+	let mut ids: HashMap<PathBuf, usize> = HashMap::new();
+
+	for path in paths {
+		let folder = &folders[path];
+		let mut children: Vec<(String, String)> = folder
+			.files
+			.iter()
+			.cloned()
+			.chain(folder.folders.iter().map(|(name, child)| {
+				(name.clone(), format!("folder:{}", ids[child]))
+			}))
+			.collect();
+		children.sort_unstable();
+
+		let next_id = interned.len();
+		let id = *interned.entry(children).or_insert(next_id);
+		ids.insert(path.clone(), id);
+	}
+
+	ids
+}
+
+// A folder is a subset of another one when everything in it exists at the
+// same relative path, with the same content, in the other folder. Folders
+// are compared child by child, so nothing is kept per descendant
+fn find_subsets<'a>(
+	folders: &'a HashMap<PathBuf, FolderInfo>,
+	folder_ids: &HashMap<PathBuf, usize>,
+	is_candidate: &dyn Fn(&Path) -> bool,
+	is_duplicated: &dyn Fn(&Path) -> bool,
+) -> Vec<(&'a Path, Vec<&'a Path>)> {
+	// Supersets are looked for among the folders sharing a direct child,
+	// the whole trees are compared after that
+	let mut postings: HashMap<(&str, &str), Vec<&Path>> = HashMap::new();
+	for (path, folder) in folders {
+		for item in get_child_items(folder) {
+			postings.entry(item).or_default().push(path);
+		}
+	}
+
+	let mut paths: Vec<&Path> = folders.keys().map(PathBuf::as_path).collect();
+	paths.sort_by_key(|path| path.components().count());
+
+	let mut known_subsets: HashMap<(usize, usize), bool> = HashMap::new();
+	let mut covered: HashSet<&Path> = HashSet::new();
+	let mut groups: BTreeMap<&Path, Vec<&Path>> = BTreeMap::new();
+
+	for path in paths {
+		if !is_candidate(path)
+			|| is_duplicated(path)
+			|| path.parent().is_some_and(|parent| covered.contains(parent))
+		{
+			continue;
+		}
+		let Some(rarest) = get_child_items(&folders[path])
+			.min_by_key(|item| postings[item].len())
+		else {
+			continue;
+		};
+
+		let superset = postings[&rarest].iter().copied().find(|candidate| {
+			*candidate != path
+				&& is_candidate(candidate)
+				&& !candidate.starts_with(path)
+				&& !path.starts_with(candidate)
+				&& folder_ids[*candidate] != folder_ids[path]
+				&& is_subset(
+					folders,
+					folder_ids,
+					path,
+					candidate,
+					&mut known_subsets,
+				)
+		});
+
+		if let Some(superset) = superset {
+			covered.insert(path);
+			groups.entry(superset).or_default().push(path);
+		}
+	}
+
+	groups.into_iter().collect()
+}
+
+// Direct children by name, with the content token of files
+fn get_child_items(folder: &FolderInfo) -> impl Iterator<Item = (&str, &str)> {
+	folder
+		.files
+		.iter()
+		.map(|(name, token)| (name.as_str(), token.as_str()))
+		.chain(
+			folder
+				.folders
+				.iter()
+				.map(|(name, _)| (name.as_str(), "folder")),
+		)
+}
+
+// Results are kept by folder id, folders with the same id hold the same tree
+fn is_subset(
+	folders: &HashMap<PathBuf, FolderInfo>,
+	folder_ids: &HashMap<PathBuf, usize>,
+	path: &Path,
+	other: &Path,
+	known_subsets: &mut HashMap<(usize, usize), bool>,
+) -> bool {
+	let key = (folder_ids[path], folder_ids[other]);
+	if key.0 == key.1 {
+		return true;
+	}
+	if let Some(known) = known_subsets.get(&key) {
+		return *known;
+	}
+
+	let (folder, other) = (&folders[path], &folders[other]);
+	let other_files: HashSet<_> = other.files.iter().collect();
+	let other_folders: HashMap<_, _> = other
+		.folders
+		.iter()
+		.map(|(name, path)| (name, path))
+		.collect();
+
+	let result = folder.files.iter().all(|file| other_files.contains(file))
+		&& folder.folders.iter().all(|(name, child)| {
+			other_folders.get(name).is_some_and(|other_child| {
+				is_subset(
+					folders,
+					folder_ids,
+					child,
+					other_child,
+					known_subsets,
+				)
+			})
+		});
+	known_subsets.insert(key, result);
+	result
+}
+
+fn file_name(path: &Path) -> String {
+	path.file_name()
+		.map(|name| name.to_string_lossy().to_string())
+		.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn tree_of(files: &[(&str, &str)]) -> (Tree, Vec<String>) {
+		let mut tree = Tree::default();
+		let mut tokens = vec![];
+		for (path, token) in files {
+			let path = PathBuf::from(path);
+			for ancestor in path.ancestors().skip(1) {
+				if ancestor.as_os_str().is_empty() {
+					break;
+				}
+				tree.folders.push(ancestor.to_path_buf());
+			}
+			tree.files.push(WalkedFile {
+				path,
+				size: token.len() as u64,
+				modified_date: 0,
+			});
+			tokens.push(token.to_string());
+		}
+		tree.folders.sort_unstable();
+		tree.folders.dedup();
+		(tree, tokens)
+	}
+
+	#[test]
+	fn subsets_compare_whole_trees() {
+		let (tree, tokens) = tree_of(&[
+			("/r/a/1.txt", "t1"),
+			("/r/a/sub/3.txt", "t3"),
+			("/r/b/1.txt", "t1"),
+			("/r/b/2.txt", "t2"),
+			("/r/b/sub/3.txt", "t3"),
+			("/r/b/sub/4.txt", "t4"),
+			("/r/c/1.txt", "t1"),
+			("/r/c/sub/3.txt", "t9"),
+		]);
+		let folders = build_folders(&tree, &tokens);
+		let folder_ids = get_folder_ids(&folders);
+		let roots = [Path::new("/"), Path::new("/r")];
+
+		let subsets = find_subsets(
+			&folders,
+			&folder_ids,
+			&|path| !roots.contains(&path),
+			&|_| false,
+		);
+
+		assert_eq!(subsets, vec![(Path::new("/r/b"), vec![Path::new("/r/a")])]);
+	}
+
+	#[test]
+	fn same_trees_get_the_same_id() {
+		let (tree, tokens) = tree_of(&[
+			("/r/a/sub/1.txt", "t1"),
+			("/r/b/sub/1.txt", "t1"),
+			("/r/c/1.txt", "t1"),
+		]);
+		let folders = build_folders(&tree, &tokens);
+		let folder_ids = get_folder_ids(&folders);
+
+		assert_eq!(
+			folder_ids[Path::new("/r/a")],
+			folder_ids[Path::new("/r/b")]
+		);
+		assert_ne!(
+			folder_ids[Path::new("/r/a")],
+			folder_ids[Path::new("/r/c")]
+		);
+	}
+}
@@ -1,3 +1,13 @@
+use std::{
+	fs::File,
+	io::Read,
+	path::Path,
+	sync::{
+		Mutex,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
 use czkawka_core::{
 	common::{
 		model::FileEntry, split_path_compare, tool_data::CommonData,
@@ -7,39 +17,141 @@ use czkawka_core::{
 };
 use rayon::prelude::*;
 use serde::Serialize;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 
 use crate::{
+	file_walker::{FileWalker, WalkedFile},
+	progress::ProgressReporter,
 	scaner::{set_scaner_common_settings, spawn_scaner_thread},
 	settings::Settings,
-	state::get_stop_flag_and_progress_tx,
+	state::{AppState, get_stop_flag_and_progress_tx},
 };
 
+const BUFFER_SIZE: usize = 64 * 1024;
+
+// Containers are only parsed when the whole file fits in the first read
+const MAX_CONTAINER_SIZE: u64 = BUFFER_SIZE as u64;
+
+// End of central directory record, the only record of an empty zip
+const EMPTY_ZIP_SIGNATURE: &[u8] = b"PK\x05\x06";
+const EMPTY_ZIP_LEN: usize = 22;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+// UTF-32 LE has to be checked before UTF-16 LE, it starts with the same bytes
+const BOMS: &[&[u8]] = &[
+	UTF8_BOM,
+	&[0x00, 0x00, 0xFE, 0xFF],
+	&[0xFF, 0xFE, 0x00, 0x00],
+	&[0xFE, 0xFF],
+	&[0xFF, 0xFE],
+];
+
+#[derive(Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+enum EmptyReason {
+	ZeroBytes,
+	Whitespace,
+	Bom,
+	Nul,
+	EmptyArchive,
+	EmptyJson,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CustomEmptyFileEntry {
+	path: String,
+	size: u64,
+	modified_date: u64,
+	reason: EmptyReason,
+}
+
+impl CustomEmptyFileEntry {
+	pub fn to_line(&self) -> String {
+		format!("{:?} - {:?}", self.path, self.reason)
+	}
+}
+
 #[derive(Serialize, Clone)]
 struct ScanResult {
 	cmd: &'static str,
-	list: Vec<FileEntry>,
+	list: Vec<CustomEmptyFileEntry>,
 	message: String,
 }
 
+struct ContentClasses {
+	whitespace: bool,
+	bom: bool,
+	nul: bool,
+	empty_container: bool,
+}
+
 pub fn scan_empty_files(app: AppHandle, settings: Settings) {
 	spawn_scaner_thread(move || {
 		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
 
+		let classes = ContentClasses {
+			whitespace: settings.empty_files_sub_whitespace,
+			bom: settings.empty_files_sub_bom,
+			nul: settings.empty_files_sub_nul,
+			empty_container: settings.empty_files_sub_empty_container,
+		};
+		// Effectively empty files are looked for with the same common
+		// settings, except for the size limits czkawka ignores for this tool
+		let walker = settings
+			.empty_files_sub_effectively_empty
+			.then(|| FileWalker::from_settings(&settings).ignore_file_size());
+
 		let mut scaner = EmptyFiles::new();
 
 		set_scaner_common_settings(&mut scaner, settings);
 
 		scaner.search(&stop_flag, Some(&progress_tx));
 
-		let mut list = scaner.get_empty_files().clone();
+		let mut list: Vec<_> = scaner
+			.get_empty_files()
+			.iter()
+			.map(convert_file_entry)
+			.collect();
 		let mut message = scaner.get_text_messages().create_messages_text();
+		let empty_count = list.len();
+
+		if let Some(walker) = walker {
+			let progress = ProgressReporter::new(&progress_tx, 0);
+			let files = walker.walk(&stop_flag, Some(&progress));
+			drop(progress);
+			list.extend(
+				files
+					.par_iter()
+					.filter_map(|file| {
+						if stop_flag.load(Ordering::Relaxed) || file.size == 0 {
+							return None;
+						}
+						let reason = get_empty_reason(
+							&file.path, file.size, &classes, &stop_flag,
+						)?;
+						Some(convert_walked_file(file, reason))
+					})
+					.collect::<Vec<_>>(),
+			);
+		}
 
 		list.par_sort_unstable_by(|a, b| {
-			split_path_compare(a.path.as_path(), b.path.as_path())
+			split_path_compare(Path::new(&a.path), Path::new(&b.path))
 		});
 
-		message = format!("Found {} empty files\n{}", list.len(), message);
+		message = if list.len() > empty_count {
+			format!(
+				"Found {} empty files and {} effectively empty files\n{}",
+				empty_count,
+				list.len() - empty_count,
+				message
+			)
+		} else {
+			format!("Found {} empty files\n{}", list.len(), message)
+		};
+
+		set_empty_files_entries(&app, list.clone());
 
 		app.emit(
 			"scan-result",
@@ -55,7 +167,169 @@ pub fn scan_empty_files(app: AppHandle, settings: Settings) {
 	});
 }
 
+fn convert_file_entry(file: &FileEntry) -> CustomEmptyFileEntry {
+	CustomEmptyFileEntry {
+		path: file.path.to_string_lossy().to_string(),
+		size: file.size,
+		modified_date: file.modified_date,
+		reason: EmptyReason::ZeroBytes,
+	}
+}
+
+fn convert_walked_file(
+	file: &WalkedFile,
+	reason: EmptyReason,
+) -> CustomEmptyFileEntry {
+	CustomEmptyFileEntry {
+		path: file.path.to_string_lossy().to_string(),
+		size: file.size,
+		modified_date: file.modified_date,
+		reason,
+	}
+}
+
+// Most files are ruled out by their first bytes, only files that keep
+// looking empty are read to the end
+fn get_empty_reason(
+	path: &Path,
+	size: u64,
+	classes: &ContentClasses,
+	stop_flag: &AtomicBool,
+) -> Option<EmptyReason> {
+	let mut file = File::open(path).ok()?;
+	let mut buf = vec![0; BUFFER_SIZE];
+	let len = read_chunk(&mut file, &mut buf)?;
+	let head = &buf[..len];
+
+	let bom = BOMS.iter().find(|bom| head.starts_with(bom));
+	if classes.bom && bom.is_some_and(|bom| size == bom.len() as u64) {
+		return Some(EmptyReason::Bom);
+	}
+
+	if classes.empty_container && size <= MAX_CONTAINER_SIZE {
+		if is_empty_zip(head) {
+			return Some(EmptyReason::EmptyArchive);
+		}
+		// `{}` or `[]` may well be meaningful content of other text files
+		if has_json_extension(path)
+			&& is_empty_json(head.strip_prefix(UTF8_BOM).unwrap_or(head))
+		{
+			return Some(EmptyReason::EmptyJson);
+		}
+	}
+
+	// Whitespace after a BOM only makes sense for UTF-8, other encodings
+	// interleave NUL bytes
+	let mut all_nul = classes.nul && bom.is_none();
+	let mut all_whitespace =
+		classes.whitespace && bom.is_none_or(|bom| *bom == UTF8_BOM);
+	let mut chunk = &head[bom.map(|bom| bom.len()).unwrap_or_default()..];
+
+	loop {
+		all_nul = all_nul && chunk.iter().all(|byte| *byte == 0);
+		all_whitespace =
+			all_whitespace && chunk.iter().all(u8::is_ascii_whitespace);
+		if !all_nul && !all_whitespace {
+			return None;
+		}
+		if stop_flag.load(Ordering::Relaxed) {
+			return None;
+		}
+
+		let len = read_chunk(&mut file, &mut buf)?;
+		if len == 0 {
+			break;
+		}
+		chunk = &buf[..len];
+	}
+
+	if all_nul {
+		Some(EmptyReason::Nul)
+	} else {
+		Some(EmptyReason::Whitespace)
+	}
+}
+
+// Fills the buffer as far as possible, a short count means end of file
+fn read_chunk(file: &mut File, buf: &mut [u8]) -> Option<usize> {
+	let mut len = 0;
+	while len < buf.len() {
+		match file.read(&mut buf[len..]) {
+			Ok(0) => break,
+			Ok(n) => len += n,
+			Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+			Err(_) => return None,
+		}
+	}
+	Some(len)
+}
+
+// The record ends with the length of the archive comment, nothing may follow
+// the comment
+fn is_empty_zip(content: &[u8]) -> bool {
+	if content.len() < EMPTY_ZIP_LEN
+		|| !content.starts_with(EMPTY_ZIP_SIGNATURE)
+	{
+		return false;
+	}
+	let entry_count = u16::from_le_bytes([content[10], content[11]]);
+	let comment_len = u16::from_le_bytes([content[20], content[21]]) as usize;
+	entry_count == 0 && content.len() == EMPTY_ZIP_LEN + comment_len
+}
+
+fn has_json_extension(path: &Path) -> bool {
+	path.extension()
+		.is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+// `{}`, `[]`, `null` and `""`, with any whitespace around them
+fn is_empty_json(content: &[u8]) -> bool {
+	let trimmed = content.trim_ascii();
+	if !trimmed.starts_with(b"{")
+		&& !trimmed.starts_with(b"[")
+		&& trimmed != b"null"
+		&& trimmed != b"\"\""
+	{
+		return false;
+	}
+	match serde_json::from_slice::<serde_json::Value>(trimmed) {
+		Ok(serde_json::Value::Object(map)) => map.is_empty(),
+		Ok(serde_json::Value::Array(list)) => list.is_empty(),
+		Ok(serde_json::Value::Null) => true,
+		Ok(serde_json::Value::String(s)) => s.is_empty(),
+		_ => false,
+	}
+}
+
+fn set_empty_files_entries(
+	app: &AppHandle,
+	entries: Vec<CustomEmptyFileEntry>,
+) {
+	let state_mutex = app.state::<Mutex<AppState>>();
+	let mut state = state_mutex.lock().unwrap();
+	state.empty_files_entries = Some(entries);
+}
+
 crate::gen_set_scaner_state_fn!(
 	empty_files_state,
 	czkawka_core::tools::empty_files::EmptyFiles
 );
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn empty_json_is_recognized() {
+		for content in [&b" {} \n"[..], b"[ ]", b"null", b"\"\"", b"{\n}"] {
+			assert!(is_empty_json(content), "{:?}", content);
+		}
+	}
+
+	#[test]
+	fn json_with_content_is_not_empty() {
+		for content in [&b"{\"a\": 1}"[..], b"[0]", b"0", b"\"a\"", b"{", b""] {
+			assert!(!is_empty_json(content), "{:?}", content);
+		}
+	}
+}
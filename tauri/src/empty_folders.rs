@@ -1,3 +1,9 @@
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	path::{Path, PathBuf},
+};
+
 use czkawka_core::{
 	common::{split_path_compare, tool_data::CommonData, traits::Search},
 	tools::empty_folder::EmptyFolder,
@@ -7,6 +13,8 @@ use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 
 use crate::{
+	file_walker::{FileWalker, Tree, get_modified_date, matches_wildcard},
+	progress::ProgressReporter,
 	scaner::{set_scaner_common_settings, spawn_scaner_thread},
 	settings::Settings,
 	state::get_stop_flag_and_progress_tx,
@@ -16,6 +24,8 @@ use crate::{
 struct CustomFolderEntry {
 	path: String,
 	modified_date: u64,
+	// Ignorable files that would be deleted with the folder
+	junk_file_count: usize,
 }
 
 #[derive(Serialize, Clone)]
@@ -25,37 +35,142 @@ struct ScanResult {
 	message: String,
 }
 
+// Files like `.DS_Store` or `Thumbs.db` that don't keep a folder from being
+// empty
+pub struct JunkFiles {
+	patterns: Vec<String>,
+	zero_byte: bool,
+}
+
+impl JunkFiles {
+	// `patterns` is a comma separated list of file names, `*` is supported
+	pub fn parse(patterns: &str, zero_byte: bool) -> Self {
+		JunkFiles {
+			patterns: patterns
+				.split(',')
+				.map(|pattern| pattern.trim().to_lowercase())
+				.filter(|pattern| !pattern.is_empty())
+				.collect(),
+			zero_byte,
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.patterns.is_empty() && !self.zero_byte
+	}
+
+	fn is_junk(&self, path: &Path, size: u64) -> bool {
+		if self.zero_byte && size == 0 {
+			return true;
+		}
+		let name = path
+			.file_name()
+			.unwrap_or_default()
+			.to_string_lossy()
+			.to_lowercase();
+		self.patterns
+			.iter()
+			.any(|pattern| matches_wildcard(pattern, &name))
+	}
+
+	// Returns the junk files inside `dir`, or `None` when it holds anything
+	// else. Symlinks are never junk and excluded paths are left alone, so
+	// their folder is kept
+	pub fn get_junk_files_in(
+		&self,
+		dir: &Path,
+		walker: &FileWalker,
+	) -> Option<Vec<PathBuf>> {
+		let mut junk_files = vec![];
+
+		for entry in fs::read_dir(dir).ok()? {
+			let entry = entry.ok()?;
+			let path = entry.path();
+			if walker.is_excluded(&path) {
+				return None;
+			}
+			let metadata = fs::symlink_metadata(&path).ok()?;
+
+			if metadata.is_dir() {
+				junk_files.extend(self.get_junk_files_in(&path, walker)?);
+			} else if metadata.is_file() && self.is_junk(&path, metadata.len())
+			{
+				junk_files.push(path);
+			} else {
+				return None;
+			}
+		}
+
+		Some(junk_files)
+	}
+}
+
 pub fn scan_empty_folders(app: AppHandle, settings: Settings) {
 	spawn_scaner_thread(move || {
 		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
 
+		let junk_files = JunkFiles::parse(
+			&settings.empty_folders_sub_ignorable_files,
+			settings.empty_folders_sub_ignore_zero_byte_files,
+		);
+		let walker = FileWalker::from_settings(&settings).ignore_file_size();
+
 		let mut scaner = EmptyFolder::new();
 
 		set_scaner_common_settings(&mut scaner, settings);
 
 		scaner.search(&stop_flag, Some(&progress_tx));
 
-		let mut raw_list = scaner
+		let mut list = scaner
 			.get_empty_folder_list()
 			.values()
-			.cloned()
-			.collect::<Vec<_>>();
-		let mut message = scaner.get_text_messages().create_messages_text();
-
-		raw_list.par_sort_unstable_by(|a, b| {
-			split_path_compare(a.path.as_path(), b.path.as_path())
-		});
-
-		message =
-			format!("Found {} empty folders\n{}", raw_list.len(), message);
-
-		let list = raw_list
-			.into_iter()
 			.map(|item| CustomFolderEntry {
 				path: item.path.to_string_lossy().to_string(),
 				modified_date: item.modified_date,
+				junk_file_count: 0,
 			})
 			.collect::<Vec<_>>();
+		let mut message = scaner.get_text_messages().create_messages_text();
+
+		if !junk_files.is_empty() {
+			let progress = ProgressReporter::new(&progress_tx, 0);
+			let tree = walker.walk_tree(&stop_flag, Some(&progress));
+			drop(progress);
+			let junk_folders = get_junk_only_folders(
+				&tree,
+				walker.included_directories(),
+				&junk_files,
+			);
+
+			// Folders czkawka found inside a junk only folder are covered
+			// by it
+			list.retain(|item| {
+				!Path::new(&item.path)
+					.ancestors()
+					.skip(1)
+					.any(|ancestor| junk_folders.contains_key(ancestor))
+			});
+			let found: HashSet<_> =
+				list.iter().map(|item| PathBuf::from(&item.path)).collect();
+			list.extend(
+				junk_folders
+					.into_iter()
+					.filter(|(path, _)| !found.contains(path))
+					.map(|(path, count)| CustomFolderEntry {
+						modified_date: fs::metadata(&path)
+							.map(|metadata| get_modified_date(&metadata))
+							.unwrap_or_default(),
+						path: path.to_string_lossy().to_string(),
+						junk_file_count: count,
+					}),
+			);
+		}
+
+		list.par_sort_unstable_by(|a, b| {
+			split_path_compare(Path::new(&a.path), Path::new(&b.path))
+		});
+
+		message = format!("Found {} empty folders\n{}", list.len(), message);
 
 		app.emit(
 			"scan-result",
@@ -71,7 +186,108 @@ pub fn scan_empty_folders(app: AppHandle, settings: Settings) {
 	});
 }
 
+// The outermost folders holding junk files and nothing else, with the count
+// of their junk files. Counted bottom up over the walked tree, included
+// directories themselves are never reported
+fn get_junk_only_folders(
+	tree: &Tree,
+	roots: &[PathBuf],
+	junk_files: &JunkFiles,
+) -> HashMap<PathBuf, usize> {
+	// `None` once a folder holds anything else than junk files
+	let mut counts: HashMap<&Path, Option<usize>> = tree
+		.folders
+		.iter()
+		.map(|folder| (folder.as_path(), Some(0)))
+		.collect();
+	for folder in &tree.unreadable {
+		counts.insert(folder.as_path(), None);
+	}
+
+	for file in &tree.files {
+		let count = junk_files.is_junk(&file.path, file.size).then_some(1);
+		add_to_parent(&mut counts, &file.path, count);
+	}
+	for (path, _) in &tree.others {
+		add_to_parent(&mut counts, path, None);
+	}
+	// Sub folders are sorted after their parents
+	for folder in tree.folders.iter().rev() {
+		let count = counts[folder.as_path()];
+		add_to_parent(&mut counts, folder, count);
+	}
+
+	let junk_folders: HashMap<&Path, usize> = counts
+		.into_iter()
+		.filter(|(path, _)| !roots.iter().any(|root| root.as_path() == *path))
+		.filter_map(|(path, count)| Some((path, count.filter(|c| *c > 0)?)))
+		.collect();
+
+	junk_folders
+		.iter()
+		.filter(|(path, _)| {
+			!path
+				.ancestors()
+				.skip(1)
+				.any(|ancestor| junk_folders.contains_key(ancestor))
+		})
+		.map(|(path, count)| (path.to_path_buf(), *count))
+		.collect()
+}
+
+fn add_to_parent(
+	counts: &mut HashMap<&Path, Option<usize>>,
+	path: &Path,
+	count: Option<usize>,
+) {
+	if let Some(parent) = path.parent()
+		&& let Some(parent_count) = counts.get_mut(parent)
+	{
+		*parent_count = parent_count.zip(count).map(|(a, b)| a + b);
+	}
+}
+
 crate::gen_set_scaner_state_fn!(
 	empty_folders_state,
 	czkawka_core::tools::empty_folder::EmptyFolder
 );
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::file_walker::WalkedFile;
+
+	fn file_of(path: &str, size: u64) -> WalkedFile {
+		WalkedFile {
+			path: PathBuf::from(path),
+			size,
+			modified_date: 0,
+		}
+	}
+
+	#[test]
+	fn junk_only_folders_are_counted_bottom_up() {
+		let tree = Tree {
+			folders: ["/r", "/r/a", "/r/a/b", "/r/c", "/r/d", "/r/e"]
+				.map(PathBuf::from)
+				.to_vec(),
+			files: vec![
+				file_of("/r/a/Thumbs.db", 10),
+				file_of("/r/a/b/.DS_Store", 10),
+				file_of("/r/c/.DS_Store", 10),
+				file_of("/r/c/photo.jpg", 10),
+				file_of("/r/e/empty.txt", 0),
+			],
+			others: vec![(PathBuf::from("/r/d/private"), "excluded".into())],
+			unreadable: vec![],
+		};
+		let junk_files = JunkFiles::parse("thumbs.db, .ds_store", true);
+
+		let junk_folders =
+			get_junk_only_folders(&tree, &[PathBuf::from("/r")], &junk_files);
+
+		assert_eq!(junk_folders.len(), 2);
+		assert_eq!(junk_folders.get(Path::new("/r/a")), Some(&2));
+		assert_eq!(junk_folders.get(Path::new("/r/e")), Some(&1));
+	}
+}
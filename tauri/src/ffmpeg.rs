@@ -1,4 +1,32 @@
-use std::{env, path::PathBuf, process::Command};
+use std::{
+	env,
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::settings::Settings;
+
+const REQUIRED_DECODERS: [&str; 4] = ["h264", "hevc", "mpeg4", "vp9"];
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct ExeInfo {
+	path: Option<String>,
+	version: Option<String>,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct CheckFfmpegResult {
+	ffmpeg: ExeInfo,
+	ffprobe: ExeInfo,
+	missing_decoders: Vec<String>,
+	usable: bool,
+	reason: Option<String>,
+}
 
 pub fn set_ffmpeg_path(resource_dir: Option<PathBuf>) {
 	let mut paths = get_sys_paths();
@@ -31,13 +59,14 @@ pub fn set_ffmpeg_path(resource_dir: Option<PathBuf>) {
 		}
 	}
 
-	let Ok(new_path) = env::join_paths(paths) else {
+	let Ok(new_path) = env::join_paths(&paths) else {
 		log::info!("Failed to join paths");
 		return;
 	};
 
 	log::info!("New PATH: {}", new_path.display());
 
+	// Runs during setup, before any other thread of the app is started
 	unsafe {
 		env::set_var("PATH", new_path);
 	}
@@ -46,6 +75,184 @@ pub fn set_ffmpeg_path(resource_dir: Option<PathBuf>) {
 	exe_info("ffprobe");
 }
 
+// The executables this app spawns itself, a custom path is used as is
+pub fn get_ffmpeg_exe(settings: &Settings) -> String {
+	get_exe(&settings.ffmpeg_path, "ffmpeg")
+}
+
+pub fn get_ffprobe_exe(settings: &Settings) -> String {
+	get_exe(&settings.ffprobe_path, "ffprobe")
+}
+
+fn get_exe(custom_path: &str, name: &str) -> String {
+	if custom_path.is_empty() {
+		name.to_string()
+	} else {
+		custom_path.to_string()
+	}
+}
+
+pub fn check_ffmpeg(app: AppHandle, settings: Settings) {
+	std::thread::spawn(move || {
+		let result = check_ffmpeg_impl(&settings);
+		app.emit("check-ffmpeg-result", result).unwrap();
+	});
+}
+
+fn check_ffmpeg_impl(settings: &Settings) -> CheckFfmpegResult {
+	let mut reasons = vec![];
+
+	let ffmpeg =
+		resolve_exe_info("ffmpeg", &settings.ffmpeg_path, &mut reasons);
+	let ffprobe =
+		resolve_exe_info("ffprobe", &settings.ffprobe_path, &mut reasons);
+
+	let missing_decoders = match &ffmpeg.path {
+		Some(ffmpeg_path) => match get_decoders(ffmpeg_path) {
+			Ok(decoders) => REQUIRED_DECODERS
+				.iter()
+				.filter(|name| !decoders.iter().any(|v| v == *name))
+				.map(|name| name.to_string())
+				.collect(),
+			Err(err) => {
+				reasons
+					.push(format!("Failed to list ffmpeg decoders: {}", err));
+				vec![]
+			}
+		},
+		None => vec![],
+	};
+
+	if !missing_decoders.is_empty() {
+		reasons.push(format!(
+			"ffmpeg is missing required decoders: {}",
+			missing_decoders.join(", ")
+		));
+	}
+
+	CheckFfmpegResult {
+		ffmpeg,
+		ffprobe,
+		missing_decoders,
+		usable: reasons.is_empty(),
+		reason: if reasons.is_empty() {
+			None
+		} else {
+			Some(reasons.join("\n"))
+		},
+	}
+}
+
+fn resolve_exe_info(
+	name: &str,
+	custom_path: &str,
+	reasons: &mut Vec<String>,
+) -> ExeInfo {
+	let path = if custom_path.is_empty() {
+		let path = find_exe(name);
+		if path.is_none() {
+			reasons.push(format!(
+				"{} was not found in PATH or the resource directory",
+				name
+			));
+		}
+		path
+	} else if !Path::new(custom_path).is_file() {
+		reasons.push(format!("{} was not found at `{}`", name, custom_path));
+		None
+	} else {
+		// Video hashing in czkawka always runs the one found in PATH, the
+		// custom path is only used by this app
+		if find_exe(name).is_none() {
+			reasons.push(format!(
+				"{} was not found in PATH, similar videos are hashed with it",
+				name
+			));
+		}
+		Some(custom_path.to_string())
+	};
+
+	let version = path.as_ref().and_then(|exe_path| {
+		get_version(exe_path, name)
+			.map_err(|err| {
+				reasons.push(format!(
+					"Failed to run `{} -version`: {}",
+					exe_path, err
+				));
+			})
+			.ok()
+	});
+
+	ExeInfo { path, version }
+}
+
+fn find_exe(name: &str) -> Option<String> {
+	let command = if cfg!(windows) {
+		"where"
+	} else {
+		"which"
+	};
+
+	let output = Command::new(command).arg(name).output().ok()?;
+
+	if !output.status.success() {
+		return None;
+	}
+
+	String::from_utf8_lossy(&output.stdout)
+		.lines()
+		.next()
+		.map(|line| line.trim().to_string())
+		.filter(|line| !line.is_empty())
+}
+
+// The first line looks like `ffmpeg version 7.1.1 Copyright (c) ...`, so
+// an executable with another name is still recognized
+fn get_version(exe_path: &str, name: &str) -> Result<String, String> {
+	let output = Command::new(exe_path)
+		.arg("-version")
+		.output()
+		.map_err(|err| err.to_string())?;
+
+	if !output.status.success() {
+		return Err(format!("exited with status: {}", output.status));
+	}
+
+	String::from_utf8_lossy(&output.stdout)
+		.lines()
+		.next()
+		.and_then(|line| {
+			let mut words = line.split_whitespace();
+			if words.next()? != name || words.next()? != "version" {
+				return None;
+			}
+			words.next().map(|word| word.to_string())
+		})
+		.ok_or_else(|| format!("not a {} executable", name))
+}
+
+// Each decoder line looks like ` V....D h264    H.264 / AVC / ...`
+fn get_decoders(ffmpeg_path: &str) -> Result<Vec<String>, String> {
+	let output = Command::new(ffmpeg_path)
+		.args(["-hide_banner", "-decoders"])
+		.output()
+		.map_err(|err| err.to_string())?;
+
+	if !output.status.success() {
+		return Err(format!("exited with status: {}", output.status));
+	}
+
+	let decoders = String::from_utf8_lossy(&output.stdout)
+		.lines()
+		.skip_while(|line| !line.trim_start().starts_with("------"))
+		.skip(1)
+		.filter_map(|line| line.split_whitespace().nth(1))
+		.map(|name| name.to_string())
+		.collect();
+
+	Ok(decoders)
+}
+
 fn exe_info(name: &str) {
 	let command = if cfg!(windows) {
 		"where"
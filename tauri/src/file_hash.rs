@@ -0,0 +1,189 @@
+use std::{
+	collections::BTreeMap,
+	fs::File,
+	hash::Hasher,
+	io::Read,
+	path::Path,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
+use czkawka_core::{
+	common::{
+		cache::{
+			get_duplicate_cache_file, load_cache_from_file_generalized_by_path,
+		},
+		model::HashType,
+		progress_data::CurrentStage,
+	},
+	tools::duplicate::DuplicateEntry,
+};
+use rayon::prelude::*;
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::{
+	file_walker::WalkedFile, progress::ProgressReporter, settings::Settings,
+};
+
+pub const PREHASH_SIZE: u64 = 16 * 1024;
+
+const BUFFER_SIZE: usize = 16 * 1024;
+
+pub fn get_hash_type(settings: &Settings) -> HashType {
+	parse_hash_type(&settings.duplicates_sub_available_hash_type)
+}
+
+pub fn parse_hash_type(name: &str) -> HashType {
+	match name {
+		"CRC32" => HashType::Crc32,
+		"XXH3" => HashType::Xxh3,
+		_ => HashType::Blake3,
+	}
+}
+
+pub fn get_hash_type_name(hash_type: HashType) -> &'static str {
+	match hash_type {
+		HashType::Blake3 => "Blake3",
+		HashType::Crc32 => "CRC32",
+		HashType::Xxh3 => "XXH3",
+	}
+}
+
+// Hashes are formatted the way czkawka stores them in its hash cache, so
+// they can be compared with the cached ones
+pub fn hash_file(
+	path: &Path,
+	hash_type: HashType,
+	limit: Option<u64>,
+	stop_flag: &Arc<AtomicBool>,
+) -> Result<String, String> {
+	let mut file = File::open(path).map_err(|err| err.to_string())?;
+	hash_reader(&mut file, hash_type, limit, stop_flag)
+}
+
+pub fn hash_reader(
+	reader: &mut dyn Read,
+	hash_type: HashType,
+	limit: Option<u64>,
+	stop_flag: &Arc<AtomicBool>,
+) -> Result<String, String> {
+	let mut buffer = vec![0; BUFFER_SIZE];
+	let mut remaining = limit.unwrap_or(u64::MAX);
+
+	let mut blake3_hasher = blake3::Hasher::new();
+	let mut crc32_hasher = crc32fast::Hasher::new();
+	let mut xxh3_hasher = Xxh3::new();
+
+	while remaining > 0 {
+		if stop_flag.load(Ordering::Relaxed) {
+			return Err("Stopped".to_string());
+		}
+
+		let to_read = remaining.min(BUFFER_SIZE as u64) as usize;
+		let n = reader
+			.read(&mut buffer[..to_read])
+			.map_err(|err| err.to_string())?;
+		if n == 0 {
+			break;
+		}
+		remaining -= n as u64;
+
+		match hash_type {
+			HashType::Blake3 => {
+				blake3_hasher.update(&buffer[..n]);
+			}
+			HashType::Crc32 => crc32_hasher.update(&buffer[..n]),
+			HashType::Xxh3 => xxh3_hasher.write(&buffer[..n]),
+		}
+	}
+
+	Ok(match hash_type {
+		HashType::Blake3 => blake3_hasher.finalize().to_hex().to_string(),
+		HashType::Crc32 => crc32_hasher.finalize().to_string(),
+		HashType::Xxh3 => xxh3_hasher.finish().to_string(),
+	})
+}
+
+// Full hashes of `files`, read from the czkawka duplicate hash cache when an
+// up to date entry exists. `None` for files that could not be read
+pub fn get_full_hashes(
+	files: &[&WalkedFile],
+	hash_type: HashType,
+	stop_flag: &Arc<AtomicBool>,
+	progress: Option<&ProgressReporter>,
+) -> Vec<Option<String>> {
+	if let Some(progress) = progress {
+		progress.next_stage(
+			CurrentStage::DuplicateFullHashing,
+			files.len(),
+			files.iter().map(|file| file.size).sum(),
+		);
+	}
+	let cache = load_duplicate_hash_cache(hash_type);
+
+	files
+		.par_iter()
+		.map(|file| {
+			if let Some(progress) = progress {
+				progress.add_entries(1);
+				progress.add_bytes(file.size);
+			}
+			if let Some(entry) = cache.get(&*file.path.to_string_lossy())
+				&& entry.size == file.size
+				&& entry.modified_date == file.modified_date
+				&& !entry.hash.is_empty()
+			{
+				return Some(entry.hash.clone());
+			}
+
+			hash_file(&file.path, hash_type, None, stop_flag)
+				.map_err(|err| {
+					log::info!(
+						"Failed to hash `{}`: {}",
+						file.path.display(),
+						err
+					);
+				})
+				.ok()
+		})
+		.collect()
+}
+
+pub fn get_prehashes(
+	files: &[&WalkedFile],
+	hash_type: HashType,
+	stop_flag: &Arc<AtomicBool>,
+	progress: Option<&ProgressReporter>,
+) -> Vec<Option<String>> {
+	if let Some(progress) = progress {
+		progress.next_stage(
+			CurrentStage::DuplicatePreHashing,
+			files.len(),
+			files.iter().map(|file| file.size.min(PREHASH_SIZE)).sum(),
+		);
+	}
+
+	files
+		.par_iter()
+		.map(|file| {
+			if let Some(progress) = progress {
+				progress.add_entries(1);
+				progress.add_bytes(file.size.min(PREHASH_SIZE));
+			}
+			hash_file(&file.path, hash_type, Some(PREHASH_SIZE), stop_flag).ok()
+		})
+		.collect()
+}
+
+pub fn load_duplicate_hash_cache(
+	hash_type: HashType,
+) -> BTreeMap<String, DuplicateEntry> {
+	let cache_file_name = get_duplicate_cache_file(&hash_type, false);
+	let (_, loaded_entries) = load_cache_from_file_generalized_by_path::<
+		DuplicateEntry,
+	>(&cache_file_name, false, &BTreeMap::new());
+
+	loaded_entries.unwrap_or_default()
+}
@@ -0,0 +1,413 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+	time::UNIX_EPOCH,
+};
+
+use czkawka_core::common::progress_data::CurrentStage;
+use rayon::prelude::*;
+
+use crate::{
+	progress::ProgressReporter,
+	settings::Settings,
+	utils::{convert_strs_to_path_bufs, split_str_with_comma},
+};
+
+pub struct WalkedFile {
+	pub path: PathBuf,
+	pub size: u64,
+	pub modified_date: u64,
+}
+
+// Everything below some directories, nothing is filtered out. For the
+// tools that look at whole folders, where files skipped by the settings
+// still count
+#[derive(Default)]
+pub struct Tree {
+	pub folders: Vec<PathBuf>,
+	pub files: Vec<WalkedFile>,
+	// Symlinks, other special files and excluded paths, with a token of
+	// what they are
+	pub others: Vec<(PathBuf, String)>,
+	pub unreadable: Vec<PathBuf>,
+}
+
+// A plain directory walker for the tools of this app that czkawka_core
+// doesn't provide, it honors the same common settings as the czkawka scaners
+pub struct FileWalker {
+	included_directories: Vec<PathBuf>,
+	excluded_directories: Vec<PathBuf>,
+	excluded_items: Vec<String>,
+	allowed_extensions: Vec<String>,
+	excluded_extensions: Vec<String>,
+	recursive_search: bool,
+	minimal_file_size: u64,
+	maximal_file_size: u64,
+}
+
+impl FileWalker {
+	pub fn from_settings(settings: &Settings) -> Self {
+		FileWalker {
+			included_directories: convert_strs_to_path_bufs(
+				settings.included_directories.clone(),
+			),
+			excluded_directories: convert_strs_to_path_bufs(
+				settings.excluded_directories.clone(),
+			),
+			excluded_items: parse_excluded_items(&settings.excluded_items),
+			allowed_extensions: parse_extensions(&settings.allowed_extensions),
+			excluded_extensions: parse_extensions(
+				&settings.excluded_extensions,
+			),
+			recursive_search: settings.recursive_search,
+			minimal_file_size: settings.minimum_file_size as u64 * 1000,
+			maximal_file_size: settings.maximum_file_size as u64 * 1000,
+		}
+	}
+
+	// Only the exclusions of the settings, for checking paths found outside
+	// of a walk
+	pub fn from_exclusions(
+		excluded_directories: Vec<String>,
+		excluded_items: &str,
+	) -> Self {
+		FileWalker {
+			included_directories: vec![],
+			excluded_directories: convert_strs_to_path_bufs(
+				excluded_directories,
+			),
+			excluded_items: parse_excluded_items(excluded_items),
+			allowed_extensions: vec![],
+			excluded_extensions: vec![],
+			recursive_search: true,
+			minimal_file_size: 0,
+			maximal_file_size: u64::MAX,
+		}
+	}
+
+	pub fn ignore_file_size(mut self) -> Self {
+		self.minimal_file_size = 0;
+		self.maximal_file_size = u64::MAX;
+		self
+	}
+
+	pub fn included_directories(&self) -> &[PathBuf] {
+		&self.included_directories
+	}
+
+	// Returns every regular file in the included directories, sorted by
+	// path. Symlinks are never followed
+	pub fn walk(
+		&self,
+		stop_flag: &Arc<AtomicBool>,
+		progress: Option<&ProgressReporter>,
+	) -> Vec<WalkedFile> {
+		self.walk_with_folders(stop_flag, progress).1
+	}
+
+	// Same as `walk`, also returning every folder below the included
+	// directories, sorted by path
+	pub fn walk_with_folders(
+		&self,
+		stop_flag: &Arc<AtomicBool>,
+		progress: Option<&ProgressReporter>,
+	) -> (Vec<PathBuf>, Vec<WalkedFile>) {
+		if let Some(progress) = progress {
+			progress.next_stage(CurrentStage::CollectingFiles, 0, 0);
+		}
+		let mut to_read: Vec<_> = self
+			.included_directories
+			.iter()
+			.filter(|dir| !self.is_excluded(dir))
+			.cloned()
+			.collect();
+		let mut folders = vec![];
+		let mut files = vec![];
+
+		while !to_read.is_empty() {
+			if stop_flag.load(Ordering::Relaxed) {
+				return (vec![], vec![]);
+			}
+
+			let read: Vec<_> = to_read
+				.par_iter()
+				.map(|dir| {
+					let read = self.read_dir(dir);
+					if let Some(progress) = progress {
+						progress.add_entries(read.1.len());
+					}
+					read
+				})
+				.collect();
+
+			to_read = vec![];
+			for (sub_folders, dir_files) in read {
+				if self.recursive_search {
+					folders.extend(sub_folders.iter().cloned());
+					to_read.extend(sub_folders);
+				}
+				files.extend(dir_files);
+			}
+		}
+
+		// Included directories may be nested in each other
+		folders.sort_unstable();
+		folders.dedup();
+		files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+		files.dedup_by(|a, b| a.path == b.path);
+
+		(folders, files)
+	}
+
+	// Same as `walk_tree` below the included directories. Excluded paths
+	// are not read, they are only listed in `others`
+	pub fn walk_tree(
+		&self,
+		stop_flag: &Arc<AtomicBool>,
+		progress: Option<&ProgressReporter>,
+	) -> Tree {
+		let roots: Vec<_> = self
+			.included_directories
+			.iter()
+			.filter(|dir| !self.is_excluded(dir))
+			.cloned()
+			.collect();
+
+		walk_tree_impl(&roots, stop_flag, progress, |path| {
+			self.is_excluded(path)
+		})
+	}
+
+	pub fn is_excluded(&self, path: &Path) -> bool {
+		if self
+			.excluded_directories
+			.iter()
+			.any(|dir| path.starts_with(dir))
+		{
+			return true;
+		}
+
+		let path = path.to_string_lossy();
+		self.excluded_items
+			.iter()
+			.any(|item| matches_wildcard(item, &path))
+	}
+
+	fn read_dir(&self, dir: &Path) -> (Vec<PathBuf>, Vec<WalkedFile>) {
+		let mut folders = vec![];
+		let mut files = vec![];
+
+		let Ok(read_dir) = fs::read_dir(dir) else {
+			log::info!("Failed to read directory `{}`", dir.display());
+			return (folders, files);
+		};
+
+		for entry in read_dir.flatten() {
+			let Ok(file_type) = entry.file_type() else {
+				continue;
+			};
+			let path = entry.path();
+
+			if file_type.is_dir() {
+				if !self.is_excluded(&path) {
+					folders.push(path);
+				}
+				continue;
+			}
+			if !file_type.is_file()
+				|| !self.is_allowed_extension(&path)
+				|| self.is_excluded(&path)
+			{
+				continue;
+			}
+
+			let Ok(metadata) = entry.metadata() else {
+				continue;
+			};
+			let size = metadata.len();
+			if !(self.minimal_file_size..=self.maximal_file_size)
+				.contains(&size)
+			{
+				continue;
+			}
+
+			files.push(WalkedFile {
+				path,
+				size,
+				modified_date: get_modified_date(&metadata),
+			});
+		}
+
+		(folders, files)
+	}
+
+	fn is_allowed_extension(&self, path: &Path) -> bool {
+		let ext = path
+			.extension()
+			.map(|ext| ext.to_string_lossy().to_lowercase())
+			.unwrap_or_default();
+
+		(self.allowed_extensions.is_empty()
+			|| self.allowed_extensions.contains(&ext))
+			&& !self.excluded_extensions.contains(&ext)
+	}
+}
+
+// Every entry below `roots`, sorted by path. Symlinks are never followed
+pub fn walk_tree(
+	roots: &[PathBuf],
+	stop_flag: &Arc<AtomicBool>,
+	progress: Option<&ProgressReporter>,
+) -> Tree {
+	walk_tree_impl(roots, stop_flag, progress, |_| false)
+}
+
+fn walk_tree_impl(
+	roots: &[PathBuf],
+	stop_flag: &Arc<AtomicBool>,
+	progress: Option<&ProgressReporter>,
+	is_excluded: impl Fn(&Path) -> bool + Sync,
+) -> Tree {
+	if let Some(progress) = progress {
+		progress.next_stage(CurrentStage::CollectingFiles, 0, 0);
+	}
+	let mut tree = Tree {
+		folders: roots.to_vec(),
+		..Default::default()
+	};
+	let mut to_read = roots.to_vec();
+
+	while !to_read.is_empty() {
+		if stop_flag.load(Ordering::Relaxed) {
+			return Tree::default();
+		}
+
+		let read: Vec<_> = to_read
+			.par_iter()
+			.map(|dir| {
+				let read = read_tree_dir(dir, &is_excluded);
+				if let Some(progress) = progress
+					&& let Some((_, files, others)) = &read
+				{
+					progress.add_entries(files.len() + others.len());
+				}
+				(dir, read)
+			})
+			.collect();
+
+		to_read = vec![];
+		for (dir, read) in read {
+			let Some((folders, files, others)) = read else {
+				tree.unreadable.push(dir.clone());
+				continue;
+			};
+			tree.folders.extend(folders.iter().cloned());
+			to_read.extend(folders);
+			tree.files.extend(files);
+			tree.others.extend(others);
+		}
+	}
+
+	// Included directories may be nested in each other
+	tree.folders.sort_unstable();
+	tree.folders.dedup();
+	tree.files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+	tree.files.dedup_by(|a, b| a.path == b.path);
+	tree.others.sort_unstable();
+	tree.others.dedup();
+
+	tree
+}
+
+type TreeDir = (Vec<PathBuf>, Vec<WalkedFile>, Vec<(PathBuf, String)>);
+
+// `None` when the directory or any of its entries can't be read
+fn read_tree_dir(
+	dir: &Path,
+	is_excluded: impl Fn(&Path) -> bool,
+) -> Option<TreeDir> {
+	let mut folders = vec![];
+	let mut files = vec![];
+	let mut others = vec![];
+
+	for entry in fs::read_dir(dir).ok()? {
+		let entry = entry.ok()?;
+		let path = entry.path();
+		if is_excluded(&path) {
+			others.push((path, "excluded".to_string()));
+			continue;
+		}
+		let metadata = entry.metadata().ok()?;
+
+		if metadata.is_dir() {
+			folders.push(path);
+		} else if metadata.is_file() {
+			files.push(WalkedFile {
+				path,
+				size: metadata.len(),
+				modified_date: get_modified_date(&metadata),
+			});
+		} else if metadata.is_symlink() {
+			let target = fs::read_link(&path).ok()?;
+			others.push((path, format!("link:{}", target.display())));
+		} else {
+			others.push((path, "special".to_string()));
+		}
+	}
+
+	Some((folders, files, others))
+}
+
+pub fn get_modified_date(metadata: &fs::Metadata) -> u64 {
+	metadata
+		.modified()
+		.ok()
+		.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+		.map(|duration| duration.as_secs())
+		.unwrap_or_default()
+}
+
+fn parse_excluded_items(items: &str) -> Vec<String> {
+	split_str_with_comma(items.to_string())
+		.into_iter()
+		.map(|item| item.trim().to_string())
+		.filter(|item| !item.is_empty())
+		.collect()
+}
+
+fn parse_extensions(s: &str) -> Vec<String> {
+	s.split(',')
+		.map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+		.filter(|ext| !ext.is_empty())
+		.collect()
+}
+
+// Excluded items only support `*`, the same as czkawka
+pub fn matches_wildcard(pattern: &str, text: &str) -> bool {
+	let pattern: Vec<char> = pattern.chars().collect();
+	let text: Vec<char> = text.chars().collect();
+	let (mut p, mut t) = (0, 0);
+	let mut backtrack = None;
+
+	while t < text.len() {
+		if p < pattern.len() && pattern[p] == '*' {
+			backtrack = Some((p, t));
+			p += 1;
+		} else if p < pattern.len() && pattern[p] == text[t] {
+			p += 1;
+			t += 1;
+		} else if let Some((star_p, star_t)) = backtrack {
+			p = star_p + 1;
+			t = star_t + 1;
+			backtrack = Some((star_p, star_t + 1));
+		} else {
+			return false;
+		}
+	}
+
+	pattern[p..].iter().all(|c| *c == '*')
+}
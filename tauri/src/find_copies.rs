@@ -0,0 +1,146 @@
+use std::{fs, path::PathBuf};
+
+use czkawka_core::tools::duplicate::DuplicateEntry;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+	file_hash::{PREHASH_SIZE, get_full_hashes, get_hash_type, get_prehashes},
+	file_walker::{FileWalker, WalkedFile, get_modified_date},
+	progress::ProgressReporter,
+	scaner::spawn_scaner_thread,
+	settings::Settings,
+	state::get_stop_flag_and_progress_tx,
+};
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<(Option<DuplicateEntry>, Vec<DuplicateEntry>)>,
+	message: String,
+}
+
+// Narrows the candidates by size, then prehash, then full hash, so only
+// files that are very likely copies are ever read completely
+pub fn find_file_copies(app: AppHandle, path: String, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let hash_type = get_hash_type(&settings);
+
+		let sample = match fs::metadata(&path) {
+			Ok(metadata) if metadata.is_file() => WalkedFile {
+				path: PathBuf::from(&path),
+				size: metadata.len(),
+				modified_date: get_modified_date(&metadata),
+			},
+			Ok(_) => {
+				emit_result(&app, vec![], format!("`{}` is not a file", path));
+				return;
+			}
+			Err(err) => {
+				emit_result(
+					&app,
+					vec![],
+					format!("Failed to read `{}`: {}", path, err),
+				);
+				return;
+			}
+		};
+
+		// Collecting, prehashing and full hashing
+		let progress = ProgressReporter::new(&progress_tx, 2);
+		let files = FileWalker::from_settings(&settings)
+			.walk(&stop_flag, Some(&progress));
+		let same_size: Vec<_> = files
+			.iter()
+			.filter(|file| file.size == sample.size && file.path != sample.path)
+			.collect();
+		let same_size_count = same_size.len();
+
+		let candidates = if sample.size > PREHASH_SIZE {
+			let Some(Some(sample_prehash)) =
+				get_prehashes(&[&sample], hash_type, &stop_flag, None).pop()
+			else {
+				emit_result(&app, vec![], format!("Failed to hash `{}`", path));
+				return;
+			};
+			let prehashes = get_prehashes(
+				&same_size,
+				hash_type,
+				&stop_flag,
+				Some(&progress),
+			);
+			same_size
+				.into_iter()
+				.zip(prehashes)
+				.filter(|(_, prehash)| {
+					prehash.as_ref() == Some(&sample_prehash)
+				})
+				.map(|(file, _)| file)
+				.collect()
+		} else {
+			same_size
+		};
+		let prehash_count = candidates.len();
+
+		let Some(Some(sample_hash)) =
+			get_full_hashes(&[&sample], hash_type, &stop_flag, None).pop()
+		else {
+			emit_result(&app, vec![], format!("Failed to hash `{}`", path));
+			return;
+		};
+		let full_hashes = get_full_hashes(
+			&candidates,
+			hash_type,
+			&stop_flag,
+			Some(&progress),
+		);
+		drop(progress);
+		let copies: Vec<_> = candidates
+			.into_iter()
+			.zip(full_hashes)
+			.filter(|(_, hash)| hash.as_ref() == Some(&sample_hash))
+			.map(|(file, _)| walked_file_to_entry(file, sample_hash.clone()))
+			.collect();
+
+		let message = format!(
+			"Found {} copies of `{}`, checked {} files of the same size and {} with the same prehash",
+			copies.len(),
+			path,
+			same_size_count,
+			prehash_count
+		);
+		let list = if copies.is_empty() {
+			vec![]
+		} else {
+			vec![(Some(walked_file_to_entry(&sample, sample_hash)), copies)]
+		};
+
+		emit_result(&app, list, message);
+	});
+}
+
+fn emit_result(
+	app: &AppHandle,
+	list: Vec<(Option<DuplicateEntry>, Vec<DuplicateEntry>)>,
+	message: String,
+) {
+	app.emit(
+		"scan-result",
+		ScanResult {
+			cmd: "scan_duplicate_files",
+			list,
+			message,
+		},
+	)
+	.unwrap();
+}
+
+fn walked_file_to_entry(file: &WalkedFile, hash: String) -> DuplicateEntry {
+	DuplicateEntry {
+		path: file.path.clone(),
+		modified_date: file.modified_date,
+		size: file.size,
+		hash,
+	}
+}
@@ -0,0 +1,160 @@
+use std::{
+	collections::HashMap,
+	path::Path,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
+use czkawka_core::{
+	common::image::get_dynamic_image_from_path,
+	tools::similar_images::{ImHash, ImagesEntry},
+};
+use image_hasher::{FilterType, HashAlg, HasherConfig};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{load_json_cache, save_json_cache};
+
+// The first transform must stay `none`, it is the hash every other variant
+// is compared against
+pub const TRANSFORMS: [&str; 8] = [
+	"none",
+	"rotate90",
+	"rotate180",
+	"rotate270",
+	"flipHorizontal",
+	"flipVertical",
+	"transverse",
+	"transpose",
+];
+
+// Only the quarter turns are not their own inverse
+pub fn inverse_transform(transform: usize) -> usize {
+	match transform {
+		1 => 3,
+		3 => 1,
+		_ => transform,
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+	size: u64,
+	modified_date: u64,
+	hashes: Vec<ImHash>,
+}
+
+pub struct TransformHasher {
+	hash_size: u8,
+	hash_alg: HashAlg,
+	resize_algorithm: FilterType,
+}
+
+impl TransformHasher {
+	pub fn new(
+		hash_size: u8,
+		hash_alg: HashAlg,
+		resize_algorithm: FilterType,
+	) -> Self {
+		TransformHasher {
+			hash_size,
+			hash_alg,
+			resize_algorithm,
+		}
+	}
+
+	// Returns the hashes of every entry in `TRANSFORMS` order, or `None`
+	// for entries that could not be decoded
+	pub fn hash_entries(
+		&self,
+		entries: &[&ImagesEntry],
+		stop_flag: &Arc<AtomicBool>,
+	) -> Vec<Option<Vec<ImHash>>> {
+		let cache_file_name = format!(
+			"cache_similar_images_transforms_{}_{:?}_{:?}.json",
+			self.hash_size, self.hash_alg, self.resize_algorithm
+		);
+		let mut cache: HashMap<String, CacheEntry> =
+			load_json_cache(&cache_file_name);
+
+		let hashed: Vec<_> = entries
+			.par_iter()
+			.map(|entry| {
+				let key = entry.path.to_string_lossy().to_string();
+
+				if let Some(cache_entry) = cache.get(&key)
+					&& cache_entry.size == entry.size
+					&& cache_entry.modified_date == entry.modified_date
+				{
+					return (Some(cache_entry.hashes.clone()), false);
+				}
+
+				if stop_flag.load(Ordering::Relaxed) {
+					return (None, false);
+				}
+
+				match self.hash_file(&entry.path) {
+					Ok(hashes) => (Some(hashes), true),
+					Err(err) => {
+						log::info!(
+							"Failed to hash transforms of `{}`: {}",
+							entry.path.display(),
+							err
+						);
+						(None, false)
+					}
+				}
+			})
+			.collect();
+
+		let mut cache_changed = false;
+
+		for (entry, (hashes, is_new)) in entries.iter().zip(&hashed) {
+			if let (Some(hashes), true) = (hashes, is_new) {
+				cache_changed = true;
+				cache.insert(
+					entry.path.to_string_lossy().to_string(),
+					CacheEntry {
+						size: entry.size,
+						modified_date: entry.modified_date,
+						hashes: hashes.clone(),
+					},
+				);
+			}
+		}
+
+		if cache_changed {
+			save_json_cache(&cache_file_name, &cache);
+		}
+
+		hashed.into_iter().map(|(hashes, _)| hashes).collect()
+	}
+
+	fn hash_file(&self, path: &Path) -> Result<Vec<ImHash>, String> {
+		let image = get_dynamic_image_from_path(&path.to_string_lossy())?;
+		let hasher = HasherConfig::new()
+			.hash_size(self.hash_size as u32, self.hash_size as u32)
+			.hash_alg(self.hash_alg)
+			.resize_filter(self.resize_algorithm)
+			.to_hasher();
+
+		let variants = [
+			image.clone(),
+			image.rotate90(),
+			image.rotate180(),
+			image.rotate270(),
+			image.fliph(),
+			image.flipv(),
+			// Same order as `TRANSFORMS`
+			image.fliph().rotate90(),
+			image.flipv().rotate90(),
+		];
+
+		Ok(variants
+			.iter()
+			.map(|variant| hasher.hash_image(variant).as_bytes().to_vec())
+			.collect())
+	}
+}
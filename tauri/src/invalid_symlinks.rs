@@ -1,42 +1,152 @@
+use std::{
+	collections::{HashMap, HashSet},
+	ffi::OsString,
+	fs,
+	path::{Component, Path, PathBuf},
+	sync::{Arc, atomic::AtomicBool},
+};
+
 use czkawka_core::{
-	common::{split_path_compare, tool_data::CommonData, traits::Search},
-	tools::invalid_symlinks::{InvalidSymlinks, SymlinksFileEntry},
+	common::{
+		model::HashType, split_path_compare, tool_data::CommonData,
+		traits::Search,
+	},
+	tools::invalid_symlinks::{ErrorType, InvalidSymlinks, SymlinksFileEntry},
 };
 use rayon::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
 
 use crate::{
+	file_hash::{get_full_hashes, get_hash_type, load_duplicate_hash_cache},
+	file_walker::{FileWalker, WalkedFile, get_modified_date},
+	progress::ProgressReporter,
 	scaner::{set_scaner_common_settings, spawn_scaner_thread},
 	settings::Settings,
 	state::get_stop_flag_and_progress_tx,
 };
 
+#[derive(Serialize, Clone)]
+struct CustomSymlinkInfo {
+	destination_path: String,
+	// `NonExistentFile` and `InfiniteRecursion` of czkawka, or `OutsideTree`
+	// for working links that leave the included directories
+	type_of_error: &'static str,
+}
+
+#[derive(Serialize, Clone)]
+struct CustomSymlinksEntry {
+	path: String,
+	size: u64,
+	modified_date: u64,
+	symlink_info: CustomSymlinkInfo,
+	// Files with the name of the missing target, only the ones with the
+	// cached size or hash of the old target when it was in the hash cache
+	candidates: Vec<String>,
+	// Set when exactly one candidate is left
+	proposed_target: String,
+	// `name`, `size` or `hash`, what the candidates were matched by
+	target_match: &'static str,
+}
+
 #[derive(Serialize, Clone)]
 struct ScanResult {
 	cmd: &'static str,
-	list: Vec<SymlinksFileEntry>,
+	list: Vec<CustomSymlinksEntry>,
 	message: String,
 }
 
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum TargetMatch {
+	Name,
+	Size,
+	Hash,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairOptions {
+	items: Vec<RepairItem>,
+	// Writes the new targets relative to the folder of the link
+	make_relative: bool,
+}
+
+#[derive(Deserialize)]
+struct RepairItem {
+	path: String,
+	// Empty to keep the current target, only useful with `make_relative`
+	target: String,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct RepairSymlinksResult {
+	success_paths: Vec<String>,
+	errors: Vec<String>,
+}
+
 pub fn scan_invalid_symlinks(app: AppHandle, settings: Settings) {
 	spawn_scaner_thread(move || {
 		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
 
+		let target_match =
+			match settings.invalid_symlinks_sub_target_match.as_ref() {
+				"name" => TargetMatch::Name,
+				"hash" => TargetMatch::Hash,
+				_ => TargetMatch::Size,
+			};
+		let report_outside_tree = settings.invalid_symlinks_sub_outside_tree;
+		let hash_type = get_hash_type(&settings);
+		let walker = FileWalker::from_settings(&settings).ignore_file_size();
+
 		let mut scaner = InvalidSymlinks::new();
 
 		set_scaner_common_settings(&mut scaner, settings);
 
 		scaner.search(&stop_flag, Some(&progress_tx));
 
-		let mut list = scaner.get_invalid_symlinks().clone();
+		let mut list: Vec<_> = scaner
+			.get_invalid_symlinks()
+			.iter()
+			.map(convert_symlinks_entry)
+			.collect();
 		let mut message = scaner.get_text_messages().create_messages_text();
 
+		let progress = ProgressReporter::new(&progress_tx, 0);
+		let (folders, files) =
+			walker.walk_with_folders(&stop_flag, Some(&progress));
+		drop(progress);
+
+		if report_outside_tree {
+			let found: HashSet<_> =
+				list.iter().map(|entry| entry.path.clone()).collect();
+			list.extend(
+				get_outside_tree_links(&walker, &folders)
+					.into_iter()
+					.filter(|entry| !found.contains(&entry.path)),
+			);
+		}
+
+		let proposed_count = find_targets(
+			&mut list,
+			&files,
+			target_match,
+			hash_type,
+			&stop_flag,
+		);
+
 		list.par_sort_unstable_by(|a, b| {
-			split_path_compare(a.path.as_path(), b.path.as_path())
+			split_path_compare(Path::new(&a.path), Path::new(&b.path))
 		});
+		// Included directories may be nested in each other
+		list.dedup_by(|a, b| a.path == b.path);
 
-		message = format!("Found {} invalid symlinks\n{}", list.len(), message);
+		message = format!(
+			"Found {} invalid symlinks, a new target was found for {} of them\n{}",
+			list.len(),
+			proposed_count,
+			message
+		);
 
 		app.emit(
 			"scan-result",
@@ -52,6 +162,356 @@ pub fn scan_invalid_symlinks(app: AppHandle, settings: Settings) {
 	});
 }
 
+pub fn repair_symlinks(app: AppHandle, options: RepairOptions) {
+	std::thread::spawn(move || {
+		let result = repair_symlinks_impl(options);
+		app.emit("repair-symlinks-result", result).unwrap();
+	});
+}
+
+fn convert_symlinks_entry(entry: &SymlinksFileEntry) -> CustomSymlinksEntry {
+	CustomSymlinksEntry {
+		path: entry.path.to_string_lossy().to_string(),
+		size: entry.size,
+		modified_date: entry.modified_date,
+		symlink_info: CustomSymlinkInfo {
+			destination_path: entry
+				.symlink_info
+				.destination_path
+				.to_string_lossy()
+				.to_string(),
+			type_of_error: match entry.symlink_info.type_of_error {
+				ErrorType::InfiniteRecursion => "InfiniteRecursion",
+				ErrorType::NonExistentFile => "NonExistentFile",
+			},
+		},
+		candidates: vec![],
+		proposed_target: String::new(),
+		target_match: "name",
+	}
+}
+
+// Working links whose final target is not below any included directory
+fn get_outside_tree_links(
+	walker: &FileWalker,
+	folders: &[PathBuf],
+) -> Vec<CustomSymlinksEntry> {
+	let roots: Vec<PathBuf> = walker
+		.included_directories()
+		.iter()
+		.map(|root| fs::canonicalize(root).unwrap_or_else(|_| root.clone()))
+		.collect();
+
+	walker
+		.included_directories()
+		.par_iter()
+		.chain(folders.par_iter())
+		.flat_map_iter(|dir| {
+			fs::read_dir(dir)
+				.into_iter()
+				.flatten()
+				.flatten()
+				.filter(|entry| {
+					entry
+						.file_type()
+						.is_ok_and(|file_type| file_type.is_symlink())
+				})
+				.map(|entry| entry.path())
+				.filter(|path| !walker.is_excluded(path))
+				.collect::<Vec<_>>()
+		})
+		.filter_map(|path| {
+			let target = fs::canonicalize(&path).ok()?;
+			if roots.iter().any(|root| target.starts_with(root)) {
+				return None;
+			}
+			let metadata = fs::symlink_metadata(&path).ok()?;
+			Some(CustomSymlinksEntry {
+				path: path.to_string_lossy().to_string(),
+				size: metadata.len(),
+				modified_date: get_modified_date(&metadata),
+				symlink_info: CustomSymlinkInfo {
+					destination_path: target.to_string_lossy().to_string(),
+					type_of_error: "OutsideTree",
+				},
+				candidates: vec![],
+				proposed_target: String::new(),
+				target_match: "name",
+			})
+		})
+		.collect()
+}
+
+// Fills the candidates of links to missing files, returns how many links got
+// a proposed target
+fn find_targets(
+	list: &mut [CustomSymlinksEntry],
+	files: &[WalkedFile],
+	target_match: TargetMatch,
+	hash_type: HashType,
+	stop_flag: &Arc<AtomicBool>,
+) -> usize {
+	let mut by_name: HashMap<OsString, Vec<&WalkedFile>> = HashMap::new();
+	for file in files {
+		if let Some(name) = file.path.file_name() {
+			by_name.entry(name.to_os_string()).or_default().push(file);
+		}
+	}
+
+	let cache = if target_match == TargetMatch::Name {
+		Default::default()
+	} else {
+		load_duplicate_hash_cache(hash_type)
+	};
+
+	// Hashes are only computed once for candidates shared by several links
+	let mut hashes: HashMap<PathBuf, Option<String>> = HashMap::new();
+	let mut proposed_count = 0;
+
+	for entry in list.iter_mut() {
+		if entry.symlink_info.type_of_error != "NonExistentFile" {
+			continue;
+		}
+		let link = Path::new(&entry.path);
+		let destination = Path::new(&entry.symlink_info.destination_path);
+		let Some(candidates) =
+			destination.file_name().and_then(|name| by_name.get(name))
+		else {
+			continue;
+		};
+
+		let old_target = normalize_path(
+			&link.parent().unwrap_or(Path::new("")).join(destination),
+		);
+		let cached = cache.get(&*old_target.to_string_lossy());
+
+		let mut matched_by = TargetMatch::Name;
+		let mut candidates: Vec<&WalkedFile> = candidates.clone();
+		if let Some(cached) = cached {
+			if target_match >= TargetMatch::Size {
+				candidates.retain(|file| file.size == cached.size);
+				matched_by = TargetMatch::Size;
+			}
+			if target_match == TargetMatch::Hash && !cached.hash.is_empty() {
+				let to_hash: Vec<&WalkedFile> = candidates
+					.iter()
+					.filter(|file| !hashes.contains_key(&file.path))
+					.copied()
+					.collect();
+				let new_hashes =
+					get_full_hashes(&to_hash, hash_type, stop_flag, None);
+				for (file, hash) in to_hash.iter().zip(new_hashes) {
+					hashes.insert(file.path.clone(), hash);
+				}
+				candidates.retain(|file| {
+					hashes
+						.get(&file.path)
+						.is_some_and(|hash| hash.as_ref() == Some(&cached.hash))
+				});
+				matched_by = TargetMatch::Hash;
+			}
+		}
+
+		entry.candidates = candidates
+			.iter()
+			.map(|file| file.path.to_string_lossy().to_string())
+			.collect();
+		if let [target] = entry.candidates.as_slice() {
+			entry.proposed_target = target.clone();
+			proposed_count += 1;
+		}
+		entry.target_match = match matched_by {
+			TargetMatch::Name => "name",
+			TargetMatch::Size => "size",
+			TargetMatch::Hash => "hash",
+		};
+	}
+
+	proposed_count
+}
+
+// All new links are created next to the old ones first and then renamed
+// over them. A failure rolls back the links already replaced, so a batch is
+// applied completely or not at all
+fn repair_symlinks_impl(options: RepairOptions) -> RepairSymlinksResult {
+	let RepairOptions {
+		items,
+		make_relative,
+	} = options;
+	let mut result = RepairSymlinksResult::default();
+
+	let mut prepared = vec![];
+	for item in &items {
+		match prepare_link(item, make_relative) {
+			Ok(link) => prepared.push(link),
+			Err(err) => result
+				.errors
+				.push(format!("`{}` Failed, reason: {}", item.path, err)),
+		}
+	}
+	if !result.errors.is_empty() {
+		for link in prepared {
+			let _ = fs::remove_file(&link.temp_path);
+		}
+		result
+			.errors
+			.push("Nothing was changed, the batch was rolled back".to_string());
+		return result;
+	}
+
+	let mut replaced = vec![];
+	for link in &prepared {
+		if let Err(err) = fs::rename(&link.temp_path, &link.path) {
+			result.errors.push(format!(
+				"`{}` Failed, reason: {}",
+				link.path.display(),
+				err
+			));
+			break;
+		}
+		replaced.push(link);
+	}
+	if result.errors.is_empty() {
+		result.success_paths =
+			items.into_iter().map(|item| item.path).collect();
+		return result;
+	}
+
+	for link in prepared.iter().skip(replaced.len()) {
+		let _ = fs::remove_file(&link.temp_path);
+	}
+	for link in replaced {
+		if let Err(err) = replace_link(&link.path, &link.old_target) {
+			result.errors.push(format!(
+				"`{}` Failed to roll back, reason: {}",
+				link.path.display(),
+				err
+			));
+		}
+	}
+	result.errors.push("The batch was rolled back".to_string());
+
+	result
+}
+
+struct PreparedLink {
+	path: PathBuf,
+	temp_path: PathBuf,
+	old_target: PathBuf,
+}
+
+fn prepare_link(
+	item: &RepairItem,
+	make_relative: bool,
+) -> Result<PreparedLink, String> {
+	let path = PathBuf::from(&item.path);
+	let parent = path.parent().ok_or("invalid path")?;
+	let old_target = fs::read_link(&path).map_err(|err| err.to_string())?;
+
+	let target = if item.target.is_empty() {
+		normalize_path(&parent.join(&old_target))
+	} else {
+		PathBuf::from(&item.target)
+	};
+	if !target.is_absolute() {
+		return Err("the new target must be an absolute path".to_string());
+	}
+	if fs::metadata(&target).is_err() {
+		return Err(format!("`{}` does not exist", target.display()));
+	}
+	let new_target = if make_relative {
+		get_relative_path(parent, &target)
+	} else {
+		target.clone()
+	};
+
+	let temp_path = get_temp_path(&path);
+	create_symlink(&new_target, &temp_path, target.is_dir())
+		.map_err(|err| err.to_string())?;
+
+	Ok(PreparedLink {
+		path,
+		temp_path,
+		old_target,
+	})
+}
+
+fn replace_link(path: &Path, target: &Path) -> std::io::Result<()> {
+	let temp_path = get_temp_path(path);
+	let is_dir = path
+		.parent()
+		.is_some_and(|parent| parent.join(target).is_dir());
+	create_symlink(target, &temp_path, is_dir)?;
+	fs::rename(&temp_path, path).inspect_err(|_| {
+		let _ = fs::remove_file(&temp_path);
+	})
+}
+
+fn get_temp_path(path: &Path) -> PathBuf {
+	let name = path.file_name().unwrap_or_default().to_string_lossy();
+	path.with_file_name(format!(".{}.czkawka-tmp", name))
+}
+
+#[cfg(unix)]
+fn create_symlink(
+	target: &Path,
+	link: &Path,
+	_is_dir: bool,
+) -> std::io::Result<()> {
+	std::os::unix::fs::symlink(target, link)
+}
+
+// Windows has separate links for files and folders
+#[cfg(windows)]
+fn create_symlink(
+	target: &Path,
+	link: &Path,
+	is_dir: bool,
+) -> std::io::Result<()> {
+	if is_dir {
+		std::os::windows::fs::symlink_dir(target, link)
+	} else {
+		std::os::windows::fs::symlink_file(target, link)
+	}
+}
+
+// Resolves `.` and `..` without touching the filesystem, the target of a
+// broken link can't be canonicalized
+fn normalize_path(path: &Path) -> PathBuf {
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				normalized.pop();
+			}
+			_ => normalized.push(component),
+		}
+	}
+	normalized
+}
+
+fn get_relative_path(from_dir: &Path, to: &Path) -> PathBuf {
+	let from: Vec<_> = normalize_path(from_dir).components().collect();
+	let to_path = normalize_path(to);
+	let to: Vec<_> = to_path.components().collect();
+
+	// Different drives have no relative path between them
+	if from.first() != to.first() {
+		return to_path;
+	}
+
+	let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+	let mut relative = PathBuf::new();
+	for _ in common..from.len() {
+		relative.push("..");
+	}
+	for component in &to[common..] {
+		relative.push(component);
+	}
+	relative
+}
+
 crate::gen_set_scaner_state_fn!(
 	same_invalid_symlinks,
 	czkawka_core::tools::invalid_symlinks::InvalidSymlinks
@@ -0,0 +1,130 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use czkawka_core::tools::big_file::SearchMode;
+use rayon::prelude::*;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+	file_walker::{FileWalker, get_modified_date},
+	progress::ProgressReporter,
+	scaner::spawn_scaner_thread,
+	settings::Settings,
+	state::get_stop_flag_and_progress_tx,
+};
+
+#[derive(Serialize, Clone)]
+struct CustomFolderSizeEntry {
+	path: String,
+	size: u64,
+	direct_size: u64,
+	file_count: usize,
+	direct_file_count: usize,
+	modified_date: u64,
+}
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<CustomFolderSizeEntry>,
+	message: String,
+}
+
+#[derive(Default)]
+struct FolderSize {
+	size: u64,
+	direct_size: u64,
+	file_count: usize,
+	direct_file_count: usize,
+}
+
+// Uses the top-N limit and search mode of the big files tool, folders are
+// ranked by recursive size, direct size or file count
+pub fn scan_largest_folders(app: AppHandle, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+
+		let search_mode = match settings.biggest_files_sub_method.as_ref() {
+			"SmallestFiles" => SearchMode::SmallestFiles,
+			_ => SearchMode::BiggestFiles,
+		};
+		let number_of_folders =
+			settings.biggest_files_sub_number_of_files.max(1) as usize;
+
+		// Every file takes up space in its folder, the size limits of the
+		// settings are not applied here
+		let walker = FileWalker::from_settings(&settings).ignore_file_size();
+		let progress = ProgressReporter::new(&progress_tx, 0);
+		let files = walker.walk(&stop_flag, Some(&progress));
+		drop(progress);
+		let roots = walker.included_directories();
+
+		let mut folders: HashMap<PathBuf, FolderSize> = HashMap::new();
+		for file in &files {
+			let Some(parent) = file.path.parent() else {
+				continue;
+			};
+			let folder = folders.entry(parent.to_path_buf()).or_default();
+			folder.direct_size += file.size;
+			folder.direct_file_count += 1;
+
+			for ancestor in parent.ancestors() {
+				let folder = folders.entry(ancestor.to_path_buf()).or_default();
+				folder.size += file.size;
+				folder.file_count += 1;
+
+				if roots.iter().any(|root| root == ancestor) {
+					break;
+				}
+			}
+		}
+
+		// The included directories themselves always rank first, only the
+		// folders inside them are listed
+		let mut ranked: Vec<_> = folders
+			.into_iter()
+			.filter(|(path, _)| !roots.iter().any(|root| root == path))
+			.collect();
+		let key = |folder: &FolderSize| match settings
+			.largest_folders_sub_rank_by
+			.as_ref()
+		{
+			"directSize" => folder.direct_size,
+			"fileCount" => folder.file_count as u64,
+			_ => folder.size,
+		};
+		if search_mode == SearchMode::BiggestFiles {
+			ranked
+				.par_sort_unstable_by_key(|(_, folder)| u64::MAX - key(folder));
+		} else {
+			ranked.par_sort_unstable_by_key(|(_, folder)| key(folder));
+		}
+		ranked.truncate(number_of_folders);
+
+		let list: Vec<_> = ranked
+			.into_iter()
+			.map(|(path, folder)| CustomFolderSizeEntry {
+				modified_date: fs::metadata(&path)
+					.map(|metadata| get_modified_date(&metadata))
+					.unwrap_or_default(),
+				path: path.to_string_lossy().to_string(),
+				size: folder.size,
+				direct_size: folder.direct_size,
+				file_count: folder.file_count,
+				direct_file_count: folder.direct_file_count,
+			})
+			.collect();
+
+		let message = format!("Found {} folders", list.len());
+
+		app.emit(
+			"scan-result",
+			ScanResult {
+				cmd: "scan_largest_folders",
+				list,
+				message,
+			},
+		)
+		.unwrap();
+	});
+}
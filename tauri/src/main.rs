@@ -1,27 +1,43 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod archive;
 mod bad_extensions;
 mod big_files;
 mod broken_files;
 mod delete_files;
+mod disk_usage;
 mod duplicate_files;
+mod duplicate_folders;
 mod empty_files;
 mod empty_folders;
 mod ffmpeg;
+mod file_hash;
+mod file_walker;
+mod find_copies;
 mod image;
+mod image_transforms;
 mod invalid_symlinks;
+mod largest_folders;
 mod move_files;
 mod music_duplicates;
+mod name_collisions;
+mod name_portability;
+mod numbered_copies;
+mod orphaned_sidecars;
 mod progress;
 mod rename_ext;
+mod sample_search;
 mod save_result;
 mod scaner;
 mod settings;
 mod similar_images;
+mod similar_names;
 mod similar_videos;
+mod stale_files;
 mod state;
 mod temporary_files;
 mod utils;
+mod video_info;
 
 use std::sync::Mutex;
 
@@ -32,6 +48,7 @@ use czkawka_core::common::{
 use tauri::{AppHandle, Emitter, Manager, State};
 
 use crate::{
+	disk_usage::DiskUsageNode,
 	ffmpeg::set_ffmpeg_path,
 	image::ImageInfo,
 	progress::process_progress_data,
@@ -63,13 +80,21 @@ fn main() {
 			listen_scan_progress,
 			read_image,
 			scan_duplicate_files,
+			scan_duplicate_folders,
 			scan_empty_folders,
 			scan_big_files,
+			scan_largest_folders,
+			scan_disk_usage,
+			get_disk_usage_tree,
 			scan_empty_files,
 			scan_temporary_files,
 			scan_similar_images,
+			scan_similar_names,
 			scan_similar_videos,
+			scan_stale_files,
 			scan_music_duplicates,
+			scan_numbered_copies,
+			scan_orphaned_sidecars,
 			scan_invalid_symlinks,
 			scan_broken_files,
 			scan_bad_extensions,
@@ -77,6 +102,15 @@ fn main() {
 			delete_files,
 			save_result,
 			rename_ext,
+			fix_bad_extensions,
+			scan_name_portability,
+			scan_name_collisions,
+			fix_names,
+			repair_symlinks,
+			check_ffmpeg,
+			regroup_similar_images,
+			find_similar_to_sample,
+			find_file_copies,
 		])
 		.plugin(tauri_plugin_opener::init())
 		.plugin(tauri_plugin_dialog::init())
@@ -154,11 +188,45 @@ fn scan_big_files(app: AppHandle, settings: Settings) {
 	big_files::scan_big_files(app, settings);
 }
 
+#[tauri::command]
+fn scan_largest_folders(app: AppHandle, settings: Settings) {
+	largest_folders::scan_largest_folders(app, settings);
+}
+
+#[tauri::command]
+fn scan_disk_usage(app: AppHandle, settings: Settings) {
+	disk_usage::scan_disk_usage(app, settings);
+}
+
+// Reading the metadata of every node blocks, so it runs off the main thread
+#[tauri::command]
+async fn get_disk_usage_tree(
+	app: AppHandle,
+	path: Option<String>,
+	depth: usize,
+) -> Result<Vec<DiskUsageNode>, String> {
+	tauri::async_runtime::spawn_blocking(move || {
+		disk_usage::get_disk_usage_tree(app, path, depth)
+	})
+	.await
+	.map_err(|err| err.to_string())?
+}
+
 #[tauri::command]
 fn scan_duplicate_files(app: AppHandle, settings: Settings) {
 	duplicate_files::scan_duplicate_files(app, settings);
 }
 
+#[tauri::command]
+fn scan_duplicate_folders(app: AppHandle, settings: Settings) {
+	duplicate_folders::scan_duplicate_folders(app, settings);
+}
+
+#[tauri::command]
+fn find_file_copies(app: AppHandle, path: String, settings: Settings) {
+	find_copies::find_file_copies(app, path, settings);
+}
+
 #[tauri::command]
 fn scan_empty_folders(app: AppHandle, settings: Settings) {
 	empty_folders::scan_empty_folders(app, settings);
@@ -179,16 +247,50 @@ fn scan_similar_images(app: AppHandle, settings: Settings) {
 	similar_images::scan_similar_images(app, settings);
 }
 
+#[tauri::command]
+fn regroup_similar_images(app: AppHandle, similarity: u32) {
+	similar_images::regroup_similar_images(app, similarity);
+}
+
+#[tauri::command]
+fn find_similar_to_sample(
+	app: AppHandle,
+	sample_path: String,
+	settings: Settings,
+) {
+	sample_search::find_similar_to_sample(app, sample_path, settings);
+}
+
+#[tauri::command]
+fn scan_similar_names(app: AppHandle, settings: Settings) {
+	similar_names::scan_similar_names(app, settings);
+}
+
 #[tauri::command]
 fn scan_similar_videos(app: AppHandle, settings: Settings) {
 	similar_videos::scan_similar_videos(app, settings);
 }
 
+#[tauri::command]
+fn scan_stale_files(app: AppHandle, settings: Settings) {
+	stale_files::scan_stale_files(app, settings);
+}
+
 #[tauri::command]
 fn scan_music_duplicates(app: AppHandle, settings: Settings) {
 	music_duplicates::scan_music_duplicates(app, settings);
 }
 
+#[tauri::command]
+fn scan_numbered_copies(app: AppHandle, settings: Settings) {
+	numbered_copies::scan_numbered_copies(app, settings);
+}
+
+#[tauri::command]
+fn scan_orphaned_sidecars(app: AppHandle, settings: Settings) {
+	orphaned_sidecars::scan_orphaned_sidecars(app, settings);
+}
+
 #[tauri::command]
 fn scan_invalid_symlinks(app: AppHandle, settings: Settings) {
 	invalid_symlinks::scan_invalid_symlinks(app, settings);
@@ -223,3 +325,33 @@ fn save_result(app: AppHandle, options: save_result::Options) {
 fn rename_ext(app: AppHandle, options: rename_ext::Options) {
 	rename_ext::rename_ext(app, options);
 }
+
+#[tauri::command]
+fn fix_bad_extensions(app: AppHandle, options: bad_extensions::FixOptions) {
+	bad_extensions::fix_bad_extensions(app, options);
+}
+
+#[tauri::command]
+fn scan_name_portability(app: AppHandle, settings: Settings) {
+	name_portability::scan_name_portability(app, settings);
+}
+
+#[tauri::command]
+fn scan_name_collisions(app: AppHandle, settings: Settings) {
+	name_collisions::scan_name_collisions(app, settings);
+}
+
+#[tauri::command]
+fn fix_names(app: AppHandle, options: name_portability::Options) {
+	name_portability::fix_names(app, options);
+}
+
+#[tauri::command]
+fn repair_symlinks(app: AppHandle, options: invalid_symlinks::RepairOptions) {
+	invalid_symlinks::repair_symlinks(app, options);
+}
+
+#[tauri::command]
+fn check_ffmpeg(app: AppHandle, settings: Settings) {
+	ffmpeg::check_ffmpeg(app, settings);
+}
@@ -1,4 +1,5 @@
 use std::{
+	collections::HashMap,
 	fs, io,
 	path::{Path, PathBuf},
 };
@@ -7,6 +8,8 @@ use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
 
+use crate::duplicate_folders::check_folder_fingerprint;
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Options {
@@ -15,6 +18,8 @@ pub struct Options {
 	copy_mode: bool,
 	preserve_structure: bool,
 	override_mode: bool,
+	// Duplicate folders by path, with their fingerprint from the scan
+	folder_fingerprints: HashMap<String, String>,
 }
 
 #[derive(Serialize, Clone, Default)]
@@ -38,6 +43,7 @@ fn move_files_impl(options: Options) -> MoveFilesResult {
 		copy_mode,
 		preserve_structure,
 		override_mode,
+		folder_fingerprints,
 	} = options;
 
 	paths
@@ -53,6 +59,18 @@ fn move_files_impl(options: Options) -> MoveFilesResult {
 					return result;
 				}
 			};
+			// A moved folder is removed from its place afterwards
+			if !copy_mode
+				&& let Some(fingerprint) = folder_fingerprints.get(source_str)
+				&& let Err(err) =
+					check_folder_fingerprint(&source_path, fingerprint)
+			{
+				result
+					.errors
+					.push(format!("`{}` Failed, reason: {}", source_str, err));
+				return result;
+			}
+
 			let mut dest_path = PathBuf::from(&destination);
 
 			if preserve_structure && let Some(parent) = source_path.parent() {
@@ -120,7 +138,11 @@ fn move_item(source: &Path, dest: &Path) -> Result<(), io::Error> {
 	}
 
 	copy_item(source, dest)?;
-	fs::remove_file(source)?;
+	if source.is_dir() {
+		fs::remove_dir_all(source)?;
+	} else {
+		fs::remove_file(source)?;
+	}
 
 	Ok(())
 }
@@ -1,6 +1,24 @@
+use std::{
+	collections::BTreeMap,
+	fs,
+	path::Path,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
 use czkawka_core::{
 	common::{
-		model::CheckingMethod, split_path_compare, tool_data::CommonData,
+		cache::{
+			get_similar_music_cache_file,
+			load_cache_from_file_generalized_by_path,
+			save_cache_to_file_generalized,
+		},
+		model::CheckingMethod,
+		progress_data::CurrentStage,
+		split_path_compare,
+		tool_data::CommonData,
 		traits::Search,
 	},
 	tools::same_music::{
@@ -8,15 +26,29 @@ use czkawka_core::{
 	},
 };
 use rayon::prelude::*;
+use rusty_chromaprint::{Configuration, Fingerprinter, match_fingerprints};
 use serde::Serialize;
+use symphonia::core::{
+	audio::SampleBuffer, codecs::DecoderOptions,
+	errors::Error as SymphoniaError, formats::FormatOptions,
+	io::MediaSourceStream, meta::MetadataOptions, probe::Hint,
+};
 use tauri::{AppHandle, Emitter};
 
 use crate::{
+	file_walker::{FileWalker, get_modified_date},
+	progress::ProgressReporter,
 	scaner::{set_scaner_common_settings, spawn_scaner_thread},
 	settings::Settings,
 	state::get_stop_flag_and_progress_tx,
 };
 
+// The audio formats czkawka compares
+const AUDIO_EXTENSIONS: &[&str] = &[
+	"mp3", "flac", "wav", "ogg", "m4a", "aac", "aiff", "pcm", "aif", "aifc",
+	"m3a", "mp2", "mp4a", "mp2a", "mpga", "wave", "weba", "wma", "oga",
+];
+
 #[derive(Serialize, Clone)]
 struct CustomMusicEntry {
 	size: u64,
@@ -28,6 +60,34 @@ struct CustomMusicEntry {
 	length: String,
 	genre: String,
 	bitrate: u32,
+	matched_tags: Vec<MatchedTag>,
+	fingerprint_segments: Vec<FingerprintSegment>,
+}
+
+#[derive(Serialize, Clone)]
+struct MatchedTag {
+	field: &'static str,
+	value: String,
+}
+
+// Offsets and durations are in seconds, `compared_offset` is the position of
+// the segment in `compared_path`
+#[derive(Serialize, Clone)]
+struct FingerprintSegment {
+	compared_path: String,
+	offset: f32,
+	compared_offset: f32,
+	duration: f32,
+	score: f64,
+}
+
+struct ExplainOptions {
+	music_similarity: MusicSimilarity,
+	approximate_comparison: bool,
+	audio_check_type: CheckingMethod,
+	minimal_fragment_duration: f32,
+	maximum_difference: f64,
+	compare_only_similar_titles: bool,
 }
 
 #[derive(Serialize, Clone)]
@@ -41,40 +101,12 @@ pub fn scan_music_duplicates(app: AppHandle, settins: Settings) {
 	spawn_scaner_thread(move || {
 		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
 
-		let mut music_similarity: MusicSimilarity = MusicSimilarity::NONE;
-		if settins.similar_music_sub_title {
-			music_similarity |= MusicSimilarity::TRACK_TITLE;
-		}
-		if settins.similar_music_sub_artist {
-			music_similarity |= MusicSimilarity::TRACK_ARTIST;
-		}
-		if settins.similar_music_sub_bitrate {
-			music_similarity |= MusicSimilarity::BITRATE;
-		}
-		if settins.similar_music_sub_length {
-			music_similarity |= MusicSimilarity::LENGTH;
-		}
-		if settins.similar_music_sub_year {
-			music_similarity |= MusicSimilarity::YEAR;
-		}
-		if settins.similar_music_sub_genre {
-			music_similarity |= MusicSimilarity::GENRE;
-		}
-		if music_similarity == MusicSimilarity::NONE {
-			music_similarity =
-				MusicSimilarity::TRACK_TITLE | MusicSimilarity::TRACK_ARTIST;
-		}
-
-		let audio_check_type =
-			match settins.similar_music_sub_audio_check_type.as_ref() {
-				"Fingerprint" => CheckingMethod::AudioContent,
-				_ => CheckingMethod::AudioTags,
-			};
+		let explain_options = get_explain_options(&settins);
 
 		let mut scaner = SameMusic::new(SameMusicParameters::new(
-			music_similarity,
+			explain_options.music_similarity,
 			settins.similar_music_sub_approximate_comparison,
-			audio_check_type,
+			explain_options.audio_check_type,
 			settins.similar_music_sub_minimal_fragment_duration_value,
 			settins.similar_music_sub_maximum_difference_value as f64,
 			settins.similar_music_compare_fingerprints_only_with_similar_titles,
@@ -118,12 +150,9 @@ pub fn scan_music_duplicates(app: AppHandle, settins: Settings) {
 		);
 
 		let list = raw_list
-			.into_iter()
+			.into_par_iter()
 			.map(|(ref_item, item)| {
-				(
-					ref_item.map(music_entry_to_custom),
-					item.into_iter().map(music_entry_to_custom).collect(),
-				)
+				explain_group(ref_item, item, &explain_options)
 			})
 			.collect::<Vec<_>>();
 
@@ -141,7 +170,482 @@ pub fn scan_music_duplicates(app: AppHandle, settins: Settings) {
 	});
 }
 
-fn music_entry_to_custom(value: MusicEntry) -> CustomMusicEntry {
+// Compares the sample fingerprint against every audio file in the scanned
+// directories instead of grouping the whole library. Fingerprints in the
+// cache are reused for unchanged files and the others are computed here
+pub fn find_similar_music_to_sample(
+	app: &AppHandle,
+	sample_path: &Path,
+	settins: Settings,
+	stop_flag: &Arc<AtomicBool>,
+	progress: &ProgressReporter,
+) {
+	let options = ExplainOptions {
+		audio_check_type: CheckingMethod::AudioContent,
+		..get_explain_options(&settins)
+	};
+	let fingerprint_config = Configuration::preset_test1();
+
+	let (_, loaded_entries) =
+		load_cache_from_file_generalized_by_path::<MusicEntry>(
+			&get_similar_music_cache_file(false),
+			false,
+			&BTreeMap::new(),
+		);
+	let loaded_entries = loaded_entries.unwrap_or_default();
+	let mut computed_entries = vec![];
+
+	let sample = match loaded_entries.get(&*sample_path.to_string_lossy()) {
+		Some(entry) if !entry.fingerprint.is_empty() => entry.clone(),
+		_ => match fingerprint_file(sample_path, &fingerprint_config) {
+			Ok(entry) => {
+				computed_entries.push(entry.clone());
+				entry
+			}
+			Err(err) => {
+				emit_sample_result(
+					app,
+					vec![],
+					format!(
+						"Failed to fingerprint sample `{}`: {}",
+						sample_path.display(),
+						err
+					),
+				);
+				return;
+			}
+		},
+	};
+
+	let files: Vec<_> = FileWalker::from_settings(&settins)
+		.walk(stop_flag, Some(progress))
+		.into_iter()
+		.filter(|file| file.path != sample.path && is_audio(&file.path))
+		.collect();
+	progress.next_stage(
+		CurrentStage::SameMusicCalculatingFingerprints,
+		files.len(),
+		files.iter().map(|file| file.size).sum(),
+	);
+
+	// Entries fingerprinted here are marked, so they can be saved to the
+	// cache czkawka reads
+	let entries: Vec<_> = files
+		.into_par_iter()
+		.filter_map(|file| {
+			progress.add_entries(1);
+			progress.add_bytes(file.size);
+			if let Some(entry) =
+				loaded_entries.get(&*file.path.to_string_lossy())
+				&& entry.size == file.size
+				&& entry.modified_date == file.modified_date
+				&& !entry.fingerprint.is_empty()
+			{
+				return Some((entry.clone(), false));
+			}
+			if stop_flag.load(Ordering::Relaxed) {
+				return None;
+			}
+			match fingerprint_file(&file.path, &fingerprint_config) {
+				Ok(entry) => Some((entry, true)),
+				Err(err) => {
+					log::info!(
+						"Failed to fingerprint `{}`: {}",
+						file.path.display(),
+						err
+					);
+					None
+				}
+			}
+		})
+		.collect();
+
+	computed_entries.extend(
+		entries
+			.iter()
+			.filter(|(_, computed)| *computed)
+			.map(|(entry, _)| entry.clone()),
+	);
+	if settins.use_cache && !computed_entries.is_empty() {
+		save_fingerprints(loaded_entries, computed_entries, &settins);
+	}
+
+	let mut similar: Vec<_> = entries
+		.into_par_iter()
+		.filter_map(|(entry, _)| {
+			let fingerprint_segments = get_fingerprint_segments(
+				&entry,
+				&sample,
+				&fingerprint_config,
+				&options,
+			);
+			let best_score = get_best_score(&fingerprint_segments)?;
+			Some((
+				best_score,
+				music_entry_to_custom(entry, vec![], fingerprint_segments),
+			))
+		})
+		.collect();
+	similar.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+	let similar: Vec<_> = similar.into_iter().map(|(_, entry)| entry).collect();
+	let message = format!(
+		"Found {} music files similar to `{}`",
+		similar.len(),
+		sample.path.display()
+	);
+	let list = if similar.is_empty() {
+		vec![]
+	} else {
+		vec![(Some(music_entry_to_custom(sample, vec![], vec![])), similar)]
+	};
+
+	emit_sample_result(app, list, message);
+}
+
+// Merged into the loaded cache, which is saved as a whole like czkawka does
+fn save_fingerprints(
+	mut cache: BTreeMap<String, MusicEntry>,
+	computed_entries: Vec<MusicEntry>,
+	settins: &Settings,
+) {
+	for entry in computed_entries {
+		cache.insert(entry.path.to_string_lossy().to_string(), entry);
+	}
+
+	let messages = save_cache_to_file_generalized(
+		&get_similar_music_cache_file(false),
+		&cache,
+		settins.save_also_as_json,
+		settins.minimum_file_size as u64 * 1000,
+	);
+	for err in messages.errors {
+		log::info!("Failed to save fingerprints: {}", err);
+	}
+}
+
+fn emit_sample_result(
+	app: &AppHandle,
+	list: Vec<(Option<CustomMusicEntry>, Vec<CustomMusicEntry>)>,
+	message: String,
+) {
+	app.emit(
+		"scan-result",
+		ScanResult {
+			cmd: "scan_music_duplicates",
+			list,
+			message,
+		},
+	)
+	.unwrap();
+}
+
+fn is_audio(path: &Path) -> bool {
+	path.extension().is_some_and(|ext| {
+		AUDIO_EXTENSIONS
+			.contains(&ext.to_string_lossy().to_lowercase().as_ref())
+	})
+}
+
+// Decodes the whole file the same way czkawka does before fingerprinting,
+// tags are left empty since only the fingerprint is compared
+fn fingerprint_file(
+	path: &Path,
+	config: &Configuration,
+) -> Result<MusicEntry, String> {
+	let metadata = fs::metadata(path).map_err(|err| err.to_string())?;
+	let modified_date = get_modified_date(&metadata);
+
+	let file = fs::File::open(path).map_err(|err| err.to_string())?;
+	let mss = MediaSourceStream::new(Box::new(file), Default::default());
+	let mut hint = Hint::new();
+	if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+		hint.with_extension(ext);
+	}
+
+	let probed = symphonia::default::get_probe()
+		.format(
+			&hint,
+			mss,
+			&FormatOptions::default(),
+			&MetadataOptions::default(),
+		)
+		.map_err(|err| err.to_string())?;
+	let mut format = probed.format;
+
+	let track = format.default_track().ok_or("No audio track".to_string())?;
+	let track_id = track.id;
+	let sample_rate = track
+		.codec_params
+		.sample_rate
+		.ok_or("Unknown sample rate".to_string())?;
+	let channels = track
+		.codec_params
+		.channels
+		.ok_or("Unknown channels".to_string())?
+		.count() as u32;
+	let mut decoder = symphonia::default::get_codecs()
+		.make(&track.codec_params, &DecoderOptions::default())
+		.map_err(|err| err.to_string())?;
+
+	let mut printer = Fingerprinter::new(config);
+	printer
+		.start(sample_rate, channels)
+		.map_err(|err| err.to_string())?;
+
+	let mut sample_buf = None;
+	while let Ok(packet) = format.next_packet() {
+		if packet.track_id() != track_id {
+			continue;
+		}
+		match decoder.decode(&packet) {
+			Ok(audio_buf) => {
+				let buf = sample_buf.get_or_insert_with(|| {
+					SampleBuffer::<i16>::new(
+						audio_buf.capacity() as u64,
+						*audio_buf.spec(),
+					)
+				});
+				buf.copy_interleaved_ref(audio_buf);
+				printer.consume(buf.samples());
+			}
+			Err(SymphoniaError::DecodeError(_)) => {}
+			Err(_) => break,
+		}
+	}
+	printer.finish();
+
+	Ok(MusicEntry {
+		path: path.to_path_buf(),
+		size: metadata.len(),
+		modified_date,
+		fingerprint: printer.fingerprint().to_vec(),
+		..Default::default()
+	})
+}
+
+fn get_explain_options(settins: &Settings) -> ExplainOptions {
+	let mut music_similarity: MusicSimilarity = MusicSimilarity::NONE;
+	if settins.similar_music_sub_title {
+		music_similarity |= MusicSimilarity::TRACK_TITLE;
+	}
+	if settins.similar_music_sub_artist {
+		music_similarity |= MusicSimilarity::TRACK_ARTIST;
+	}
+	if settins.similar_music_sub_bitrate {
+		music_similarity |= MusicSimilarity::BITRATE;
+	}
+	if settins.similar_music_sub_length {
+		music_similarity |= MusicSimilarity::LENGTH;
+	}
+	if settins.similar_music_sub_year {
+		music_similarity |= MusicSimilarity::YEAR;
+	}
+	if settins.similar_music_sub_genre {
+		music_similarity |= MusicSimilarity::GENRE;
+	}
+	if music_similarity == MusicSimilarity::NONE {
+		music_similarity =
+			MusicSimilarity::TRACK_TITLE | MusicSimilarity::TRACK_ARTIST;
+	}
+
+	let audio_check_type =
+		match settins.similar_music_sub_audio_check_type.as_ref() {
+			"Fingerprint" => CheckingMethod::AudioContent,
+			_ => CheckingMethod::AudioTags,
+		};
+
+	ExplainOptions {
+		music_similarity,
+		approximate_comparison: settins
+			.similar_music_sub_approximate_comparison,
+		audio_check_type,
+		minimal_fragment_duration: settins
+			.similar_music_sub_minimal_fragment_duration_value,
+		maximum_difference: settins.similar_music_sub_maximum_difference_value
+			as f64,
+		compare_only_similar_titles: settins
+			.similar_music_compare_fingerprints_only_with_similar_titles,
+	}
+}
+
+// czkawka doesn't keep which entries matched each other, so every entry is
+// explained against the other entry of its group it matches best
+fn explain_group(
+	ref_item: Option<MusicEntry>,
+	items: Vec<MusicEntry>,
+	options: &ExplainOptions,
+) -> (Option<CustomMusicEntry>, Vec<CustomMusicEntry>) {
+	let fingerprint_config = Configuration::preset_test1();
+	let group: Vec<_> = ref_item.iter().chain(&items).cloned().collect();
+
+	let to_custom = |value: MusicEntry| {
+		let matched_tags = get_matched_tags(&value, options);
+		let fingerprint_segments =
+			if options.audio_check_type == CheckingMethod::AudioContent {
+				group
+					.iter()
+					.map(|compared| {
+						get_fingerprint_segments(
+							&value,
+							compared,
+							&fingerprint_config,
+							options,
+						)
+					})
+					.filter_map(|segments| {
+						Some((get_best_score(&segments)?, segments))
+					})
+					.min_by(|(a, _), (b, _)| a.total_cmp(b))
+					.map(|(_, segments)| segments)
+					.unwrap_or_default()
+			} else {
+				vec![]
+			};
+		music_entry_to_custom(value, matched_tags, fingerprint_segments)
+	};
+
+	(
+		ref_item.map(&to_custom),
+		items.into_par_iter().map(&to_custom).collect(),
+	)
+}
+
+fn get_matched_tags(
+	value: &MusicEntry,
+	options: &ExplainOptions,
+) -> Vec<MatchedTag> {
+	let ExplainOptions {
+		music_similarity,
+		approximate_comparison,
+		audio_check_type,
+		compare_only_similar_titles,
+		..
+	} = options;
+	let mut matched_tags = vec![];
+
+	// Fingerprint mode compares no tags, except the simplified titles when
+	// it is limited to similar titles
+	let (music_similarity, approximate_comparison) = match audio_check_type {
+		CheckingMethod::AudioTags => {
+			(*music_similarity, *approximate_comparison)
+		}
+		_ if *compare_only_similar_titles => {
+			(MusicSimilarity::TRACK_TITLE, true)
+		}
+		_ => return matched_tags,
+	};
+
+	if music_similarity.contains(MusicSimilarity::TRACK_TITLE) {
+		matched_tags.push(MatchedTag {
+			field: "title",
+			value: normalize_tag(&value.track_title, approximate_comparison),
+		});
+	}
+	if music_similarity.contains(MusicSimilarity::TRACK_ARTIST) {
+		matched_tags.push(MatchedTag {
+			field: "artist",
+			value: normalize_tag(&value.track_artist, approximate_comparison),
+		});
+	}
+	if music_similarity.contains(MusicSimilarity::YEAR) {
+		matched_tags.push(MatchedTag {
+			field: "year",
+			value: value.year.trim().to_string(),
+		});
+	}
+	if music_similarity.contains(MusicSimilarity::LENGTH) {
+		matched_tags.push(MatchedTag {
+			field: "length",
+			value: value.length.trim().to_string(),
+		});
+	}
+	if music_similarity.contains(MusicSimilarity::GENRE) {
+		matched_tags.push(MatchedTag {
+			field: "genre",
+			value: normalize_tag(&value.genre, false),
+		});
+	}
+	if music_similarity.contains(MusicSimilarity::BITRATE) {
+		matched_tags.push(MatchedTag {
+			field: "bitrate",
+			value: value.bitrate.to_string(),
+		});
+	}
+
+	matched_tags
+}
+
+// A copy of czkawka's private tag normalization, so the values shown are
+// only indicative of what was compared and the UI labels them as
+// approximate. Approximate comparison ignores anything in brackets like
+// `(Remix)` and every character that is not alphanumeric
+fn normalize_tag(value: &str, approximate: bool) -> String {
+	let value = value.trim().to_lowercase();
+
+	if !approximate {
+		return value;
+	}
+
+	let mut depth = 0usize;
+	let mut normalized = String::with_capacity(value.len());
+
+	for c in value.chars() {
+		match c {
+			'(' | '[' | '{' => depth += 1,
+			')' | ']' | '}' => depth = depth.saturating_sub(1),
+			_ if depth == 0 && c.is_alphanumeric() => normalized.push(c),
+			_ => {}
+		}
+	}
+
+	normalized
+}
+
+fn get_fingerprint_segments(
+	value: &MusicEntry,
+	compared: &MusicEntry,
+	config: &Configuration,
+	options: &ExplainOptions,
+) -> Vec<FingerprintSegment> {
+	if value.path == compared.path {
+		return vec![];
+	}
+
+	let Ok(segments) =
+		match_fingerprints(&value.fingerprint, &compared.fingerprint, config)
+	else {
+		return vec![];
+	};
+
+	segments
+		.into_iter()
+		.filter(|segment| {
+			segment.duration(config) >= options.minimal_fragment_duration
+				&& segment.score <= options.maximum_difference
+		})
+		.map(|segment| FingerprintSegment {
+			compared_path: compared.path.to_string_lossy().to_string(),
+			offset: segment.start1(config),
+			compared_offset: segment.start2(config),
+			duration: segment.duration(config),
+			score: segment.score,
+		})
+		.collect()
+}
+
+fn get_best_score(segments: &[FingerprintSegment]) -> Option<f64> {
+	segments
+		.iter()
+		.map(|segment| segment.score)
+		.min_by(f64::total_cmp)
+}
+
+fn music_entry_to_custom(
+	value: MusicEntry,
+	matched_tags: Vec<MatchedTag>,
+	fingerprint_segments: Vec<FingerprintSegment>,
+) -> CustomMusicEntry {
 	CustomMusicEntry {
 		size: value.size,
 		path: value.path.to_string_lossy().to_string(),
@@ -152,6 +656,8 @@ fn music_entry_to_custom(value: MusicEntry) -> CustomMusicEntry {
 		length: value.length,
 		genre: value.genre,
 		bitrate: value.bitrate,
+		matched_tags,
+		fingerprint_segments,
 	}
 }
 
@@ -0,0 +1,229 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use unicode_normalization::{UnicodeNormalization, is_nfc, is_nfd};
+
+use crate::{
+	file_hash::{get_full_hashes, get_hash_type},
+	file_walker::{FileWalker, WalkedFile, get_modified_date},
+	progress::ProgressReporter,
+	scaner::spawn_scaner_thread,
+	settings::Settings,
+	state::get_stop_flag_and_progress_tx,
+};
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum CollisionKind {
+	Case,
+	Normalization,
+	CaseAndNormalization,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum ContentStatus {
+	Identical,
+	Different,
+	// Folders and mixes of files and folders are not compared
+	NotCompared,
+}
+
+#[derive(Serialize, Clone)]
+struct CustomCollisionEntry {
+	path: String,
+	is_dir: bool,
+	size: u64,
+	modified_date: u64,
+	// `NFC`, `NFD` or empty when the name is in neither form
+	normalization_form: &'static str,
+	kind: CollisionKind,
+	content: ContentStatus,
+}
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<(Option<CustomCollisionEntry>, Vec<CustomCollisionEntry>)>,
+	message: String,
+}
+
+// Names collide on case-insensitive or normalizing filesystems, like the
+// ones of macOS and Windows, when this key is the same
+fn collision_key(name: &str) -> String {
+	name.nfc().flat_map(char::to_lowercase).collect::<String>()
+}
+
+pub fn scan_name_collisions(app: AppHandle, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let hash_type = get_hash_type(&settings);
+
+		// Collecting and hashing the files that may be identical
+		let progress = ProgressReporter::new(&progress_tx, 1);
+		let walker = FileWalker::from_settings(&settings).ignore_file_size();
+		let (folders, files) =
+			walker.walk_with_folders(&stop_flag, Some(&progress));
+
+		let entries: Vec<WalkedFile> = folders
+			.into_iter()
+			.map(|path| {
+				let modified_date = fs::symlink_metadata(&path)
+					.map(|metadata| get_modified_date(&metadata))
+					.unwrap_or_default();
+				WalkedFile {
+					path,
+					size: 0,
+					modified_date,
+				}
+			})
+			.collect();
+		let folder_count = entries.len();
+		let entries: Vec<_> = entries.into_iter().chain(files).collect();
+
+		let mut siblings: HashMap<(PathBuf, String), Vec<usize>> =
+			HashMap::new();
+		for (i, entry) in entries.iter().enumerate() {
+			let (Some(parent), Some(name)) =
+				(entry.path.parent(), entry.path.file_name())
+			else {
+				continue;
+			};
+			siblings
+				.entry((
+					parent.to_path_buf(),
+					collision_key(&name.to_string_lossy()),
+				))
+				.or_default()
+				.push(i);
+		}
+		let mut groups: Vec<Vec<usize>> = siblings
+			.into_values()
+			.filter(|group| group.len() > 1)
+			.collect();
+		groups.sort_by(|a, b| entries[a[0]].path.cmp(&entries[b[0]].path));
+
+		// Only files of the same size in one group can be identical
+		let to_hash: Vec<&WalkedFile> = groups
+			.iter()
+			.filter(|group| {
+				group.iter().all(|i| *i >= folder_count)
+					&& group
+						.iter()
+						.all(|i| entries[*i].size == entries[group[0]].size)
+			})
+			.flatten()
+			.map(|i| &entries[*i])
+			.collect();
+		let hashes: HashMap<&PathBuf, String> = to_hash
+			.iter()
+			.map(|file| &file.path)
+			.zip(get_full_hashes(
+				&to_hash,
+				hash_type,
+				&stop_flag,
+				Some(&progress),
+			))
+			.filter_map(|(path, hash)| hash.map(|hash| (path, hash)))
+			.collect();
+		drop(progress);
+
+		let mut identical_count = 0;
+		let list: Vec<_> = groups
+			.into_iter()
+			.map(|group| {
+				let names: Vec<String> = group
+					.iter()
+					.map(|i| {
+						entries[*i]
+							.path
+							.file_name()
+							.unwrap_or_default()
+							.to_string_lossy()
+							.to_string()
+					})
+					.collect();
+				let kind = get_collision_kind(&names);
+				let content = if group.iter().any(|i| *i < folder_count) {
+					ContentStatus::NotCompared
+				} else {
+					let first = hashes.get(&entries[group[0]].path);
+					if first.is_some()
+						&& group
+							.iter()
+							.all(|i| hashes.get(&entries[*i].path) == first)
+					{
+						identical_count += 1;
+						ContentStatus::Identical
+					} else {
+						ContentStatus::Different
+					}
+				};
+
+				let items = group
+					.iter()
+					.zip(&names)
+					.map(|(i, name)| {
+						let entry = &entries[*i];
+						CustomCollisionEntry {
+							path: entry.path.to_string_lossy().to_string(),
+							is_dir: *i < folder_count,
+							size: entry.size,
+							modified_date: entry.modified_date,
+							normalization_form: get_normalization_form(name),
+							kind,
+							content,
+						}
+					})
+					.collect();
+				(None, items)
+			})
+			.collect();
+
+		let message = format!(
+			"Found {} groups of colliding names, {} of them with identical content",
+			list.len(),
+			identical_count
+		);
+
+		app.emit(
+			"scan-result",
+			ScanResult {
+				cmd: "scan_name_collisions",
+				list,
+				message,
+			},
+		)
+		.unwrap();
+	});
+}
+
+// Names that only differ in case are equal once lowercased, and names that
+// only differ in normalization are equal once normalized
+fn get_collision_kind(names: &[String]) -> CollisionKind {
+	let all_equal =
+		|forms: Vec<String>| forms.iter().all(|form| *form == forms[0]);
+	let differs_in_case =
+		!all_equal(names.iter().map(|name| name.nfc().collect()).collect());
+	let differs_in_normalization =
+		!all_equal(names.iter().map(|name| name.to_lowercase()).collect());
+
+	match (differs_in_case, differs_in_normalization) {
+		(true, true) => CollisionKind::CaseAndNormalization,
+		(false, true) => CollisionKind::Normalization,
+		_ => CollisionKind::Case,
+	}
+}
+
+fn get_normalization_form(name: &str) -> &'static str {
+	if name.is_ascii() {
+		""
+	} else if is_nfc(name) {
+		"NFC"
+	} else if is_nfd(name) {
+		"NFD"
+	} else {
+		""
+	}
+}
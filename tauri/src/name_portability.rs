@@ -0,0 +1,370 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+	file_walker::{FileWalker, get_modified_date},
+	progress::ProgressReporter,
+	scaner::spawn_scaner_thread,
+	settings::Settings,
+	state::get_stop_flag_and_progress_tx,
+};
+
+const INVALID_CHARS: &[char] = &['"', '*', '/', ':', '<', '>', '?', '\\', '|'];
+
+const RESERVED_NAMES: &[&str] = &[
+	"CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6",
+	"COM7", "COM8", "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6",
+	"LPT7", "LPT8", "LPT9",
+];
+
+// `X:\` of the drive the tree is copied to
+const DRIVE_PREFIX_LEN: usize = 3;
+
+// Limits of a target filesystem as seen through the Windows API, lengths
+// are in UTF-16 code units
+struct Profile {
+	max_name_len: usize,
+	max_path_len: usize,
+	max_file_size: Option<u64>,
+}
+
+const FAT_PROFILE: Profile = Profile {
+	max_name_len: 255,
+	max_path_len: 260,
+	max_file_size: Some(u32::MAX as u64),
+};
+
+const EXFAT_PROFILE: Profile = Profile {
+	max_name_len: 255,
+	max_path_len: 260,
+	max_file_size: None,
+};
+
+const NTFS_PROFILE: Profile = Profile {
+	max_name_len: 255,
+	max_path_len: 260,
+	max_file_size: None,
+};
+
+// The Windows `MAX_PATH` limit also applies to a share, its
+// `\\server\share\` prefix isn't known here and is counted like a drive
+// letter
+const SMB_PROFILE: Profile = Profile {
+	max_name_len: 255,
+	max_path_len: 260,
+	max_file_size: None,
+};
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum NameIssue {
+	InvalidChars,
+	ControlChars,
+	TrailingDotOrSpace,
+	ReservedName,
+	NameTooLong,
+	PathTooLong,
+	FileTooLarge,
+}
+
+#[derive(Serialize, Clone)]
+struct CustomNameIssueEntry {
+	path: String,
+	is_dir: bool,
+	size: u64,
+	modified_date: u64,
+	issues: Vec<NameIssue>,
+	// Empty when renaming alone can't fix the issues
+	proposed_name: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<CustomNameIssueEntry>,
+	message: String,
+}
+
+#[derive(Deserialize)]
+pub struct Options {
+	items: Vec<Item>,
+}
+
+#[derive(Deserialize)]
+struct Item {
+	path: String,
+	name: String,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct FixNamesResult {
+	success_paths: Vec<String>,
+	errors: Vec<String>,
+}
+
+pub fn scan_name_portability(app: AppHandle, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let profile = match settings.name_portability_sub_target.as_ref() {
+			"fat" => &FAT_PROFILE,
+			"exfat" => &EXFAT_PROFILE,
+			"smb" => &SMB_PROFILE,
+			_ => &NTFS_PROFILE,
+		};
+
+		// Names are checked whatever the file size, the size limits of the
+		// settings are for finding files
+		let walker = FileWalker::from_settings(&settings).ignore_file_size();
+		let progress = ProgressReporter::new(&progress_tx, 0);
+		let (folders, files) =
+			walker.walk_with_folders(&stop_flag, Some(&progress));
+		drop(progress);
+		let roots = walker.included_directories();
+
+		let mut list: Vec<_> = folders
+			.iter()
+			.filter_map(|path| {
+				let metadata = fs::symlink_metadata(path).ok()?;
+				check_path(
+					path,
+					true,
+					0,
+					get_modified_date(&metadata),
+					roots,
+					profile,
+				)
+			})
+			.chain(files.iter().filter_map(|file| {
+				check_path(
+					&file.path,
+					false,
+					file.size,
+					file.modified_date,
+					roots,
+					profile,
+				)
+			}))
+			.collect();
+		list.sort_by(|a, b| a.path.cmp(&b.path));
+
+		let fixable = list
+			.iter()
+			.filter(|entry| !entry.proposed_name.is_empty())
+			.count();
+		let message = format!(
+			"Found {} paths that would break on the target, {} of them can be fixed by renaming",
+			list.len(),
+			fixable
+		);
+
+		app.emit(
+			"scan-result",
+			ScanResult {
+				cmd: "scan_name_portability",
+				list,
+				message,
+			},
+		)
+		.unwrap();
+	});
+}
+
+pub fn fix_names(app: AppHandle, options: Options) {
+	std::thread::spawn(move || {
+		let result = fix_names_impl(options);
+		app.emit("fix-names-result", result).unwrap();
+	});
+}
+
+// Deeper paths are renamed first, so renaming a folder never moves an item
+// that is still waiting to be renamed
+fn fix_names_impl(options: Options) -> FixNamesResult {
+	let Options { mut items } = options;
+	items.sort_by_key(|item| {
+		std::cmp::Reverse(Path::new(&item.path).components().count())
+	});
+
+	let mut result = FixNamesResult::default();
+
+	for item in items {
+		let old_path = PathBuf::from(&item.path);
+		let new_path = old_path.with_file_name(&item.name);
+
+		if new_path == old_path {
+			result.success_paths.push(item.path);
+			continue;
+		}
+		if item.name.is_empty() || item.name.contains(['/', '\\']) {
+			result
+				.errors
+				.push(format!("`{}` Failed, reason: invalid name", item.path));
+			continue;
+		}
+		if fs::symlink_metadata(&new_path).is_ok() {
+			result.errors.push(format!(
+				"`{}` Failed, reason: `{}` already exists",
+				item.path,
+				new_path.display()
+			));
+			continue;
+		}
+
+		match fs::rename(&old_path, &new_path) {
+			Ok(_) => result.success_paths.push(item.path),
+			Err(err) => result
+				.errors
+				.push(format!("`{}` Failed, reason: {}", item.path, err)),
+		}
+	}
+
+	result
+}
+
+fn check_path(
+	path: &Path,
+	is_dir: bool,
+	size: u64,
+	modified_date: u64,
+	roots: &[PathBuf],
+	profile: &Profile,
+) -> Option<CustomNameIssueEntry> {
+	let name = path.file_name()?.to_string_lossy();
+	let mut issues = vec![];
+
+	if name.contains(INVALID_CHARS) {
+		issues.push(NameIssue::InvalidChars);
+	}
+	if name.chars().any(|c| c.is_ascii_control()) {
+		issues.push(NameIssue::ControlChars);
+	}
+	if name.ends_with(['.', ' ']) {
+		issues.push(NameIssue::TrailingDotOrSpace);
+	}
+	if is_reserved_name(&name) {
+		issues.push(NameIssue::ReservedName);
+	}
+	if utf16_len(&name) > profile.max_name_len {
+		issues.push(NameIssue::NameTooLong);
+	}
+	// The tree is copied below the root of the target, only the part below
+	// the included directory counts
+	let relative = roots
+		.iter()
+		.filter_map(|root| path.strip_prefix(root).ok())
+		.min_by_key(|relative| relative.as_os_str().len())
+		.unwrap_or(path);
+	if DRIVE_PREFIX_LEN + utf16_len(&relative.to_string_lossy())
+		> profile.max_path_len
+	{
+		issues.push(NameIssue::PathTooLong);
+	}
+	if !is_dir && profile.max_file_size.is_some_and(|max| size > max) {
+		issues.push(NameIssue::FileTooLarge);
+	}
+
+	if issues.is_empty() {
+		return None;
+	}
+
+	let proposed_name = sanitize_name(&name, profile);
+	let proposed_name = if proposed_name != name
+		&& !issues.iter().any(|issue| {
+			matches!(issue, NameIssue::PathTooLong | NameIssue::FileTooLarge)
+		}) {
+		proposed_name
+	} else {
+		String::new()
+	};
+
+	Some(CustomNameIssueEntry {
+		path: path.to_string_lossy().to_string(),
+		is_dir,
+		size,
+		modified_date,
+		issues,
+		proposed_name,
+	})
+}
+
+// `CON`, `con.txt` and `COM1.tar.gz` are all reserved
+fn is_reserved_name(name: &str) -> bool {
+	let base = name.split('.').next().unwrap_or_default().trim_end();
+	RESERVED_NAMES
+		.iter()
+		.any(|reserved| reserved.eq_ignore_ascii_case(base))
+}
+
+fn sanitize_name(name: &str, profile: &Profile) -> String {
+	let mut sanitized: String = name
+		.chars()
+		.map(|c| {
+			if INVALID_CHARS.contains(&c) || c.is_ascii_control() {
+				'_'
+			} else {
+				c
+			}
+		})
+		.collect();
+
+	let trimmed_len = sanitized.trim_end_matches(['.', ' ']).len();
+	sanitized.truncate(trimmed_len);
+	if sanitized.is_empty() {
+		sanitized.push('_');
+	}
+
+	if is_reserved_name(&sanitized) {
+		sanitized.insert(0, '_');
+	}
+
+	// Keep the extension when shortening
+	if utf16_len(&sanitized) > profile.max_name_len {
+		let (stem, ext) = match sanitized.rfind('.') {
+			Some(i) if i > 0 => sanitized.split_at(i),
+			_ => (sanitized.as_str(), ""),
+		};
+		let mut len = utf16_len(ext);
+		let stem: String = stem
+			.chars()
+			.take_while(|c| {
+				len += c.len_utf16();
+				len <= profile.max_name_len
+			})
+			.collect();
+		sanitized = format!("{}{}", stem.trim_end_matches(['.', ' ']), ext);
+	}
+
+	sanitized
+}
+
+fn utf16_len(s: &str) -> usize {
+	s.encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn invalid_names_are_sanitized() {
+		assert_eq!(sanitize_name("a:b?c", &NTFS_PROFILE), "a_b_c");
+		assert_eq!(sanitize_name("tab\there", &NTFS_PROFILE), "tab_there");
+		assert_eq!(sanitize_name("name. . ", &NTFS_PROFILE), "name");
+		assert_eq!(sanitize_name("...", &NTFS_PROFILE), "_");
+		assert_eq!(sanitize_name("con.txt", &NTFS_PROFILE), "_con.txt");
+		assert_eq!(sanitize_name("valid.txt", &NTFS_PROFILE), "valid.txt");
+	}
+
+	#[test]
+	fn long_names_keep_the_extension() {
+		let name = format!("{}.jpeg", "ä".repeat(300));
+		let sanitized = sanitize_name(&name, &FAT_PROFILE);
+		assert_eq!(utf16_len(&sanitized), 255);
+		assert!(sanitized.ends_with("ä.jpeg"));
+	}
+}
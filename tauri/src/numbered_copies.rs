@@ -0,0 +1,288 @@
+use std::{
+	collections::{BTreeMap, HashMap},
+	fs,
+	path::{Path, PathBuf},
+	sync::LazyLock,
+};
+
+use czkawka_core::common::split_path_compare;
+use regex::Regex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+	file_hash::{get_full_hashes, get_hash_type},
+	file_walker::{FileWalker, WalkedFile, get_modified_date},
+	progress::ProgressReporter,
+	scaner::spawn_scaner_thread,
+	settings::Settings,
+	state::get_stop_flag_and_progress_tx,
+};
+
+// `report (1).pdf`, `report(2).pdf`
+static BRACKETED_NUMBER: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r"^(.+?) ?\(\d{1,3}\)$").unwrap());
+
+// `photo-2.jpg`, also a common way to number unrelated files, so it is only
+// reported when the base file exists
+static DASH_NUMBER: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r"^(.+?)-\d{1,3}$").unwrap());
+
+// `file (conflicted copy 2025-03-01).docx`,
+// `file (John's conflicted copy 2025-03-01).docx`
+static CONFLICTED_COPY: LazyLock<Regex> = LazyLock::new(|| {
+	Regex::new(r"^(.+?) \([^()]*conflicted copy[^()]*\)$").unwrap()
+});
+
+// `name.sync-conflict-20250301-120000-ABCDEF7.ext`, matched against the
+// whole file name since the marker sits before the extension
+static SYNC_CONFLICT: LazyLock<Regex> = LazyLock::new(|| {
+	Regex::new(r"^(.+?)\.sync-conflict-\d{8}-\d{6}-[0-9A-Z]{7}(\.[^.]+)?$")
+		.unwrap()
+});
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum CopyKind {
+	Base,
+	NumberedCopy,
+	ConflictedCopy,
+	SyncConflict,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum CopyStatus {
+	Base,
+	Identical,
+	Different,
+	Orphan,
+}
+
+#[derive(Serialize, Clone)]
+struct CustomCopyEntry {
+	path: String,
+	size: u64,
+	modified_date: u64,
+	base_path: String,
+	kind: CopyKind,
+	status: CopyStatus,
+}
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<(Option<CustomCopyEntry>, Vec<CustomCopyEntry>)>,
+	message: String,
+}
+
+struct FoundCopy<'a> {
+	file: &'a WalkedFile,
+	base_path: PathBuf,
+	kind: CopyKind,
+}
+
+pub fn scan_numbered_copies(app: AppHandle, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let hash_type = get_hash_type(&settings);
+
+		// Base files are looked up among every file, the size limits of the
+		// settings only apply to the copies
+		let minimal_file_size = settings.minimum_file_size as u64 * 1000;
+		let maximal_file_size = settings.maximum_file_size as u64 * 1000;
+		// Collecting and hashing the copies of the same size as their base
+		let progress = ProgressReporter::new(&progress_tx, 1);
+		let files = FileWalker::from_settings(&settings)
+			.ignore_file_size()
+			.walk(&stop_flag, Some(&progress));
+		let by_path: HashMap<&Path, &WalkedFile> = files
+			.iter()
+			.map(|file| (file.path.as_path(), file))
+			.collect();
+
+		let mut bases: HashMap<PathBuf, WalkedFile> = HashMap::new();
+		let mut groups: BTreeMap<PathBuf, Vec<FoundCopy>> = BTreeMap::new();
+
+		for file in &files {
+			if !(minimal_file_size..=maximal_file_size).contains(&file.size) {
+				continue;
+			}
+			let Some((base_path, kind)) = get_base_path(&file.path) else {
+				continue;
+			};
+			let base = by_path
+				.get(base_path.as_path())
+				.map(|base| WalkedFile {
+					path: base.path.clone(),
+					size: base.size,
+					modified_date: base.modified_date,
+				})
+				.or_else(|| read_base(&base_path));
+
+			// A dash and a number alone is too weak a hint without a base
+			if base.is_none() && kind == CopyKind::NumberedCopy {
+				let stem = file.path.file_stem().unwrap_or_default();
+				if !BRACKETED_NUMBER.is_match(&stem.to_string_lossy()) {
+					continue;
+				}
+			}
+
+			if let Some(base) = base {
+				bases.entry(base_path.clone()).or_insert(base);
+			}
+			groups
+				.entry(base_path.clone())
+				.or_default()
+				.push(FoundCopy {
+					file,
+					base_path,
+					kind,
+				});
+		}
+
+		// Only pairs of the same size need to be hashed
+		let mut to_hash: Vec<&WalkedFile> = vec![];
+		for (base_path, copies) in &groups {
+			let Some(base) = bases.get(base_path) else {
+				continue;
+			};
+			let same_size: Vec<_> = copies
+				.iter()
+				.filter(|copy| copy.file.size == base.size)
+				.map(|copy| copy.file)
+				.collect();
+			if !same_size.is_empty() {
+				to_hash.push(base);
+				to_hash.extend(same_size);
+			}
+		}
+		let hashes: HashMap<&Path, String> = to_hash
+			.iter()
+			.map(|file| file.path.as_path())
+			.zip(get_full_hashes(
+				&to_hash,
+				hash_type,
+				&stop_flag,
+				Some(&progress),
+			))
+			.filter_map(|(path, hash)| hash.map(|hash| (path, hash)))
+			.collect();
+		drop(progress);
+
+		let (mut identical, mut different, mut orphan) = (0, 0, 0);
+		let mut list = vec![];
+
+		for (base_path, copies) in groups {
+			let base = bases.get(&base_path);
+			let mut items: Vec<_> = copies
+				.into_iter()
+				.map(|copy| {
+					let status = match base {
+						None => CopyStatus::Orphan,
+						Some(base) => {
+							let base_hash = hashes.get(base.path.as_path());
+							let copy_hash =
+								hashes.get(copy.file.path.as_path());
+							if base.size == copy.file.size
+								&& base_hash.is_some() && base_hash == copy_hash
+							{
+								CopyStatus::Identical
+							} else {
+								CopyStatus::Different
+							}
+						}
+					};
+					match status {
+						CopyStatus::Identical => identical += 1,
+						CopyStatus::Different => different += 1,
+						_ => orphan += 1,
+					}
+					to_custom(copy.file, &copy.base_path, copy.kind, status)
+				})
+				.collect();
+			items.sort_by(|a, b| {
+				split_path_compare(Path::new(&a.path), Path::new(&b.path))
+			});
+
+			let ref_item = base.map(|base| {
+				to_custom(base, &base_path, CopyKind::Base, CopyStatus::Base)
+			});
+			list.push((ref_item, items));
+		}
+
+		let message = format!(
+			"Found {} identical copies, {} conflicting copies and {} copies without a base file",
+			identical, different, orphan
+		);
+
+		app.emit(
+			"scan-result",
+			ScanResult {
+				cmd: "scan_numbered_copies",
+				list,
+				message,
+			},
+		)
+		.unwrap();
+	});
+}
+
+fn get_base_path(path: &Path) -> Option<(PathBuf, CopyKind)> {
+	let parent = path.parent()?;
+	let name = path.file_name()?.to_string_lossy();
+
+	if let Some(caps) = SYNC_CONFLICT.captures(&name) {
+		let ext = caps.get(2).map(|ext| ext.as_str()).unwrap_or_default();
+		return Some((
+			parent.join(format!("{}{}", &caps[1], ext)),
+			CopyKind::SyncConflict,
+		));
+	}
+
+	let stem = path.file_stem()?.to_string_lossy();
+	let ext = path
+		.extension()
+		.map(|ext| format!(".{}", ext.to_string_lossy()))
+		.unwrap_or_default();
+
+	let (base_stem, kind) = if let Some(caps) = CONFLICTED_COPY.captures(&stem)
+	{
+		(caps[1].to_string(), CopyKind::ConflictedCopy)
+	} else if let Some(caps) = BRACKETED_NUMBER.captures(&stem) {
+		(caps[1].to_string(), CopyKind::NumberedCopy)
+	} else if let Some(caps) = DASH_NUMBER.captures(&stem) {
+		(caps[1].to_string(), CopyKind::NumberedCopy)
+	} else {
+		return None;
+	};
+
+	Some((parent.join(format!("{}{}", base_stem, ext)), kind))
+}
+
+// The base file may be left out of the walk by the excluded extensions or
+// items, it is still a valid base
+fn read_base(path: &Path) -> Option<WalkedFile> {
+	let metadata = fs::symlink_metadata(path).ok()?;
+	metadata.is_file().then(|| WalkedFile {
+		path: path.to_path_buf(),
+		size: metadata.len(),
+		modified_date: get_modified_date(&metadata),
+	})
+}
+
+fn to_custom(
+	file: &WalkedFile,
+	base_path: &Path,
+	kind: CopyKind,
+	status: CopyStatus,
+) -> CustomCopyEntry {
+	CustomCopyEntry {
+		path: file.path.to_string_lossy().to_string(),
+		size: file.size,
+		modified_date: file.modified_date,
+		base_path: base_path.to_string_lossy().to_string(),
+		kind,
+		status,
+	}
+}
@@ -0,0 +1,213 @@
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+	file_walker::FileWalker, progress::ProgressReporter,
+	scaner::spawn_scaner_thread, settings::Settings,
+	state::get_stop_flag_and_progress_tx,
+};
+
+#[derive(Serialize, Clone)]
+struct CustomSidecarEntry {
+	path: String,
+	size: u64,
+	modified_date: u64,
+	primary_extensions: Vec<String>,
+}
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<CustomSidecarEntry>,
+	message: String,
+}
+
+// Sidecar extension to the extensions of the primary files it belongs to,
+// parsed from rules like `xmp: jpg, jpeg, dng`, one rule per line
+pub struct SidecarMap(HashMap<String, Vec<String>>);
+
+impl SidecarMap {
+	pub fn parse(rules: &str) -> Self {
+		let map = rules
+			.split(['\n', ';'])
+			.filter_map(|rule| {
+				let (sidecar, primaries) = rule.split_once(':')?;
+				let sidecar = normalize_ext(sidecar);
+				let primaries: Vec<_> = primaries
+					.split(',')
+					.map(normalize_ext)
+					.filter(|ext| !ext.is_empty())
+					.collect();
+				(!sidecar.is_empty() && !primaries.is_empty())
+					.then_some((sidecar, primaries))
+			})
+			.collect();
+		SidecarMap(map)
+	}
+
+	fn primaries_of(&self, path: &Path) -> Option<&[String]> {
+		let ext = path.extension()?.to_string_lossy().to_lowercase();
+		self.0.get(&ext).map(Vec::as_slice)
+	}
+}
+
+pub fn scan_orphaned_sidecars(app: AppHandle, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let map = SidecarMap::parse(&settings.sidecar_map);
+
+		// A sidecar is orphaned whatever its size, the size limits of the
+		// settings are for finding files
+		let walker = FileWalker::from_settings(&settings).ignore_file_size();
+		let progress = ProgressReporter::new(&progress_tx, 0);
+		let files = walker.walk(&stop_flag, Some(&progress));
+		drop(progress);
+
+		// Primaries are looked up in the folder itself, they may be filtered
+		// out of the walk by the extension settings
+		let mut folders: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+		let mut checked = 0;
+		let list: Vec<_> = files
+			.into_iter()
+			.filter_map(|file| {
+				let primaries = map.primaries_of(&file.path)?;
+				let parent = file.path.parent()?;
+				checked += 1;
+				let names = folders
+					.entry(parent.to_path_buf())
+					.or_insert_with(|| read_dir_names(parent));
+				if has_primary(&file.path, primaries, names) {
+					return None;
+				}
+				Some(CustomSidecarEntry {
+					path: file.path.to_string_lossy().to_string(),
+					size: file.size,
+					modified_date: file.modified_date,
+					primary_extensions: primaries.to_vec(),
+				})
+			})
+			.collect();
+
+		let message = format!(
+			"Found {} orphaned sidecars out of {} checked sidecars",
+			list.len(),
+			checked
+		);
+
+		app.emit(
+			"scan-result",
+			ScanResult {
+				cmd: "scan_orphaned_sidecars",
+				list,
+				message,
+			},
+		)
+		.unwrap();
+	});
+}
+
+// Sidecars left without a primary once the given primaries are gone. A
+// sidecar shared with a primary that still exists, like the `xmp` of a raw
+// and a jpg pair, is kept
+pub fn get_orphaned_sidecars_of(
+	primaries: &[PathBuf],
+	map: &SidecarMap,
+) -> Vec<PathBuf> {
+	let mut by_folder: HashMap<&Path, Vec<&Path>> = HashMap::new();
+	for primary in primaries {
+		if let Some(parent) = primary.parent() {
+			by_folder.entry(parent).or_default().push(primary);
+		}
+	}
+
+	let mut sidecars = vec![];
+	for (folder, primaries) in by_folder {
+		let Ok(read_dir) = fs::read_dir(folder) else {
+			continue;
+		};
+		let paths: Vec<_> = read_dir
+			.filter_map(|entry| entry.ok())
+			.filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+			.map(|entry| entry.path())
+			.collect();
+		let names: HashSet<_> = paths
+			.iter()
+			.filter_map(|path| path.file_name())
+			.map(|name| name.to_string_lossy().to_lowercase())
+			.collect();
+
+		for path in paths {
+			let Some(sidecar_primaries) = map.primaries_of(&path) else {
+				continue;
+			};
+			let belongs_to_deleted = primaries.iter().any(|primary| {
+				let Some(name) = primary.file_name() else {
+					return false;
+				};
+				let name = name.to_string_lossy().to_lowercase();
+				get_stems(&path).any(|stem| {
+					sidecar_primaries
+						.iter()
+						.any(|ext| name == format!("{stem}.{ext}"))
+				})
+			});
+			if belongs_to_deleted
+				&& !has_primary(&path, sidecar_primaries, &names)
+			{
+				sidecars.push(path);
+			}
+		}
+	}
+
+	sidecars
+}
+
+// `IMG_1.xmp`, `IMG_1.jpg.xmp` and `movie.en.srt` all name their primary
+// with a leading part of the stem
+fn has_primary(
+	sidecar: &Path,
+	primaries: &[String],
+	names: &HashSet<String>,
+) -> bool {
+	get_stems(sidecar).any(|stem| {
+		primaries
+			.iter()
+			.any(|ext| names.contains(&format!("{stem}.{ext}")))
+	})
+}
+
+// The lowercased stem of the path, followed by the stem with its trailing
+// extensions removed one at a time
+fn get_stems(path: &Path) -> impl Iterator<Item = String> {
+	let stem = path
+		.file_stem()
+		.map(|stem| stem.to_string_lossy().to_lowercase())
+		.unwrap_or_default();
+	std::iter::successors(Some(stem), |stem| {
+		stem.rsplit_once('.')
+			.map(|(head, _)| head.to_string())
+			.filter(|head| !head.is_empty())
+	})
+	.filter(|stem| !stem.is_empty())
+}
+
+fn read_dir_names(dir: &Path) -> HashSet<String> {
+	fs::read_dir(dir)
+		.map(|read_dir| {
+			read_dir
+				.filter_map(|entry| entry.ok())
+				.map(|entry| entry.file_name().to_string_lossy().to_lowercase())
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+fn normalize_ext(ext: &str) -> String {
+	ext.trim().trim_start_matches('.').to_lowercase()
+}
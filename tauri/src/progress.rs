@@ -1,10 +1,22 @@
+use std::{
+	sync::{
+		Arc, Mutex,
+		atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+	},
+	thread::{self, JoinHandle},
+	time::Duration,
+};
+
+use crossbeam_channel::Sender;
 use czkawka_core::common::{
-	model::ToolType,
+	model::{CheckingMethod, ToolType},
 	progress_data::{CurrentStage, ProgressData},
 };
 use humansize::{DECIMAL, format_size};
 use serde::Serialize;
 
+const PROGRESS_SEND_INTERVAL: Duration = Duration::from_millis(20);
+
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ProgressToSend {
@@ -13,8 +25,125 @@ pub struct ProgressToSend {
 	pub step_name: String,
 }
 
+// Sends the progress of the tools of this app the way czkawka's tools do,
+// from a thread that runs until the reporter is dropped
+pub struct ProgressReporter {
+	shared: Arc<ReporterShared>,
+	handle: Option<JoinHandle<()>>,
+}
+
+struct ReporterShared {
+	max_stage_idx: u8,
+	stage: Mutex<Option<ReporterStage>>,
+	entries_checked: AtomicUsize,
+	bytes_checked: AtomicU64,
+	running: AtomicBool,
+}
+
+#[derive(Clone, Copy)]
+struct ReporterStage {
+	sstage: CurrentStage,
+	current_stage_idx: u8,
+	entries_to_check: usize,
+	bytes_to_check: u64,
+}
+
+impl ProgressReporter {
+	// Nothing is sent until the first stage starts
+	pub fn new(progress_tx: &Sender<ProgressData>, max_stage_idx: u8) -> Self {
+		let shared = Arc::new(ReporterShared {
+			max_stage_idx,
+			stage: Mutex::new(None),
+			entries_checked: AtomicUsize::new(0),
+			bytes_checked: AtomicU64::new(0),
+			running: AtomicBool::new(true),
+		});
+
+		let thread_shared = shared.clone();
+		let progress_tx = progress_tx.clone();
+		let handle = thread::spawn(move || {
+			let shared = thread_shared;
+			while shared.running.load(Ordering::Relaxed) {
+				let stage = *shared.stage.lock().unwrap();
+				if let Some(stage) = stage {
+					let _ = progress_tx.send(shared.get_progress_data(stage));
+				}
+				thread::sleep(PROGRESS_SEND_INTERVAL);
+			}
+		});
+
+		ProgressReporter {
+			shared,
+			handle: Some(handle),
+		}
+	}
+
+	// Every call moves to the next stage, `0` entries to check means the
+	// count is unknown, like when collecting files
+	pub fn next_stage(
+		&self,
+		sstage: CurrentStage,
+		entries_to_check: usize,
+		bytes_to_check: u64,
+	) {
+		let mut stage = self.shared.stage.lock().unwrap();
+		let current_stage_idx = stage
+			.map(|stage| stage.current_stage_idx + 1)
+			.unwrap_or_default()
+			.min(self.shared.max_stage_idx);
+		self.shared.entries_checked.store(0, Ordering::Relaxed);
+		self.shared.bytes_checked.store(0, Ordering::Relaxed);
+		*stage = Some(ReporterStage {
+			sstage,
+			current_stage_idx,
+			entries_to_check,
+			bytes_to_check,
+		});
+	}
+
+	pub fn add_entries(&self, count: usize) {
+		self.shared
+			.entries_checked
+			.fetch_add(count, Ordering::Relaxed);
+	}
+
+	pub fn add_bytes(&self, count: u64) {
+		self.shared
+			.bytes_checked
+			.fetch_add(count, Ordering::Relaxed);
+	}
+}
+
+impl ReporterShared {
+	fn get_progress_data(&self, stage: ReporterStage) -> ProgressData {
+		ProgressData {
+			sstage: stage.sstage,
+			checking_method: CheckingMethod::None,
+			current_stage_idx: stage.current_stage_idx,
+			max_stage_idx: self.max_stage_idx,
+			entries_checked: self.entries_checked.load(Ordering::Relaxed),
+			entries_to_check: stage.entries_to_check,
+			bytes_checked: self.bytes_checked.load(Ordering::Relaxed),
+			bytes_to_check: stage.bytes_to_check,
+			tool_type: ToolType::None,
+		}
+	}
+}
+
+impl Drop for ProgressReporter {
+	fn drop(&mut self) {
+		self.shared.running.store(false, Ordering::Relaxed);
+		if let Some(handle) = self.handle.take() {
+			let _ = handle.join();
+		}
+	}
+}
+
 pub fn process_progress_data(progress_data: ProgressData) -> ProgressToSend {
-	if progress_data.current_stage_idx == 0 {
+	// The tools of this app also collect files in later stages
+	if progress_data.current_stage_idx == 0
+		|| matches!(progress_data.sstage, CurrentStage::CollectingFiles)
+	{
 		progress_collect_items(
 			&progress_data,
 			progress_data.tool_type != ToolType::EmptyFolders,
@@ -1,62 +1,319 @@
-use std::{fs, path::PathBuf};
+use std::{
+	fs::{self, File},
+	io::{self, BufReader, Read},
+	path::{Path, PathBuf},
+};
 
-use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
 
 #[derive(Deserialize)]
 pub struct Options {
 	items: Vec<Item>,
+	#[serde(flatten)]
+	conflict_options: ConflictOptions,
 }
 
 #[derive(Deserialize)]
-struct Item {
+pub struct Item {
+	pub path: String,
+	pub ext: String,
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ConflictOptions {
+	conflict_strategy: ConflictStrategy,
+	// Removes the file instead when the existing one has the same content
+	remove_identical: bool,
+	move_deleted_files_to_trash: bool,
+}
+
+// What to do when a file with the new name already exists
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+enum ConflictStrategy {
+	// Renames to the first free name like `a (1).jpg`
+	Suffix,
+	#[default]
+	Skip,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+enum ConflictAction {
+	Suffixed,
+	Skipped,
+	RemovedIdentical,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct Renamed {
 	path: String,
-	ext: String,
+	new_path: String,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct Conflict {
+	path: String,
+	// The existing file that has the new name
+	target: String,
+	action: ConflictAction,
+	// Empty unless the file was renamed with a suffix
+	new_path: String,
 }
 
 #[derive(Serialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-struct RenameExtResult {
-	success_paths: Vec<String>,
+pub struct RenameExtResult {
+	// Renamed or removed files, they are gone from the result table
+	pub success_paths: Vec<String>,
 	errors: Vec<String>,
+	renamed: Vec<Renamed>,
+	conflicts: Vec<Conflict>,
 }
 
 pub fn rename_ext(app: AppHandle, options: Options) {
 	std::thread::spawn(move || {
-		let result = rename_ext_impl(options);
+		let Options {
+			items,
+			conflict_options,
+		} = options;
+		let result = rename_ext_impl(items, &conflict_options);
 		app.emit("rename-ext-result", result).unwrap();
 	});
 }
 
-fn rename_ext_impl(options: Options) -> RenameExtResult {
-	let Options { items } = options;
+// Items are renamed one by one, two items may want the same new name, like
+// `a.jpeg` and `a.JPEG`, and must not overwrite each other
+pub fn rename_ext_impl(
+	items: Vec<Item>,
+	conflict_options: &ConflictOptions,
+) -> RenameExtResult {
+	let ConflictOptions {
+		conflict_strategy,
+		remove_identical,
+		move_deleted_files_to_trash,
+	} = *conflict_options;
+
+	let mut result = RenameExtResult::default();
+
+	for item in items {
+		let old_path = PathBuf::from(&item.path);
+		let mut new_path = old_path.clone();
+
+		new_path.set_extension(&item.ext);
 
-	items
-		.par_iter()
-		.fold(RenameExtResult::default, |mut result, item| {
-			let old_path = PathBuf::from(&item.path);
-			let mut new_path = old_path.clone();
+		if new_path == old_path {
+			result.success_paths.push(item.path);
+			continue;
+		}
 
-			new_path.set_extension(&item.ext);
+		// A rename that only changes the case of the extension finds the
+		// file itself on case-insensitive filesystems
+		let is_case_change = old_path.to_string_lossy().to_lowercase()
+			== new_path.to_string_lossy().to_lowercase();
+		let renamed = if is_case_change && is_same_file(&old_path, &new_path) {
+			fs::rename(&old_path, &new_path)
+		} else {
+			rename_no_replace(&old_path, &new_path)
+		};
 
-			if new_path == old_path {
-				result.success_paths.push(item.path.clone());
-				return result;
+		match renamed {
+			Ok(_) => {
+				result.renamed.push(Renamed {
+					path: item.path.clone(),
+					new_path: new_path.to_string_lossy().to_string(),
+				});
+				result.success_paths.push(item.path);
 			}
+			Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+				match resolve_conflict(
+					&old_path,
+					&new_path,
+					conflict_strategy,
+					remove_identical,
+					move_deleted_files_to_trash,
+				) {
+					Ok(conflict) => {
+						if let ConflictAction::Suffixed = conflict.action {
+							result.renamed.push(Renamed {
+								path: item.path.clone(),
+								new_path: conflict.new_path.clone(),
+							});
+						}
+						if !matches!(conflict.action, ConflictAction::Skipped) {
+							result.success_paths.push(item.path);
+						}
+						result.conflicts.push(conflict);
+					}
+					Err(err) => result.errors.push(format!(
+						"`{}` Failed, reason: {}",
+						item.path, err
+					)),
+				}
+			}
+			Err(err) => result
+				.errors
+				.push(format!("`{}` Failed, reason: {}", item.path, err)),
+		}
+	}
+
+	result
+}
+
+fn resolve_conflict(
+	old_path: &Path,
+	target: &Path,
+	conflict_strategy: ConflictStrategy,
+	remove_identical: bool,
+	move_deleted_files_to_trash: bool,
+) -> Result<Conflict, String> {
+	let mut conflict = Conflict {
+		path: old_path.to_string_lossy().to_string(),
+		target: target.to_string_lossy().to_string(),
+		action: ConflictAction::Skipped,
+		new_path: String::new(),
+	};
+
+	if remove_identical
+		&& has_same_content(old_path, target).map_err(|err| err.to_string())?
+	{
+		if move_deleted_files_to_trash {
+			trash::delete(old_path).map_err(|err| err.to_string())?;
+		} else {
+			fs::remove_file(old_path).map_err(|err| err.to_string())?;
+		}
+		conflict.action = ConflictAction::RemovedIdentical;
+		return Ok(conflict);
+	}
 
-			match fs::rename(old_path, new_path) {
-				Ok(_) => result.success_paths.push(item.path.clone()),
-				Err(err) => result
-					.errors
-					.push(format!("`{}` Failed, reason: {}", item.path, err)),
+	if let ConflictStrategy::Suffix = conflict_strategy {
+		// A free name may be taken before the rename, the next one is tried
+		let mut new_path = None;
+		for candidate in get_free_paths(target) {
+			match rename_no_replace(old_path, &candidate) {
+				Ok(_) => {
+					new_path = Some(candidate);
+					break;
+				}
+				Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+				Err(err) => return Err(err.to_string()),
 			}
+		}
+		let new_path = new_path.ok_or("no free name with a numbered suffix")?;
+		conflict.action = ConflictAction::Suffixed;
+		conflict.new_path = new_path.to_string_lossy().to_string();
+	}
+
+	Ok(conflict)
+}
+
+// `a.jpg` becomes `a (1).jpg`, `a (2).jpg` and so on
+fn get_free_paths(path: &Path) -> impl Iterator<Item = PathBuf> {
+	let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+	let ext = path
+		.extension()
+		.map(|ext| format!(".{}", ext.to_string_lossy()))
+		.unwrap_or_default();
+
+	(1..10000)
+		.map(move |i| path.with_file_name(format!("{} ({}){}", stem, i, ext)))
+		.filter(|candidate| fs::symlink_metadata(candidate).is_err())
+}
+
+// Renames without replacing a file that took the new name in the meantime,
+// creating a hard link fails when the name is taken. Filesystems without
+// hard links, like FAT, fall back to a rename after checking the name
+fn rename_no_replace(from: &Path, to: &Path) -> io::Result<()> {
+	match fs::hard_link(from, to) {
+		Ok(_) => fs::remove_file(from).inspect_err(|_| {
+			let _ = fs::remove_file(to);
+		}),
+		Err(err) if err.kind() == io::ErrorKind::AlreadyExists => Err(err),
+		Err(_) if fs::symlink_metadata(to).is_ok() => {
+			Err(io::ErrorKind::AlreadyExists.into())
+		}
+		Err(_) => fs::rename(from, to),
+	}
+}
+
+fn has_same_content(a: &Path, b: &Path) -> io::Result<bool> {
+	if fs::metadata(a)?.len() != fs::metadata(b)?.len() {
+		return Ok(false);
+	}
+
+	let mut a = BufReader::new(File::open(a)?);
+	let mut b = BufReader::new(File::open(b)?);
+	let mut buf_a = vec![0; 64 * 1024];
+	let mut buf_b = vec![0; 64 * 1024];
+	loop {
+		let len = a.read(&mut buf_a)?;
+		if len == 0 {
+			return Ok(true);
+		}
+		b.read_exact(&mut buf_b[..len])?;
+		if buf_a[..len] != buf_b[..len] {
+			return Ok(false);
+		}
+	}
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+	use std::os::unix::fs::MetadataExt;
+
+	match (fs::metadata(a), fs::metadata(b)) {
+		(Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+		_ => false,
+	}
+}
+
+#[cfg(not(unix))]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+	match (fs::canonicalize(a), fs::canonicalize(b)) {
+		(Ok(a), Ok(b)) => a
+			.to_string_lossy()
+			.eq_ignore_ascii_case(&b.to_string_lossy()),
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn existing_files_are_not_replaced() {
+		let dir = std::env::temp_dir()
+			.join(format!("czkawka-tauri-rename-{}", std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let old_path = dir.join("a.jpeg");
+		let target = dir.join("a.jpg");
+		fs::write(&old_path, "old").unwrap();
+		fs::write(&target, "target").unwrap();
+
+		let result = rename_ext_impl(
+			vec![Item {
+				path: old_path.to_string_lossy().to_string(),
+				ext: "jpg".to_string(),
+			}],
+			&ConflictOptions::default(),
+		);
+		let kept = (fs::read(&old_path), fs::read(&target));
+		fs::remove_dir_all(&dir).unwrap();
 
-			result
-		})
-		.reduce(RenameExtResult::default, |mut acc, mut x| {
-			acc.success_paths.append(&mut x.success_paths);
-			acc.errors.append(&mut x.errors);
-			acc
-		})
+		assert!(result.success_paths.is_empty());
+		assert!(matches!(
+			result.conflicts[..],
+			[Conflict {
+				action: ConflictAction::Skipped,
+				..
+			}]
+		));
+		assert_eq!(kept.0.unwrap(), b"old");
+		assert_eq!(kept.1.unwrap(), b"target");
+	}
 }
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use infer::MatcherType;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+	music_duplicates::find_similar_music_to_sample, progress::ProgressReporter,
+	scaner::spawn_scaner_thread, settings::Settings,
+	similar_images::find_similar_images_to_sample,
+	similar_videos::find_similar_videos_to_sample,
+	state::get_stop_flag_and_progress_tx,
+};
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<()>,
+	message: String,
+}
+
+// Results are emitted as a single group of the matching tool, with the
+// sample as the referenced item and the other files ranked by distance
+pub fn find_similar_to_sample(
+	app: AppHandle,
+	sample_path: String,
+	settings: Settings,
+) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let sample_path = PathBuf::from(sample_path);
+
+		let matcher_type = match infer::get_from_path(&sample_path) {
+			Ok(Some(kind)) => kind.matcher_type(),
+			Ok(None) => MatcherType::Custom,
+			Err(err) => {
+				emit_unsupported(
+					&app,
+					format!(
+						"Failed to read sample `{}`: {}",
+						sample_path.display(),
+						err
+					),
+				);
+				return;
+			}
+		};
+
+		// Collecting and hashing the files of the same type
+		let progress = ProgressReporter::new(&progress_tx, 1);
+		match matcher_type {
+			MatcherType::Image => find_similar_images_to_sample(
+				&app,
+				&sample_path,
+				settings,
+				&stop_flag,
+				&progress,
+			),
+			MatcherType::Video => find_similar_videos_to_sample(
+				&app,
+				&sample_path,
+				settings,
+				&stop_flag,
+				&progress,
+			),
+			MatcherType::Audio => find_similar_music_to_sample(
+				&app,
+				&sample_path,
+				settings,
+				&stop_flag,
+				&progress,
+			),
+			_ => emit_unsupported(
+				&app,
+				format!(
+					"`{}` is not an image, audio or video file",
+					sample_path.display()
+				),
+			),
+		}
+	});
+}
+
+fn emit_unsupported(app: &AppHandle, message: String) {
+	app.emit(
+		"scan-result",
+		ScanResult {
+			cmd: "find_similar_to_sample",
+			list: vec![],
+			message,
+		},
+	)
+	.unwrap();
+}
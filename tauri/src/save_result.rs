@@ -1,7 +1,11 @@
-use std::sync::Mutex;
+use std::{
+	fs::File,
+	io::{BufWriter, Write},
+	sync::Mutex,
+};
 
 use czkawka_core::common::traits::PrintResults;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter, Manager};
 
 use crate::state::AppState;
@@ -31,8 +35,14 @@ pub fn save_result(app: AppHandle, options: Options) {
 		"Big Files" => state.big_files_state.as_ref().map(|scaner| {
 			scaner.save_all_in_one(&destination, "results_big_files")
 		}),
-		"Empty Files" => state.empty_files_state.as_ref().map(|scaner| {
-			scaner.save_all_in_one(&destination, "results_empty_files")
+		// Effectively empty files are only known to this app
+		"Empty Files" => state.empty_files_entries.as_ref().map(|entries| {
+			save_entries(
+				entries,
+				&destination,
+				"results_empty_files",
+				|entry| entry.to_line(),
+			)
 		}),
 		"Temporary Files" => {
 			state.temporary_files_state.as_ref().map(|scaner| {
@@ -53,12 +63,26 @@ pub fn save_result(app: AppHandle, options: Options) {
 				scaner.save_all_in_one(&destination, "results_invalid_symlinks")
 			})
 		}
-		"Broken Files" => state.broken_files_state.as_ref().map(|scaner| {
-			scaner.save_all_in_one(&destination, "results_broken_files")
-		}),
-		"Bad Extensions" => state.bad_extensions_state.as_ref().map(|scaner| {
-			scaner.save_all_in_one(&destination, "results_bad_extensions")
+		// Custom checks are only known to this app
+		"Broken Files" => state.broken_files_entries.as_ref().map(|entries| {
+			save_entries(
+				entries,
+				&destination,
+				"results_broken_files",
+				|entry| entry.to_line(),
+			)
 		}),
+		// Custom signatures are only known to this app
+		"Bad Extensions" => {
+			state.bad_extensions_entries.as_ref().map(|entries| {
+				save_entries(
+					entries,
+					&destination,
+					"results_bad_extensions",
+					|entry| entry.to_line(),
+				)
+			})
+		}
 		_ => return,
 	};
 
@@ -75,3 +99,28 @@ pub fn save_result(app: AppHandle, options: Options) {
 
 	app.emit("save-result-done", message).unwrap();
 }
+
+// Saved like `PrintResults::save_all_in_one`, for results that are not
+// kept in a czkawka scaner
+fn save_entries<T: Serialize>(
+	entries: &[T],
+	folder: &str,
+	base_file_name: &str,
+	to_line: impl Fn(&T) -> String,
+) -> std::io::Result<()> {
+	let pretty_file =
+		File::create(format!("{folder}/{base_file_name}_pretty.json"))?;
+	serde_json::to_writer_pretty(BufWriter::new(pretty_file), entries)?;
+
+	let compact_file =
+		File::create(format!("{folder}/{base_file_name}_compact.json"))?;
+	serde_json::to_writer(BufWriter::new(compact_file), entries)?;
+
+	let text_file = File::create(format!("{folder}/{base_file_name}.txt"))?;
+	let mut writer = BufWriter::new(text_file);
+	writeln!(writer, "Found {} entries", entries.len())?;
+	for entry in entries {
+		writeln!(writer, "{}", to_line(entry))?;
+	}
+	writer.flush()
+}
@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use czkawka_core::common::{
 	consts::DEFAULT_THREAD_SIZE, tool_data::CommonData,
 };
@@ -41,3 +43,39 @@ pub fn set_scaner_common_settings<T: CommonData>(
 	scaner.set_use_cache(settings.use_cache);
 	scaner.set_save_also_as_json(settings.save_also_as_json);
 }
+
+// The reference folders of the common settings, used to split groups the
+// same way czkawka does
+#[derive(Default)]
+pub struct ScanScope {
+	reference_directories: Vec<PathBuf>,
+}
+
+impl ScanScope {
+	pub fn from_settings(settings: &Settings) -> Self {
+		let all_referenced = settings.included_directories.len()
+			== settings.included_directories_referenced.len();
+
+		ScanScope {
+			reference_directories: if all_referenced {
+				vec![]
+			} else {
+				convert_strs_to_path_bufs(
+					settings.included_directories_referenced.clone(),
+				)
+			},
+		}
+	}
+
+	pub fn use_reference(&self) -> bool {
+		!self.reference_directories.is_empty()
+	}
+
+	pub fn is_referenced(&self, path: &Path) -> bool {
+		is_in_directories(path, &self.reference_directories)
+	}
+}
+
+fn is_in_directories(path: &Path, directories: &[PathBuf]) -> bool {
+	directories.iter().any(|dir| path.starts_with(dir))
+}
@@ -14,6 +14,7 @@ pub struct Settings {
 	pub excluded_items: String,
 	pub allowed_extensions: String,
 	pub excluded_extensions: String,
+	pub sidecar_map: String,
 	pub minimum_file_size: i32,
 	pub maximum_file_size: i32,
 	pub recursive_search: bool,
@@ -33,14 +34,36 @@ pub struct Settings {
 	pub similar_images_sub_resize_algorithm: String,
 	pub similar_images_sub_ignore_same_size: bool,
 	pub similar_images_sub_similarity: i32,
+	pub similar_images_sub_rotation_invariant: bool,
 	pub duplicates_sub_check_method: String,
 	pub duplicates_sub_available_hash_type: String,
 	pub duplicates_sub_name_case_sensitive: bool,
+	pub duplicates_sub_scan_archives: bool,
+	pub duplicate_folders_sub_include_subsets: bool,
+	pub empty_folders_sub_ignorable_files: String,
+	pub empty_folders_sub_ignore_zero_byte_files: bool,
+	pub empty_files_sub_effectively_empty: bool,
+	pub empty_files_sub_whitespace: bool,
+	pub empty_files_sub_bom: bool,
+	pub empty_files_sub_nul: bool,
+	pub empty_files_sub_empty_container: bool,
+	pub similar_names_sub_threshold: i32,
+	pub stale_files_sub_time_kind: String,
+	pub stale_files_sub_days: i32,
+	pub name_portability_sub_target: String,
+	pub invalid_symlinks_sub_target_match: String,
+	pub invalid_symlinks_sub_outside_tree: bool,
 	pub biggest_files_sub_method: String,
 	pub biggest_files_sub_number_of_files: i32,
+	pub largest_folders_sub_rank_by: String,
 	pub similar_videos_hide_hard_links: bool,
 	pub similar_videos_sub_ignore_same_size: bool,
 	pub similar_videos_sub_similarity: i32,
+	pub similar_videos_sub_skip_forward_amount: i32,
+	pub similar_videos_sub_vid_hash_duration: i32,
+	pub similar_videos_sub_crop_detect: String,
+	pub ffmpeg_path: String,
+	pub ffprobe_path: String,
 	pub similar_music_sub_audio_check_type: String,
 	pub similar_music_sub_approximate_comparison: bool,
 	pub similar_music_compare_fingerprints_only_with_similar_titles: bool,
@@ -56,6 +79,12 @@ pub struct Settings {
 	pub broken_files_sub_pdf: bool,
 	pub broken_files_sub_archive: bool,
 	pub broken_files_sub_image: bool,
+	pub broken_files_sub_video: bool,
+	pub broken_files_sub_document: bool,
+	pub broken_files_sub_font: bool,
+	pub broken_files_sub_deep_archive: bool,
+	pub bad_extensions_sub_custom_signatures: String,
+	pub bad_extensions_sub_mime_extensions: String,
 }
 
 #[derive(Serialize)]
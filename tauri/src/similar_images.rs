@@ -1,21 +1,67 @@
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	fs,
+	path::{Path, PathBuf},
+	sync::{
+		Arc, Mutex,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
 use czkawka_core::{
-	common::{tool_data::CommonData, traits::Search},
+	common::{
+		cache::{
+			get_similar_images_cache_file,
+			load_cache_from_file_generalized_by_path,
+		},
+		image::get_dynamic_image_from_path,
+		progress_data::CurrentStage,
+		tool_data::CommonData,
+		traits::Search,
+	},
 	tools::similar_images::{
-		ImagesEntry, SimilarImages, SimilarImagesParameters,
+		ImHash, ImagesEntry, SimilarImages, SimilarImagesParameters,
 		core::get_string_from_similarity,
 	},
 };
-use image_hasher::{FilterType, HashAlg};
+use image_hasher::{FilterType, HashAlg, HasherConfig};
 use rayon::prelude::*;
 use serde::Serialize;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 
 use crate::{
-	scaner::{set_scaner_common_settings, spawn_scaner_thread},
+	file_walker::{FileWalker, WalkedFile, get_modified_date},
+	image_transforms::{TRANSFORMS, TransformHasher, inverse_transform},
+	progress::ProgressReporter,
+	scaner::{ScanScope, set_scaner_common_settings, spawn_scaner_thread},
 	settings::Settings,
-	state::get_stop_flag_and_progress_tx,
+	state::{AppState, get_stop_flag_and_progress_tx},
 };
 
+// The image formats czkawka compares, including raw photos
+const IMAGE_EXTENSIONS: &[&str] = &[
+	"jpg", "jpeg", "png", "tiff", "tif", "tga", "ff", "jif", "jfi", "bmp",
+	"webp", "exr", "qoi", "mrw", "arw", "srf", "sr2", "mef", "orf", "srw",
+	"erf", "kdc", "dcs", "rw2", "raf", "dcr", "dng", "pef", "crw", "iiq",
+	"3fr", "nrw", "nef", "mos", "cr2", "ari",
+];
+
+// The highest difference the UI allows, czkawka names every difference up
+// to it
+const MAX_SIMILARITY: u32 = 40;
+
+// Hashes of every image in the scanned directories, kept after a scan so
+// the comparison and grouping step can be re-run with another threshold
+pub struct ImagesHashes {
+	hash_size: u8,
+	ignore_same_size: bool,
+	hide_hard_links: bool,
+	scope: ScanScope,
+	hashes: Vec<(ImHash, Vec<ImagesEntry>)>,
+	// Hashes of the rotated and flipped variants, in `TRANSFORMS` order
+	transform_hashes: Option<Vec<Option<Vec<ImHash>>>>,
+}
+
 #[derive(Serialize, Clone)]
 struct CustomImagesEntry {
 	path: String,
@@ -24,6 +70,9 @@ struct CustomImagesEntry {
 	height: u32,
 	modified_date: u64,
 	similarity: String,
+	distance: u32,
+	hash_size: u8,
+	transform: &'static str,
 }
 
 #[derive(Serialize, Clone)]
@@ -36,29 +85,19 @@ struct ScanResult {
 pub fn scan_similar_images(app: AppHandle, settins: Settings) {
 	spawn_scaner_thread(move || {
 		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		// The hashes of the previous scan no longer match the results
+		clear_images_hashes(&app);
+
+		let (hash_size, hash_alg, resize_algorithm) = get_hash_params(&settins);
+		let scan_scope = ScanScope::from_settings(&settins);
+		let walker = FileWalker::from_settings(&settins);
+		let ignore_same_size = settins.similar_images_sub_ignore_same_size;
+		let hide_hard_links = settins.similar_images_hide_hard_links;
+		let similarity = settins.similar_images_sub_similarity as u32;
+		let rotation_invariant = settins.similar_images_sub_rotation_invariant;
 
-		let hash_alg = match settins.similar_images_sub_hash_alg.as_ref() {
-			"Gradient" => HashAlg::Gradient,
-			"BlockHash" => HashAlg::Blockhash,
-			"VertGradient" => HashAlg::VertGradient,
-			"DoubleGradient" => HashAlg::DoubleGradient,
-			"Median" => HashAlg::Median,
-			_ => HashAlg::Mean,
-		};
-		let resize_algorithm =
-			match settins.similar_images_sub_resize_algorithm.as_ref() {
-				"Gaussian" => FilterType::Gaussian,
-				"CatmullRom" => FilterType::CatmullRom,
-				"Triangle" => FilterType::Triangle,
-				"Nearest" => FilterType::Nearest,
-				_ => FilterType::Lanczos3,
-			};
-		let hash_size = settins
-			.similar_images_sub_hash_size
-			.parse::<u8>()
-			.unwrap_or(16);
 		let mut scaner = SimilarImages::new(SimilarImagesParameters::new(
-			settins.similar_images_sub_similarity as u32,
+			similarity,
 			hash_size,
 			hash_alg,
 			resize_algorithm,
@@ -73,8 +112,8 @@ pub fn scan_similar_images(app: AppHandle, settins: Settings) {
 
 		scaner.search(&stop_flag, Some(&progress_tx));
 
-		let mut message = scaner.get_text_messages().create_messages_text();
-		let mut raw_list: Vec<_> = if scaner.get_use_reference() {
+		let message = scaner.get_text_messages().create_messages_text();
+		let raw_list: Vec<_> = if scaner.get_use_reference() {
 			scaner
 				.get_similar_images_referenced()
 				.iter()
@@ -90,53 +129,752 @@ pub fn scan_similar_images(app: AppHandle, settins: Settings) {
 				.collect()
 		};
 
-		for (_, vec_fe) in &mut raw_list {
-			vec_fe.par_sort_unstable_by_key(|e| e.similarity);
-		}
+		let progress = ProgressReporter::new(&progress_tx, 1);
+		let mut images_hashes = ImagesHashes {
+			ignore_same_size,
+			hide_hard_links,
+			..collect_images_hashes(
+				&walker,
+				scan_scope,
+				hash_size,
+				hash_alg,
+				resize_algorithm,
+				&stop_flag,
+				Some(&progress),
+			)
+		};
+		drop(progress);
+
+		let (raw_list, transforms) = if rotation_invariant {
+			images_hashes.hash_transforms(
+				TransformHasher::new(hash_size, hash_alg, resize_algorithm),
+				&stop_flag,
+			);
+			group_hashes(&images_hashes, similarity)
+		} else {
+			(raw_list, HashMap::new())
+		};
+
+		// Stored before the result is shown, so a regroup started from it
+		// uses the hashes of this scan
+		set_images_hashes(&app, images_hashes);
+		emit_result(&app, raw_list, message, hash_size, &transforms);
+
+		set_scaner_state(app, scaner);
+	});
+}
 
-		message = format!(
-			"Found {} similar image files\n{}",
-			raw_list.len(),
-			message
+// Compares the sample against every image in the scanned directories
+// instead of grouping the whole library
+pub fn find_similar_images_to_sample(
+	app: &AppHandle,
+	sample_path: &Path,
+	settins: Settings,
+	stop_flag: &Arc<AtomicBool>,
+	progress: &ProgressReporter,
+) {
+	let (hash_size, hash_alg, resize_algorithm) = get_hash_params(&settins);
+	let similarity = settins.similar_images_sub_similarity as u32;
+
+	if let Err(err) = validate_similarity(similarity, hash_size) {
+		emit_result(app, vec![], err, hash_size, &HashMap::new());
+		return;
+	}
+
+	let images_hashes = collect_images_hashes(
+		&FileWalker::from_settings(&settins),
+		ScanScope::from_settings(&settins),
+		hash_size,
+		hash_alg,
+		resize_algorithm,
+		stop_flag,
+		Some(progress),
+	);
+
+	let sample = match images_hashes
+		.hashes
+		.iter()
+		.flat_map(|(_, entries)| entries)
+		.find(|entry| entry.path == sample_path)
+	{
+		Some(entry) => entry.clone(),
+		None => match get_sample_entry(sample_path) {
+			Ok(entry) => entry,
+			Err(err) => {
+				emit_result(
+					app,
+					vec![],
+					format!(
+						"Failed to read sample `{}`: {}",
+						sample_path.display(),
+						err
+					),
+					hash_size,
+					&HashMap::new(),
+				);
+				return;
+			}
+		},
+	};
+
+	let Some(Some(sample_hashes)) =
+		TransformHasher::new(hash_size, hash_alg, resize_algorithm)
+			.hash_entries(&[&sample], stop_flag)
+			.pop()
+	else {
+		emit_result(
+			app,
+			vec![],
+			format!("Failed to hash sample `{}`", sample_path.display()),
+			hash_size,
+			&HashMap::new(),
 		);
+		return;
+	};
+	let sample_hashes = if settins.similar_images_sub_rotation_invariant {
+		&sample_hashes[..]
+	} else {
+		&sample_hashes[..1]
+	};
 
-		let list = raw_list
-			.into_iter()
-			.map(|(ref_item, item)| {
-				(
-					ref_item.map(|v| images_entry_to_custom(v, hash_size)),
-					item.into_iter()
-						.map(|v| images_entry_to_custom(v, hash_size))
-						.collect(),
-				)
+	let mut similar = vec![];
+	let mut transforms = HashMap::new();
+
+	for (hash, entries) in &images_hashes.hashes {
+		let Some((distance, transform)) = sample_hashes
+			.iter()
+			.enumerate()
+			.map(|(transform, sample_hash)| {
+				(hamming_distance(sample_hash, hash), transform)
 			})
-			.collect::<Vec<_>>();
-
-		app.emit(
-			"scan-result",
-			ScanResult {
-				cmd: "scan_similar_images",
-				list,
-				message,
-			},
-		)
-		.unwrap();
+			.min()
+		else {
+			continue;
+		};
+		if distance > similarity {
+			continue;
+		}
 
-		set_scaner_state(app, scaner);
+		for entry in entries {
+			if entry.path == sample.path {
+				continue;
+			}
+			let mut entry = entry.clone();
+			entry.similarity = distance;
+			// The transform was applied to the sample, the entry needs the
+			// inverse one to match it
+			if transform != 0 {
+				transforms.insert(
+					entry.path.clone(),
+					TRANSFORMS[inverse_transform(transform)],
+				);
+			}
+			similar.push(entry);
+		}
+	}
+
+	let message = format!(
+		"Found {} images similar to `{}`",
+		similar.len(),
+		sample.path.display()
+	);
+	let raw_list = if similar.is_empty() {
+		vec![]
+	} else {
+		vec![(Some(sample), similar)]
+	};
+
+	emit_result(app, raw_list, message, hash_size, &transforms);
+}
+
+pub fn regroup_similar_images(app: AppHandle, similarity: u32) {
+	spawn_scaner_thread(move || {
+		let images_hashes = {
+			let state_mutex = app.state::<Mutex<AppState>>();
+			let state = state_mutex.lock().unwrap();
+			state.similar_images_hashes.clone()
+		};
+
+		let Some(images_hashes) = images_hashes else {
+			emit_result(
+				&app,
+				vec![],
+				"No image hashes in memory, scan first".to_string(),
+				0,
+				&HashMap::new(),
+			);
+			return;
+		};
+
+		if let Err(err) =
+			validate_similarity(similarity, images_hashes.hash_size)
+		{
+			emit_result(
+				&app,
+				vec![],
+				err,
+				images_hashes.hash_size,
+				&HashMap::new(),
+			);
+			return;
+		}
+
+		let (raw_list, transforms) = group_hashes(&images_hashes, similarity);
+		let message = format!(
+			"Regrouped {} hashed images with max difference {}",
+			images_hashes
+				.hashes
+				.iter()
+				.map(|(_, v)| v.len())
+				.sum::<usize>(),
+			similarity
+		);
+
+		emit_result(
+			&app,
+			raw_list,
+			message,
+			images_hashes.hash_size,
+			&transforms,
+		);
+	});
+}
+
+fn emit_result(
+	app: &AppHandle,
+	mut raw_list: Vec<(Option<ImagesEntry>, Vec<ImagesEntry>)>,
+	message: String,
+	hash_size: u8,
+	transforms: &HashMap<PathBuf, &'static str>,
+) {
+	for (_, vec_fe) in &mut raw_list {
+		vec_fe.par_sort_unstable_by_key(|e| e.similarity);
+	}
+
+	let message =
+		format!("Found {} similar image files\n{}", raw_list.len(), message);
+
+	let list = raw_list
+		.into_iter()
+		.map(|(ref_item, item)| {
+			(
+				ref_item
+					.map(|v| images_entry_to_custom(v, hash_size, transforms)),
+				item.into_iter()
+					.map(|v| images_entry_to_custom(v, hash_size, transforms))
+					.collect(),
+			)
+		})
+		.collect::<Vec<_>>();
+
+	app.emit(
+		"scan-result",
+		ScanResult {
+			cmd: "scan_similar_images",
+			list,
+			message,
+		},
+	)
+	.unwrap();
+}
+
+fn get_hash_params(settins: &Settings) -> (u8, HashAlg, FilterType) {
+	let hash_alg = match settins.similar_images_sub_hash_alg.as_ref() {
+		"Gradient" => HashAlg::Gradient,
+		"BlockHash" => HashAlg::Blockhash,
+		"VertGradient" => HashAlg::VertGradient,
+		"DoubleGradient" => HashAlg::DoubleGradient,
+		"Median" => HashAlg::Median,
+		_ => HashAlg::Mean,
+	};
+	let resize_algorithm =
+		match settins.similar_images_sub_resize_algorithm.as_ref() {
+			"Gaussian" => FilterType::Gaussian,
+			"CatmullRom" => FilterType::CatmullRom,
+			"Triangle" => FilterType::Triangle,
+			"Nearest" => FilterType::Nearest,
+			_ => FilterType::Lanczos3,
+		};
+	let hash_size = settins
+		.similar_images_sub_hash_size
+		.parse::<u8>()
+		.unwrap_or(16);
+
+	(hash_size, hash_alg, resize_algorithm)
+}
+
+// The hash itself is left empty, it is computed by `TransformHasher` along
+// with the rotated and flipped variants
+fn get_sample_entry(path: &Path) -> Result<ImagesEntry, String> {
+	let metadata = fs::metadata(path).map_err(|err| err.to_string())?;
+	let image = get_dynamic_image_from_path(&path.to_string_lossy())?;
+	let modified_date = get_modified_date(&metadata);
+
+	Ok(ImagesEntry {
+		path: path.to_path_buf(),
+		size: metadata.len(),
+		width: image.width(),
+		height: image.height(),
+		modified_date,
+		..Default::default()
+	})
+}
+
+// The scaner keeps its hashes private, so the images it compared are found
+// again with the same settings. Hashes in czkawka's cache are reused for
+// unchanged files and the others are hashed here, so the cache doesn't need
+// to be enabled
+fn collect_images_hashes(
+	walker: &FileWalker,
+	scope: ScanScope,
+	hash_size: u8,
+	hash_alg: HashAlg,
+	resize_algorithm: FilterType,
+	stop_flag: &Arc<AtomicBool>,
+	progress: Option<&ProgressReporter>,
+) -> ImagesHashes {
+	let cache_file_name =
+		get_similar_images_cache_file(&hash_size, &hash_alg, &resize_algorithm);
+	let (_, loaded_entries) = load_cache_from_file_generalized_by_path::<
+		ImagesEntry,
+	>(&cache_file_name, false, &BTreeMap::new());
+	let loaded_entries = loaded_entries.unwrap_or_default();
+
+	let files: Vec<_> = walker
+		.walk(stop_flag, progress)
+		.into_iter()
+		.filter(|file| is_image(&file.path))
+		.collect();
+	if let Some(progress) = progress {
+		progress.next_stage(
+			CurrentStage::SimilarImagesCalculatingHashes,
+			files.len(),
+			files.iter().map(|file| file.size).sum(),
+		);
+	}
+
+	let entries: Vec<_> = files
+		.into_par_iter()
+		.filter_map(|file| {
+			if let Some(progress) = progress {
+				progress.add_entries(1);
+				progress.add_bytes(file.size);
+			}
+			if let Some(entry) =
+				loaded_entries.get(&*file.path.to_string_lossy())
+				&& entry.size == file.size
+				&& entry.modified_date == file.modified_date
+				&& !entry.hash.is_empty()
+			{
+				return Some(entry.clone());
+			}
+			if stop_flag.load(Ordering::Relaxed) {
+				return None;
+			}
+			match hash_image(&file, hash_size, hash_alg, resize_algorithm) {
+				Ok(entry) => Some(entry),
+				Err(err) => {
+					log::info!(
+						"Failed to hash `{}`: {}",
+						file.path.display(),
+						err
+					);
+					None
+				}
+			}
+		})
+		.collect();
+
+	let mut hashes: BTreeMap<ImHash, Vec<ImagesEntry>> = BTreeMap::new();
+	for entry in entries {
+		hashes.entry(entry.hash.clone()).or_default().push(entry);
+	}
+
+	ImagesHashes {
+		hash_size,
+		ignore_same_size: false,
+		hide_hard_links: false,
+		scope,
+		hashes: hashes.into_iter().collect(),
+		transform_hashes: None,
+	}
+}
+
+fn is_image(path: &Path) -> bool {
+	path.extension().is_some_and(|ext| {
+		IMAGE_EXTENSIONS
+			.contains(&ext.to_string_lossy().to_lowercase().as_ref())
+	})
+}
+
+// Hashed the same way as czkawka does
+fn hash_image(
+	file: &WalkedFile,
+	hash_size: u8,
+	hash_alg: HashAlg,
+	resize_algorithm: FilterType,
+) -> Result<ImagesEntry, String> {
+	let image = get_dynamic_image_from_path(&file.path.to_string_lossy())?;
+	let hasher = HasherConfig::new()
+		.hash_size(hash_size as u32, hash_size as u32)
+		.hash_alg(hash_alg)
+		.resize_filter(resize_algorithm)
+		.to_hasher();
+
+	Ok(ImagesEntry {
+		path: file.path.clone(),
+		size: file.size,
+		width: image.width(),
+		height: image.height(),
+		modified_date: file.modified_date,
+		hash: hasher.hash_image(&image).as_bytes().to_vec(),
+		..Default::default()
+	})
+}
+
+fn validate_similarity(similarity: u32, hash_size: u8) -> Result<(), String> {
+	if ![8, 16, 32, 64].contains(&hash_size) {
+		return Err(format!(
+			"Invalid hash size `{}`, expected 8, 16, 32 or 64",
+			hash_size
+		));
+	}
+	let max = MAX_SIMILARITY.min(hash_size as u32 * hash_size as u32);
+	if similarity > max {
+		return Err(format!(
+			"Invalid max difference `{}`, expected 0..={} for hash size {}",
+			similarity, max, hash_size
+		));
+	}
+	Ok(())
+}
+
+impl ImagesHashes {
+	fn hash_transforms(
+		&mut self,
+		hasher: TransformHasher,
+		stop_flag: &Arc<AtomicBool>,
+	) {
+		let entries: Vec<_> =
+			self.hashes.iter().map(|(_, entries)| &entries[0]).collect();
+		self.transform_hashes = Some(hasher.hash_entries(&entries, stop_flag));
+	}
+
+	// The hashes of `i` to look up, every rotated and flipped variant when
+	// they were computed, in `TRANSFORMS` order
+	fn query_hashes(&self, i: usize) -> Vec<&[u8]> {
+		if let Some(transform_hashes) = &self.transform_hashes
+			&& let Some(hashes) = &transform_hashes[i]
+		{
+			return hashes.iter().map(Vec::as_slice).collect();
+		}
+
+		vec![self.hashes[i].0.as_slice()]
+	}
+}
+
+fn set_images_hashes(app: &AppHandle, images_hashes: ImagesHashes) {
+	let state_mutex = app.state::<Mutex<AppState>>();
+	let mut state = state_mutex.lock().unwrap();
+	state.similar_images_hashes = Some(Arc::new(images_hashes));
+}
+
+fn clear_images_hashes(app: &AppHandle) {
+	let state_mutex = app.state::<Mutex<AppState>>();
+	let mut state = state_mutex.lock().unwrap();
+	state.similar_images_hashes = None;
+}
+
+// A BK-tree over the distinct hashes, so finding the neighbours of a hash
+// doesn't compare it with every other one
+struct BkTree<'a> {
+	hashes: &'a [(ImHash, Vec<ImagesEntry>)],
+	nodes: Vec<BkNode>,
+}
+
+struct BkNode {
+	index: usize,
+	// Child nodes by their distance to this one
+	children: Vec<(u32, usize)>,
+}
+
+impl<'a> BkTree<'a> {
+	fn new(hashes: &'a [(ImHash, Vec<ImagesEntry>)]) -> Self {
+		let mut tree = BkTree {
+			hashes,
+			nodes: Vec::with_capacity(hashes.len()),
+		};
+		for index in 0..hashes.len() {
+			tree.insert(index);
+		}
+		tree
+	}
+
+	fn insert(&mut self, index: usize) {
+		let new_node = BkNode {
+			index,
+			children: vec![],
+		};
+		if self.nodes.is_empty() {
+			self.nodes.push(new_node);
+			return;
+		}
+
+		let hash = &self.hashes[index].0;
+		let mut node = 0;
+
+		loop {
+			let distance =
+				hamming_distance(&self.hashes[self.nodes[node].index].0, hash);
+			let child = self.nodes[node]
+				.children
+				.iter()
+				.find(|(child_distance, _)| *child_distance == distance)
+				.map(|(_, child)| *child);
+
+			match child {
+				Some(child) => node = child,
+				None => {
+					let child = self.nodes.len();
+					self.nodes.push(new_node);
+					self.nodes[node].children.push((distance, child));
+					return;
+				}
+			}
+		}
+	}
+
+	// Returns the index of every hash within `max_distance` of `hash`,
+	// together with its distance
+	fn find(&self, hash: &[u8], max_distance: u32) -> Vec<(usize, u32)> {
+		let mut found = vec![];
+		let mut to_visit = if self.nodes.is_empty() {
+			vec![]
+		} else {
+			vec![0]
+		};
+
+		while let Some(node) = to_visit.pop() {
+			let node = &self.nodes[node];
+			let distance = hamming_distance(&self.hashes[node.index].0, hash);
+			if distance <= max_distance {
+				found.push((node.index, distance));
+			}
+			to_visit.extend(
+				node.children
+					.iter()
+					.filter(|(child_distance, _)| {
+						child_distance.abs_diff(distance) <= max_distance
+					})
+					.map(|(_, child)| *child),
+			);
+		}
+
+		found
+	}
+}
+
+fn hamming_distance(a: &[u8], b: &[u8]) -> u32 {
+	a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum()
+}
+
+// Hashes with the most neighbours become group parents first, the same
+// greedy strategy czkawka uses, and `similarity` of every entry is its
+// distance to the parent. With reference folders only referenced images are
+// parents and every one of them gets its own group. The returned map holds
+// the transform every rotated or mirrored entry matched its parent with
+fn group_hashes(
+	images_hashes: &ImagesHashes,
+	similarity: u32,
+) -> (
+	Vec<(Option<ImagesEntry>, Vec<ImagesEntry>)>,
+	HashMap<PathBuf, &'static str>,
+) {
+	let hashes = &images_hashes.hashes;
+	let scope = &images_hashes.scope;
+	let tree = BkTree::new(hashes);
+
+	// Every pair within `similarity`, as (parent, child, distance,
+	// transform of the child)
+	let mut pairs: Vec<(usize, usize, u32, usize)> = (0..hashes.len())
+		.into_par_iter()
+		.flat_map_iter(|child| {
+			images_hashes
+				.query_hashes(child)
+				.into_iter()
+				.enumerate()
+				.flat_map(|(transform, hash)| {
+					tree.find(hash, similarity).into_iter().map(
+						move |(parent, distance)| {
+							(parent, child, distance, transform)
+						},
+					)
+				})
+				.filter(|(parent, child, ..)| parent != child)
+				.collect::<Vec<_>>()
+		})
+		.collect();
+	pairs.par_sort_unstable();
+	pairs.dedup_by_key(|(parent, child, ..)| (*parent, *child));
+
+	let mut neighbours = vec![vec![]; hashes.len()];
+	for (parent, child, distance, transform) in pairs {
+		neighbours[parent].push((child, distance, transform));
+	}
+
+	let mut order: Vec<usize> = (0..hashes.len()).collect();
+	order.sort_by_key(|i| std::cmp::Reverse(neighbours[*i].len()));
+
+	let mut assigned = vec![false; hashes.len()];
+	let mut groups = vec![];
+	let mut transforms = HashMap::new();
+
+	let mut take_entries =
+		|group: &mut Vec<ImagesEntry>,
+		 (index, distance, transform): (usize, u32, usize),
+		 skip_referenced: bool| {
+			for entry in &hashes[index].1 {
+				if skip_referenced && scope.is_referenced(&entry.path) {
+					continue;
+				}
+				let mut entry = entry.clone();
+				entry.similarity = distance;
+				if transform != 0 {
+					transforms
+						.insert(entry.path.clone(), TRANSFORMS[transform]);
+				}
+				group.push(entry);
+			}
+		};
+
+	if !scope.use_reference() {
+		for parent in order {
+			if assigned[parent] {
+				continue;
+			}
+			assigned[parent] = true;
+
+			let mut group = vec![];
+			take_entries(&mut group, (parent, 0, 0), false);
+
+			for (child, distance, transform) in &neighbours[parent] {
+				if assigned[*child] {
+					continue;
+				}
+				assigned[*child] = true;
+				take_entries(
+					&mut group,
+					(*child, *distance, *transform),
+					false,
+				);
+			}
+
+			if images_hashes.ignore_same_size {
+				let mut sizes = HashSet::new();
+				group.retain(|entry| sizes.insert(entry.size));
+			}
+			if images_hashes.hide_hard_links {
+				remove_hard_links(&mut group, HashSet::new());
+			}
+
+			if group.len() > 1 {
+				groups.push((None, group));
+			}
+		}
+
+		return (groups, transforms);
+	}
+
+	// Referenced images are never put in another group, `assigned` only
+	// tracks the images outside the reference folders
+	for parent in order {
+		let refs: Vec<_> = hashes[parent]
+			.1
+			.iter()
+			.filter(|entry| scope.is_referenced(&entry.path))
+			.cloned()
+			.collect();
+
+		for mut ref_entry in refs {
+			ref_entry.similarity = 0;
+			let mut others = vec![];
+
+			for (child, distance, transform) in
+				std::iter::once(&(parent, 0, 0)).chain(&neighbours[parent])
+			{
+				if assigned[*child] {
+					continue;
+				}
+				assigned[*child] = true;
+				take_entries(
+					&mut others,
+					(*child, *distance, *transform),
+					true,
+				);
+			}
+
+			if images_hashes.ignore_same_size {
+				let mut sizes = HashSet::from([ref_entry.size]);
+				others.retain(|entry| sizes.insert(entry.size));
+			}
+			if images_hashes.hide_hard_links {
+				let ids = get_file_id(&ref_entry.path).into_iter().collect();
+				remove_hard_links(&mut others, ids);
+			}
+
+			if !others.is_empty() {
+				groups.push((Some(ref_entry), others));
+			}
+		}
+	}
+
+	(groups, transforms)
+}
+
+// Keeps only the first path of every file, the others are hard links to it
+fn remove_hard_links(
+	entries: &mut Vec<ImagesEntry>,
+	mut ids: HashSet<(u64, u64)>,
+) {
+	entries.retain(|entry| match get_file_id(&entry.path) {
+		Some(id) => ids.insert(id),
+		None => true,
 	});
 }
 
+#[cfg(unix)]
+fn get_file_id(path: &Path) -> Option<(u64, u64)> {
+	use std::os::unix::fs::MetadataExt;
+
+	fs::metadata(path)
+		.ok()
+		.map(|metadata| (metadata.dev(), metadata.ino()))
+}
+
+// Like czkawka, hard links are only told apart on unix
+#[cfg(not(unix))]
+fn get_file_id(_path: &Path) -> Option<(u64, u64)> {
+	None
+}
+
 fn images_entry_to_custom(
 	value: ImagesEntry,
 	hash_size: u8,
+	transforms: &HashMap<PathBuf, &'static str>,
 ) -> CustomImagesEntry {
 	CustomImagesEntry {
+		transform: transforms
+			.get(&value.path)
+			.copied()
+			.unwrap_or(TRANSFORMS[0]),
 		path: value.path.to_string_lossy().to_string(),
 		size: value.size,
 		width: value.width,
 		height: value.height,
 		modified_date: value.modified_date,
 		similarity: get_string_from_similarity(&value.similarity, hash_size),
+		distance: value.similarity,
+		hash_size,
 	}
 }
 
@@ -144,3 +882,67 @@ crate::gen_set_scaner_state_fn!(
 	similar_images_state,
 	czkawka_core::tools::similar_images::SimilarImages
 );
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn entry_of(path: &str, hash: &[u8]) -> (ImHash, Vec<ImagesEntry>) {
+		let entry = ImagesEntry {
+			path: PathBuf::from(path),
+			hash: hash.to_vec(),
+			..Default::default()
+		};
+		(hash.to_vec(), vec![entry])
+	}
+
+	#[test]
+	fn groups_keep_the_matching_transform() {
+		let plain = [0x00; 8];
+		let other = [0xff; 8];
+		// `/b.png` rotated by 270 degrees is `/a.png`
+		let mut b_variants = vec![other.to_vec(); TRANSFORMS.len()];
+		b_variants[3] = plain.to_vec();
+
+		let images_hashes = ImagesHashes {
+			hash_size: 8,
+			ignore_same_size: false,
+			hide_hard_links: false,
+			scope: ScanScope::default(),
+			hashes: vec![
+				entry_of("/a.png", &plain),
+				entry_of("/b.png", &other),
+			],
+			transform_hashes: Some(vec![
+				Some(vec![plain.to_vec(); TRANSFORMS.len()]),
+				Some(b_variants),
+			]),
+		};
+
+		let (groups, transforms) = group_hashes(&images_hashes, 0);
+		assert_eq!(groups.len(), 1);
+		let paths: Vec<_> = groups[0].1.iter().map(|e| &e.path).collect();
+		assert_eq!(paths, [Path::new("/a.png"), Path::new("/b.png")]);
+		assert_eq!(transforms.get(Path::new("/b.png")), Some(&"rotate270"));
+		assert_eq!(transforms.get(Path::new("/a.png")), None);
+	}
+
+	#[test]
+	fn unrotated_hashes_are_not_grouped() {
+		let images_hashes = ImagesHashes {
+			hash_size: 8,
+			ignore_same_size: false,
+			hide_hard_links: false,
+			scope: ScanScope::default(),
+			hashes: vec![
+				entry_of("/a.png", &[0x00; 8]),
+				entry_of("/b.png", &[0xff; 8]),
+			],
+			transform_hashes: None,
+		};
+
+		let (groups, transforms) = group_hashes(&images_hashes, 0);
+		assert!(groups.is_empty());
+		assert!(transforms.is_empty());
+	}
+}
@@ -0,0 +1,311 @@
+use std::{
+	collections::{HashMap, HashSet},
+	ops::RangeInclusive,
+	path::Path,
+	sync::LazyLock,
+};
+
+use rayon::prelude::*;
+use regex::Regex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+use crate::{
+	file_walker::{FileWalker, WalkedFile},
+	progress::ProgressReporter,
+	scaner::{ScanScope, spawn_scaner_thread},
+	settings::Settings,
+	state::get_stop_flag_and_progress_tx,
+};
+
+const ALLOWED_THRESHOLD: RangeInclusive<i32> = 50..=100;
+const DEFAULT_THRESHOLD: i32 = 80;
+
+// Words that appear in very many names are not used to find candidates,
+// names only sharing them are rarely related
+const MAX_POSTINGS: usize = 2000;
+
+static BRACKETED_NUMBER: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r"[(\[]\s*\d{1,3}\s*[)\]]").unwrap());
+
+static NOISE_TOKEN: LazyLock<Regex> = LazyLock::new(|| {
+	Regex::new(
+		r"^(copy|final|new|old|backup|edited|edit|duplicate|副本|v\d+|ver\d+|rev\d+|\d{3,4}[pi]|[248]k|sd|hd|fhd|uhd|\d{3,5}x\d{3,5})$",
+	)
+	.unwrap()
+});
+
+#[derive(Serialize, Clone)]
+struct CustomNameEntry {
+	path: String,
+	size: u64,
+	modified_date: u64,
+	normalized_name: String,
+	score: u32,
+}
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<(Option<CustomNameEntry>, Vec<CustomNameEntry>)>,
+	message: String,
+}
+
+// Files whose names normalize to the same words share one key
+struct NameKey {
+	normalized: String,
+	words: Vec<String>,
+	numbers: Vec<String>,
+	files: Vec<usize>,
+}
+
+pub fn scan_similar_names(app: AppHandle, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let scope = ScanScope::from_settings(&settings);
+
+		let mut message = String::new();
+		let threshold = settings.similar_names_sub_threshold;
+		let threshold = if ALLOWED_THRESHOLD.contains(&threshold) {
+			threshold
+		} else {
+			message.push_str(&format!(
+				"Invalid threshold `{}`, expected 50..=100, fell back to {}\n",
+				threshold, DEFAULT_THRESHOLD
+			));
+			DEFAULT_THRESHOLD
+		};
+		let threshold = threshold as f64 / 100.0;
+
+		let progress = ProgressReporter::new(&progress_tx, 0);
+		let files = FileWalker::from_settings(&settings)
+			.walk(&stop_flag, Some(&progress));
+		drop(progress);
+		let keys = get_name_keys(&files);
+		let neighbours = get_neighbours(&keys, threshold);
+
+		let mut groups = group_keys(&keys, &neighbours, &files);
+
+		if scope.use_reference() {
+			groups.retain_mut(|(ref_item, items)| {
+				let Some(index) = items.iter().position(|entry| {
+					scope.is_referenced(Path::new(&entry.path))
+				}) else {
+					return false;
+				};
+				*ref_item = Some(items.remove(index));
+				items.retain(|entry| {
+					!scope.is_referenced(Path::new(&entry.path))
+				});
+				!items.is_empty()
+			});
+		}
+
+		message = format!(
+			"Found {} groups of files with similar names\n{}",
+			groups.len(),
+			message
+		);
+
+		app.emit(
+			"scan-result",
+			ScanResult {
+				cmd: "scan_similar_names",
+				list: groups,
+				message,
+			},
+		)
+		.unwrap();
+	});
+}
+
+fn get_name_keys(files: &[WalkedFile]) -> Vec<NameKey> {
+	let mut keys: HashMap<String, NameKey> = HashMap::new();
+
+	for (i, file) in files.iter().enumerate() {
+		let Some(stem) = file.path.file_stem() else {
+			continue;
+		};
+		let (words, numbers) = normalize_name(&stem.to_string_lossy());
+		if words.is_empty() && numbers.is_empty() {
+			continue;
+		}
+		let normalized = words
+			.iter()
+			.chain(&numbers)
+			.cloned()
+			.collect::<Vec<_>>()
+			.join(" ");
+
+		keys.entry(normalized.clone())
+			.or_insert_with(|| NameKey {
+				normalized,
+				words,
+				numbers,
+				files: vec![],
+			})
+			.files
+			.push(i);
+	}
+
+	keys.into_values().collect()
+}
+
+// Splits a name into sorted words and numbers after folding case, accents
+// and separators, and dropping copy markers, version and resolution tags
+fn normalize_name(name: &str) -> (Vec<String>, Vec<String>) {
+	let name = BRACKETED_NUMBER.replace_all(name, " ");
+	let folded: String = name
+		.nfkd()
+		.filter(|c| !is_combining_mark(*c))
+		.flat_map(char::to_lowercase)
+		.map(|c| {
+			if c.is_alphanumeric() {
+				c
+			} else {
+				' '
+			}
+		})
+		.collect();
+
+	let mut words = vec![];
+	let mut numbers = vec![];
+
+	for token in folded.split_whitespace() {
+		if NOISE_TOKEN.is_match(token) {
+			continue;
+		}
+		if token.chars().all(|c| c.is_ascii_digit()) {
+			numbers.push(token.trim_start_matches('0').to_string());
+		} else {
+			words.push(token.to_string());
+		}
+	}
+
+	words.sort_unstable();
+	numbers.sort_unstable();
+
+	(words, numbers)
+}
+
+// Keys are only compared when they share a word and have the same numbers,
+// names like `IMG_1234` and `IMG_1235` are different files, not copies
+fn get_neighbours(keys: &[NameKey], threshold: f64) -> Vec<Vec<(usize, f64)>> {
+	let mut postings: HashMap<&str, Vec<usize>> = HashMap::new();
+	for (i, key) in keys.iter().enumerate() {
+		for word in key.words.iter().collect::<HashSet<_>>() {
+			postings.entry(word).or_default().push(i);
+		}
+	}
+
+	(0..keys.len())
+		.into_par_iter()
+		.map(|i| {
+			let key = &keys[i];
+			let candidates: HashSet<usize> = key
+				.words
+				.iter()
+				.filter_map(|word| postings.get(word.as_str()))
+				.filter(|posting| posting.len() <= MAX_POSTINGS)
+				.flatten()
+				.copied()
+				.filter(|j| *j != i && keys[*j].numbers == key.numbers)
+				.collect();
+
+			candidates
+				.into_iter()
+				.filter_map(|j| {
+					let score = strsim::normalized_levenshtein(
+						&key.normalized,
+						&keys[j].normalized,
+					);
+					(score >= threshold).then_some((j, score))
+				})
+				.collect()
+		})
+		.collect()
+}
+
+// Keys with the most neighbours become group parents first, the score of
+// every entry is its similarity to the parent
+fn group_keys(
+	keys: &[NameKey],
+	neighbours: &[Vec<(usize, f64)>],
+	files: &[WalkedFile],
+) -> Vec<(Option<CustomNameEntry>, Vec<CustomNameEntry>)> {
+	let mut order: Vec<usize> = (0..keys.len()).collect();
+	order.sort_by_key(|i| std::cmp::Reverse(neighbours[*i].len()));
+
+	let mut assigned = vec![false; keys.len()];
+	let mut groups = vec![];
+
+	let to_entries = |key: &NameKey, score: f64| {
+		key.files
+			.iter()
+			.map(|i| CustomNameEntry {
+				path: files[*i].path.to_string_lossy().to_string(),
+				size: files[*i].size,
+				modified_date: files[*i].modified_date,
+				normalized_name: key.normalized.clone(),
+				score: (score * 100.0).round() as u32,
+			})
+			.collect::<Vec<_>>()
+	};
+
+	for parent in order {
+		if assigned[parent] {
+			continue;
+		}
+		assigned[parent] = true;
+
+		let mut group = to_entries(&keys[parent], 1.0);
+		for (child, score) in &neighbours[parent] {
+			if assigned[*child] {
+				continue;
+			}
+			assigned[*child] = true;
+			group.extend(to_entries(&keys[*child], *score));
+		}
+
+		if group.len() > 1 {
+			group.sort_by(|a, b| {
+				b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path))
+			});
+			groups.push(group);
+		}
+	}
+
+	// Groups with the closest names come first
+	groups.sort_by_key(|group| {
+		let total: u32 = group.iter().map(|entry| entry.score).sum();
+		std::cmp::Reverse(total * 100 / group.len() as u32)
+	});
+
+	groups.into_iter().map(|group| (None, group)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn names_are_normalized() {
+		assert_eq!(
+			normalize_name("Café_Photo (1) - Copy 1080p 007"),
+			(
+				vec!["cafe".to_string(), "photo".to_string()],
+				vec!["7".to_string()]
+			)
+		);
+		assert_eq!(normalize_name("photo CAFÉ"), normalize_name("Café Photo"));
+	}
+
+	#[test]
+	fn numbers_are_kept_apart_from_words() {
+		let (words, numbers) = normalize_name("IMG_1234");
+		assert_eq!(words, ["img"]);
+		assert_eq!(numbers, ["1234"]);
+		assert_ne!(normalize_name("IMG_1234"), normalize_name("IMG_1235"));
+	}
+}
@@ -1,18 +1,44 @@
+use std::{
+	collections::{BTreeMap, HashMap},
+	fs,
+	ops::RangeInclusive,
+	path::{Path, PathBuf},
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
 use czkawka_core::{
-	common::{split_path_compare, tool_data::CommonData, traits::Search},
+	common::{
+		cache::{
+			get_similar_videos_cache_file,
+			load_cache_from_file_generalized_by_path,
+		},
+		progress_data::CurrentStage,
+		split_path_compare,
+		tool_data::CommonData,
+		traits::Search,
+	},
 	tools::similar_videos::{
-		SimilarVideos, SimilarVideosParameters, VideosEntry,
+		ALLOWED_SKIP_FORWARD_AMOUNT, ALLOWED_VID_HASH_DURATION,
+		DEFAULT_SKIP_FORWARD_AMOUNT, DEFAULT_VID_HASH_DURATION, SimilarVideos,
+		SimilarVideosParameters, VideosEntry,
 	},
 };
 use rayon::prelude::*;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
-use vid_dup_finder_lib::Cropdetect;
+use vid_dup_finder_lib::{CreationOptions, Cropdetect, VideoHashBuilder};
 
 use crate::{
+	ffmpeg::get_ffprobe_exe,
+	file_walker::{FileWalker, get_modified_date},
+	progress::ProgressReporter,
 	scaner::{set_scaner_common_settings, spawn_scaner_thread},
 	settings::Settings,
 	state::get_stop_flag_and_progress_tx,
+	video_info::{VIDEO_EXTENSIONS, VideoInfo, VideoToProbe, probe_videos},
 };
 
 #[derive(Serialize, Clone)]
@@ -20,6 +46,8 @@ struct CustomVideosEntry {
 	path: String,
 	size: u64,
 	modified_date: u64,
+	#[serde(flatten)]
+	info: VideoInfo,
 }
 
 #[derive(Serialize, Clone)]
@@ -32,14 +60,21 @@ struct ScanResult {
 pub fn scan_similar_videos(app: AppHandle, settins: Settings) {
 	spawn_scaner_thread(move || {
 		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let ffprobe = get_ffprobe_exe(&settins);
+
+		let mut invalid_params = vec![];
+		let (skip_forward_amount, vid_hash_duration, crop_detect) =
+			get_hash_params(&settins, &mut invalid_params);
 
+		// The hash cache file name is derived from these three parameters by
+		// czkawka_core, so changing any of them never reuses stale hashes
 		let mut scaner = SimilarVideos::new(SimilarVideosParameters::new(
 			settins.similar_videos_sub_similarity,
 			settins.similar_videos_sub_ignore_same_size,
 			settins.similar_videos_hide_hard_links,
-			15,                    // DEFAULT_SKIP_FORWARD_AMOUNT
-			10,                    // DEFAULT_VID_HASH_DURATION
-			Cropdetect::Letterbox, // DEFAULT_CROP_DETECT
+			skip_forward_amount,
+			vid_hash_duration,
+			crop_detect,
 		));
 
 		scaner.set_delete_outdated_cache(
@@ -77,13 +112,30 @@ pub fn scan_similar_videos(app: AppHandle, settins: Settings) {
 			raw_list.len(),
 			message
 		);
+		for invalid_param in invalid_params {
+			message.push_str(&invalid_param);
+			message.push('\n');
+		}
+
+		let videos_to_probe = raw_list
+			.iter()
+			.flat_map(|(ref_item, items)| ref_item.iter().chain(items))
+			.map(|item| VideoToProbe {
+				path: item.path.as_path(),
+				size: item.size,
+				modified_date: item.modified_date,
+			})
+			.collect();
+		let videos_info = probe_videos(videos_to_probe, &ffprobe, &stop_flag);
 
 		let list = raw_list
 			.into_iter()
 			.map(|(ref_item, item)| {
 				(
-					ref_item.map(videos_entry_to_custom),
-					item.into_iter().map(videos_entry_to_custom).collect(),
+					ref_item.map(|v| videos_entry_to_custom(v, &videos_info)),
+					item.into_iter()
+						.map(|v| videos_entry_to_custom(v, &videos_info))
+						.collect(),
 				)
 			})
 			.collect::<Vec<_>>();
@@ -102,14 +154,264 @@ pub fn scan_similar_videos(app: AppHandle, settins: Settings) {
 	});
 }
 
-fn videos_entry_to_custom(value: VideosEntry) -> CustomVideosEntry {
+// Compares the sample against every video in the scanned directories
+// instead of grouping the whole library. Hashes in the cache are reused for
+// unchanged files and the others are hashed here
+pub fn find_similar_videos_to_sample(
+	app: &AppHandle,
+	sample_path: &Path,
+	settins: Settings,
+	stop_flag: &Arc<AtomicBool>,
+	progress: &ProgressReporter,
+) {
+	let ffprobe = get_ffprobe_exe(&settins);
+
+	let mut invalid_params = vec![];
+	let (skip_forward_amount, vid_hash_duration, crop_detect) =
+		get_hash_params(&settins, &mut invalid_params);
+	let tolerance = settins.similar_videos_sub_similarity as f64 / 100.0;
+
+	let cache_file_name = get_similar_videos_cache_file(
+		skip_forward_amount,
+		vid_hash_duration,
+		crop_detect,
+	);
+	let (_, loaded_entries) = load_cache_from_file_generalized_by_path::<
+		VideosEntry,
+	>(&cache_file_name, false, &BTreeMap::new());
+	let loaded_entries = loaded_entries.unwrap_or_default();
+
+	let sample = match loaded_entries.get(&*sample_path.to_string_lossy()) {
+		Some(entry) if entry.error.is_empty() => entry.clone(),
+		_ => match hash_video(
+			sample_path,
+			skip_forward_amount,
+			vid_hash_duration,
+			crop_detect,
+		) {
+			Ok(entry) => entry,
+			Err(err) => {
+				emit_sample_result(
+					app,
+					vec![],
+					format!(
+						"Failed to hash sample `{}`: {}",
+						sample_path.display(),
+						err
+					),
+				);
+				return;
+			}
+		},
+	};
+
+	let files: Vec<_> = FileWalker::from_settings(&settins)
+		.walk(stop_flag, Some(progress))
+		.into_iter()
+		.filter(|file| file.path != sample.path && is_video(&file.path))
+		.collect();
+	progress.next_stage(
+		CurrentStage::SimilarVideosCalculatingHashes,
+		files.len(),
+		files.iter().map(|file| file.size).sum(),
+	);
+
+	let mut similar: Vec<_> = files
+		.into_par_iter()
+		.filter_map(|file| {
+			progress.add_entries(1);
+			progress.add_bytes(file.size);
+			if let Some(entry) =
+				loaded_entries.get(&*file.path.to_string_lossy())
+				&& entry.size == file.size
+				&& entry.modified_date == file.modified_date
+			{
+				return entry.error.is_empty().then(|| entry.clone());
+			}
+			if stop_flag.load(Ordering::Relaxed) {
+				return None;
+			}
+			match hash_video(
+				&file.path,
+				skip_forward_amount,
+				vid_hash_duration,
+				crop_detect,
+			) {
+				Ok(entry) => Some(entry),
+				Err(err) => {
+					log::info!(
+						"Failed to hash `{}`: {}",
+						file.path.display(),
+						err
+					);
+					None
+				}
+			}
+		})
+		.filter_map(|entry| {
+			let distance =
+				sample.vhash.normalized_hamming_distance(&entry.vhash);
+			(distance <= tolerance).then_some((distance, entry))
+		})
+		.collect();
+	similar.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+	let similar: Vec<_> = similar.into_iter().map(|(_, entry)| entry).collect();
+
+	let mut message = format!(
+		"Found {} videos similar to `{}`\n",
+		similar.len(),
+		sample.path.display()
+	);
+	for invalid_param in invalid_params {
+		message.push_str(&invalid_param);
+		message.push('\n');
+	}
+
+	let raw_list = if similar.is_empty() {
+		vec![]
+	} else {
+		vec![(Some(sample), similar)]
+	};
+	let videos_to_probe = raw_list
+		.iter()
+		.flat_map(|(ref_item, items)| ref_item.iter().chain(items))
+		.map(|item| VideoToProbe {
+			path: item.path.as_path(),
+			size: item.size,
+			modified_date: item.modified_date,
+		})
+		.collect();
+	let videos_info = probe_videos(videos_to_probe, &ffprobe, stop_flag);
+
+	let list = raw_list
+		.into_iter()
+		.map(|(ref_item, item)| {
+			(
+				ref_item.map(|v| videos_entry_to_custom(v, &videos_info)),
+				item.into_iter()
+					.map(|v| videos_entry_to_custom(v, &videos_info))
+					.collect(),
+			)
+		})
+		.collect();
+
+	emit_sample_result(app, list, message);
+}
+
+fn emit_sample_result(
+	app: &AppHandle,
+	list: Vec<(Option<CustomVideosEntry>, Vec<CustomVideosEntry>)>,
+	message: String,
+) {
+	app.emit(
+		"scan-result",
+		ScanResult {
+			cmd: "scan_similar_videos",
+			list,
+			message,
+		},
+	)
+	.unwrap();
+}
+
+fn is_video(path: &Path) -> bool {
+	path.extension().is_some_and(|ext| {
+		VIDEO_EXTENSIONS
+			.contains(&ext.to_string_lossy().to_lowercase().as_ref())
+	})
+}
+
+fn hash_video(
+	path: &Path,
+	skip_forward_amount: u32,
+	vid_hash_duration: u32,
+	crop_detect: Cropdetect,
+) -> Result<VideosEntry, String> {
+	let metadata = fs::metadata(path).map_err(|err| err.to_string())?;
+	let modified_date = get_modified_date(&metadata);
+	let vhash = VideoHashBuilder::from_options(CreationOptions {
+		skip_forward_amount: skip_forward_amount as f64,
+		duration: vid_hash_duration as f64,
+		cropdetect: crop_detect,
+	})
+	.hash(path.to_path_buf())
+	.map_err(|err| err.to_string())?;
+
+	Ok(VideosEntry {
+		path: path.to_path_buf(),
+		size: metadata.len(),
+		modified_date,
+		vhash,
+		..Default::default()
+	})
+}
+
+fn get_hash_params(
+	settins: &Settings,
+	invalid_params: &mut Vec<String>,
+) -> (u32, u32, Cropdetect) {
+	let skip_forward_amount = validate_param(
+		settins.similar_videos_sub_skip_forward_amount,
+		&ALLOWED_SKIP_FORWARD_AMOUNT,
+		DEFAULT_SKIP_FORWARD_AMOUNT,
+		"skip forward amount",
+		invalid_params,
+	);
+	let vid_hash_duration = validate_param(
+		settins.similar_videos_sub_vid_hash_duration,
+		&ALLOWED_VID_HASH_DURATION,
+		DEFAULT_VID_HASH_DURATION,
+		"hash duration",
+		invalid_params,
+	);
+	let crop_detect = match settins.similar_videos_sub_crop_detect.as_ref() {
+		"None" => Cropdetect::None,
+		"Motion" => Cropdetect::Motion,
+		_ => Cropdetect::Letterbox,
+	};
+
+	(skip_forward_amount, vid_hash_duration, crop_detect)
+}
+
+fn videos_entry_to_custom(
+	value: VideosEntry,
+	videos_info: &HashMap<PathBuf, VideoInfo>,
+) -> CustomVideosEntry {
 	CustomVideosEntry {
+		info: videos_info.get(&value.path).cloned().unwrap_or_default(),
 		path: value.path.to_string_lossy().to_string(),
 		size: value.size,
 		modified_date: value.modified_date,
 	}
 }
 
+// The ranges are the ones czkawka checks its own parameters against
+fn validate_param(
+	value: i32,
+	range: &RangeInclusive<u32>,
+	default: u32,
+	name: &str,
+	invalid_params: &mut Vec<String>,
+) -> u32 {
+	if let Ok(value) = u32::try_from(value)
+		&& range.contains(&value)
+	{
+		return value;
+	}
+
+	invalid_params.push(format!(
+		"Invalid {} `{}`, expected {}..={}, fell back to {}",
+		name,
+		value,
+		range.start(),
+		range.end(),
+		default
+	));
+
+	default
+}
+
 crate::gen_set_scaner_state_fn!(
 	similar_videos_state,
 	czkawka_core::tools::similar_videos::SimilarVideos
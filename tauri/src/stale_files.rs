@@ -0,0 +1,340 @@
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	path::{Path, PathBuf},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+	file_walker::{FileWalker, Tree, walk_tree},
+	progress::ProgressReporter,
+	scaner::spawn_scaner_thread,
+	settings::Settings,
+	state::get_stop_flag_and_progress_tx,
+};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, PartialEq)]
+enum TimeKind {
+	Modified,
+	Accessed,
+	Changed,
+}
+
+#[derive(Serialize, Clone)]
+struct CustomStaleEntry {
+	path: String,
+	is_dir: bool,
+	size: u64,
+	file_count: usize,
+	last_used_date: u64,
+	age_days: u64,
+}
+
+#[derive(Serialize, Clone)]
+struct ScanResult {
+	cmd: &'static str,
+	list: Vec<CustomStaleEntry>,
+	message: String,
+}
+
+struct StaleFile {
+	path: PathBuf,
+	size: u64,
+	time: u64,
+}
+
+#[derive(Default)]
+struct FolderAge {
+	size: u64,
+	file_count: usize,
+	// The most recent time of everything in the folder
+	newest: u64,
+	// Something in the folder could not be read, so it is never stale
+	incomplete: bool,
+}
+
+pub fn scan_stale_files(app: AppHandle, settings: Settings) {
+	spawn_scaner_thread(move || {
+		let (stop_flag, progress_tx) = get_stop_flag_and_progress_tx(&app);
+		let time_kind = match settings.stale_files_sub_time_kind.as_ref() {
+			"accessed" => TimeKind::Accessed,
+			"changed" => TimeKind::Changed,
+			_ => TimeKind::Modified,
+		};
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|duration| duration.as_secs())
+			.unwrap_or_default();
+		let threshold = now.saturating_sub(
+			settings.stale_files_sub_days.max(0) as u64 * SECONDS_PER_DAY,
+		);
+
+		// The settings decide which files and folders are reported, but a
+		// folder is only stale when everything in it is, so its age comes
+		// from a walk that filters nothing out
+		let walker = FileWalker::from_settings(&settings);
+		let roots = walker.included_directories();
+		let progress = ProgressReporter::new(&progress_tx, 1);
+		let (scanned_folders, scanned_files) =
+			walker.walk_with_folders(&stop_flag, Some(&progress));
+		let scanned_folders: HashSet<_> = scanned_folders.into_iter().collect();
+		let files: Vec<_> = scanned_files
+			.into_iter()
+			.filter_map(|file| {
+				let time = get_time(&file.path, time_kind)?;
+				Some(StaleFile {
+					path: file.path,
+					size: file.size,
+					time,
+				})
+			})
+			.collect();
+
+		let tree = walk_tree(roots, &stop_flag, Some(&progress));
+		drop(progress);
+		let folders = get_folder_ages(&tree, time_kind);
+		// Included directories themselves are never reported
+		let is_stale_folder = |path: &Path| {
+			scanned_folders.contains(path)
+				&& folders.get(path).is_some_and(|folder| {
+					!folder.incomplete
+						&& folder.file_count > 0
+						&& folder.newest < threshold
+				})
+		};
+
+		// Only the outermost stale folders are reported, files in them are
+		// covered by their folder
+		let stale_folders: Vec<_> = folders
+			.iter()
+			.filter(|(path, _)| {
+				is_stale_folder(path)
+					&& !path.parent().is_some_and(is_stale_folder)
+			})
+			.map(|(path, folder)| CustomStaleEntry {
+				path: path.to_string_lossy().to_string(),
+				is_dir: true,
+				size: folder.size,
+				file_count: folder.file_count,
+				last_used_date: folder.newest,
+				age_days: now.saturating_sub(folder.newest) / SECONDS_PER_DAY,
+			})
+			.collect();
+
+		let stale_files: Vec<_> = files
+			.iter()
+			.filter(|file| {
+				file.time < threshold
+					&& !file.path.parent().is_some_and(is_stale_folder)
+			})
+			.map(|file| CustomStaleEntry {
+				path: file.path.to_string_lossy().to_string(),
+				is_dir: false,
+				size: file.size,
+				file_count: 1,
+				last_used_date: file.time,
+				age_days: now.saturating_sub(file.time) / SECONDS_PER_DAY,
+			})
+			.collect();
+
+		let mut message = format!(
+			"Found {} stale folders and {} stale files",
+			stale_folders.len(),
+			stale_files.len()
+		);
+		let noatime_dirs: Vec<_> = roots
+			.iter()
+			.filter(|dir| is_noatime_mount(dir))
+			.map(|dir| dir.to_string_lossy().to_string())
+			.collect();
+		if !noatime_dirs.is_empty() {
+			message.push_str(&format!(
+				"\nThe filesystem of {} is mounted with `noatime`, access times are not updated there{}",
+				noatime_dirs.join(", "),
+				if time_kind == TimeKind::Accessed {
+					" and the result is unreliable"
+				} else {
+					""
+				}
+			));
+		}
+
+		let mut list = stale_folders;
+		list.extend(stale_files);
+		list.sort_by(|a, b| {
+			b.age_days
+				.cmp(&a.age_days)
+				.then_with(|| b.size.cmp(&a.size))
+		});
+
+		app.emit(
+			"scan-result",
+			ScanResult {
+				cmd: "scan_stale_files",
+				list,
+				message,
+			},
+		)
+		.unwrap();
+	});
+}
+
+fn get_time(path: &Path, time_kind: TimeKind) -> Option<u64> {
+	let metadata = fs::symlink_metadata(path).ok()?;
+	let time = match time_kind {
+		TimeKind::Modified => metadata.modified().ok()?,
+		TimeKind::Accessed => metadata.accessed().ok()?,
+		TimeKind::Changed => return get_changed_time(&metadata),
+	};
+	time.duration_since(UNIX_EPOCH)
+		.ok()
+		.map(|duration| duration.as_secs())
+}
+
+#[cfg(unix)]
+fn get_changed_time(metadata: &fs::Metadata) -> Option<u64> {
+	use std::os::unix::fs::MetadataExt;
+
+	u64::try_from(metadata.ctime()).ok()
+}
+
+// There is no inode change time on other platforms, the creation time is
+// the closest to it
+#[cfg(not(unix))]
+fn get_changed_time(metadata: &fs::Metadata) -> Option<u64> {
+	metadata
+		.created()
+		.ok()?
+		.duration_since(UNIX_EPOCH)
+		.ok()
+		.map(|duration| duration.as_secs())
+}
+
+fn get_folder_ages(
+	tree: &Tree,
+	time_kind: TimeKind,
+) -> HashMap<PathBuf, FolderAge> {
+	let mut folders: HashMap<PathBuf, FolderAge> = tree
+		.folders
+		.iter()
+		.map(|path| (path.clone(), FolderAge::default()))
+		.collect();
+
+	let entries = tree
+		.files
+		.iter()
+		.map(|file| (&file.path, file.size, true))
+		.chain(tree.others.iter().map(|(path, _)| (path, 0, false)));
+
+	for (path, size, is_file) in entries {
+		let time = get_time(path, time_kind);
+		for ancestor in path.ancestors().skip(1) {
+			let Some(folder) = folders.get_mut(ancestor) else {
+				break;
+			};
+			folder.size += size;
+			folder.file_count += is_file as usize;
+			match time {
+				Some(time) => folder.newest = folder.newest.max(time),
+				None => folder.incomplete = true,
+			}
+		}
+	}
+
+	for path in &tree.unreadable {
+		for ancestor in path.ancestors() {
+			let Some(folder) = folders.get_mut(ancestor) else {
+				break;
+			};
+			folder.incomplete = true;
+		}
+	}
+
+	folders
+}
+
+#[cfg(target_os = "linux")]
+fn is_noatime_mount(path: &Path) -> bool {
+	let Ok(mounts) = fs::read_to_string("/proc/self/mounts") else {
+		return false;
+	};
+
+	// The mount point closest to the path is the one it lives on
+	mounts
+		.lines()
+		.filter_map(|line| {
+			let mut fields = line.split_whitespace();
+			let mount_point = fields.nth(1)?.replace("\\040", " ");
+			let options = fields.nth(1)?;
+			path.starts_with(&mount_point)
+				.then_some((mount_point, options))
+		})
+		.max_by_key(|(mount_point, _)| mount_point.len())
+		.is_some_and(|(_, options)| {
+			options.split(',').any(|option| option == "noatime")
+		})
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_noatime_mount(_path: &Path) -> bool {
+	false
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		fs::File,
+		sync::{Arc, atomic::AtomicBool},
+		time::Duration,
+	};
+
+	use super::*;
+
+	fn write_file(path: &Path, content: &str, modified: u64) {
+		fs::write(path, content).unwrap();
+		File::options()
+			.write(true)
+			.open(path)
+			.unwrap()
+			.set_modified(UNIX_EPOCH + Duration::from_secs(modified))
+			.unwrap();
+	}
+
+	#[test]
+	fn folder_ages_come_from_everything_below() {
+		let root = std::env::temp_dir()
+			.join(format!("czkawka-tauri-stale-{}", std::process::id()));
+		fs::create_dir_all(root.join("a/b")).unwrap();
+		fs::create_dir_all(root.join("c")).unwrap();
+		write_file(&root.join("a/old.txt"), "old", 1000);
+		write_file(&root.join("a/b/new.txt"), "newer", 5000);
+		write_file(&root.join("c/other.txt"), "x", 2000);
+
+		let mut tree = walk_tree(
+			std::slice::from_ref(&root),
+			&Arc::new(AtomicBool::new(false)),
+			None,
+		);
+		// Like a folder that could not be read by the walk
+		tree.folders.push(root.join("c/locked"));
+		tree.unreadable.push(root.join("c/locked"));
+		let ages = get_folder_ages(&tree, TimeKind::Modified);
+		fs::remove_dir_all(&root).unwrap();
+
+		let a = &ages[&root.join("a")];
+		assert_eq!((a.size, a.file_count, a.newest), (8, 2, 5000));
+		assert!(!a.incomplete);
+		let b = &ages[&root.join("a/b")];
+		assert_eq!((b.size, b.file_count, b.newest), (5, 1, 5000));
+		let c = &ages[&root.join("c")];
+		assert_eq!((c.size, c.file_count, c.newest), (1, 1, 2000));
+		assert!(c.incomplete);
+		assert!(ages[&root].incomplete);
+		assert_eq!(ages[&root].file_count, 3);
+	}
+}
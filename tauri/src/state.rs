@@ -14,6 +14,12 @@ use czkawka_core::{
 };
 use tauri::{AppHandle, Manager};
 
+use crate::{
+	bad_extensions::CustomBadFileEntry, broken_files::CustomBrokenEntry,
+	disk_usage::DiskUsageTree, empty_files::CustomEmptyFileEntry,
+	similar_images::ImagesHashes,
+};
+
 pub struct AppState {
 	pub is_number_of_threads_setup: bool,
 	pub is_progress_thread_setup: bool,
@@ -23,14 +29,19 @@ pub struct AppState {
 	pub duplication_state: Option<DuplicateFinder>,
 	pub empty_folders_state: Option<EmptyFolder>,
 	pub empty_files_state: Option<EmptyFiles>,
+	pub empty_files_entries: Option<Vec<CustomEmptyFileEntry>>,
 	pub temporary_files_state: Option<Temporary>,
 	pub big_files_state: Option<BigFile>,
+	pub disk_usage_tree: Option<Arc<DiskUsageTree>>,
 	pub similar_images_state: Option<SimilarImages>,
+	pub similar_images_hashes: Option<Arc<ImagesHashes>>,
 	pub similar_videos_state: Option<SimilarVideos>,
 	pub same_music_state: Option<SameMusic>,
 	pub same_invalid_symlinks: Option<InvalidSymlinks>,
 	pub broken_files_state: Option<BrokenFiles>,
+	pub broken_files_entries: Option<Vec<CustomBrokenEntry>>,
 	pub bad_extensions_state: Option<BadExtensions>,
+	pub bad_extensions_entries: Option<Vec<CustomBadFileEntry>>,
 }
 
 impl Default for AppState {
@@ -46,14 +57,19 @@ impl Default for AppState {
 			duplication_state: None,
 			empty_folders_state: None,
 			empty_files_state: None,
+			empty_files_entries: None,
 			temporary_files_state: None,
 			big_files_state: None,
+			disk_usage_tree: None,
 			similar_images_state: None,
+			similar_images_hashes: None,
 			similar_videos_state: None,
 			same_music_state: None,
 			same_invalid_symlinks: None,
 			broken_files_state: None,
+			broken_files_entries: None,
 			bad_extensions_state: None,
+			bad_extensions_entries: None,
 		}
 	}
 }
@@ -1,5 +1,7 @@
-use std::{fs, path::PathBuf};
+use std::{collections::HashMap, fs, path::PathBuf};
 
+use czkawka_core::common::config_cache_path::get_config_cache_path;
+use serde::{Serialize, de::DeserializeOwned};
 use simplelog::{Config, LevelFilter, WriteLogger};
 
 pub fn convert_strs_to_path_bufs(strs: Vec<String>) -> Vec<PathBuf> {
@@ -25,3 +27,51 @@ pub fn setup_log(app_env: &tauri::Env) {
 	};
 	let _ = WriteLogger::init(LevelFilter::Info, Config::default(), log_file);
 }
+
+// Caches of this app are kept as json files next to the czkawka caches
+pub fn load_json_cache<T: DeserializeOwned>(
+	file_name: &str,
+) -> HashMap<String, T> {
+	let Some(cache_file_path) = get_cache_file_path(file_name) else {
+		return HashMap::new();
+	};
+	let Ok(data) = fs::read(&cache_file_path) else {
+		return HashMap::new();
+	};
+
+	serde_json::from_slice(&data).unwrap_or_else(|err| {
+		log::info!(
+			"Failed to load cache `{}`: {}",
+			cache_file_path.display(),
+			err
+		);
+		HashMap::new()
+	})
+}
+
+pub fn save_json_cache<T: Serialize>(
+	file_name: &str,
+	cache: &HashMap<String, T>,
+) {
+	let Some(cache_file_path) = get_cache_file_path(file_name) else {
+		return;
+	};
+	let result = serde_json::to_vec(cache)
+		.map_err(|err| err.to_string())
+		.and_then(|data| {
+			fs::write(&cache_file_path, data).map_err(|err| err.to_string())
+		});
+
+	if let Err(err) = result {
+		log::info!(
+			"Failed to save cache `{}`: {}",
+			cache_file_path.display(),
+			err
+		);
+	}
+}
+
+fn get_cache_file_path(file_name: &str) -> Option<PathBuf> {
+	get_config_cache_path()
+		.map(|config_cache| config_cache.cache_folder.join(file_name))
+}
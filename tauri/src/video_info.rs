@@ -0,0 +1,207 @@
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+	process::Command,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{load_json_cache, save_json_cache};
+
+const CACHE_FILE_NAME: &str = "cache_video_info.json";
+
+pub const VIDEO_EXTENSIONS: &[&str] = &[
+	"mp4", "m4v", "mkv", "webm", "mov", "avi", "wmv", "flv", "mpg", "mpeg",
+	"ts", "mts", "m2ts", "3gp",
+];
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct VideoInfo {
+	pub duration: f64,
+	pub width: u32,
+	pub height: u32,
+	pub video_codec: String,
+	pub audio_codec: String,
+	pub bitrate: u64,
+	pub frame_rate: f64,
+	pub container: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+	size: u64,
+	modified_date: u64,
+	info: VideoInfo,
+}
+
+pub struct VideoToProbe<'a> {
+	pub path: &'a Path,
+	pub size: u64,
+	pub modified_date: u64,
+}
+
+#[derive(Deserialize)]
+struct FfprobeOutput {
+	#[serde(default)]
+	streams: Vec<FfprobeStream>,
+	format: Option<FfprobeFormat>,
+}
+
+#[derive(Deserialize)]
+struct FfprobeStream {
+	codec_type: Option<String>,
+	codec_name: Option<String>,
+	width: Option<u32>,
+	height: Option<u32>,
+	avg_frame_rate: Option<String>,
+	r_frame_rate: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct FfprobeFormat {
+	format_name: Option<String>,
+	duration: Option<String>,
+	bit_rate: Option<String>,
+}
+
+pub fn probe_videos(
+	videos: Vec<VideoToProbe<'_>>,
+	ffprobe: &str,
+	stop_flag: &Arc<AtomicBool>,
+) -> HashMap<PathBuf, VideoInfo> {
+	let mut cache: HashMap<String, CacheEntry> =
+		load_json_cache(CACHE_FILE_NAME);
+
+	let probed: Vec<_> = videos
+		.into_par_iter()
+		.filter_map(|video| {
+			let key = video.path.to_string_lossy().to_string();
+
+			if let Some(entry) = cache.get(&key)
+				&& entry.size == video.size
+				&& entry.modified_date == video.modified_date
+			{
+				return Some((video, entry.info.clone(), false));
+			}
+
+			if stop_flag.load(Ordering::Relaxed) {
+				return None;
+			}
+
+			match probe_video(video.path, ffprobe) {
+				Ok(info) => Some((video, info, true)),
+				Err(err) => {
+					log::info!(
+						"Failed to probe `{}`: {}",
+						video.path.display(),
+						err
+					);
+					None
+				}
+			}
+		})
+		.collect();
+
+	let mut cache_changed = false;
+	let mut result = HashMap::with_capacity(probed.len());
+
+	for (video, info, is_new) in probed {
+		if is_new {
+			cache_changed = true;
+			cache.insert(
+				video.path.to_string_lossy().to_string(),
+				CacheEntry {
+					size: video.size,
+					modified_date: video.modified_date,
+					info: info.clone(),
+				},
+			);
+		}
+		result.insert(video.path.to_path_buf(), info);
+	}
+
+	if cache_changed {
+		save_json_cache(CACHE_FILE_NAME, &cache);
+	}
+
+	result
+}
+
+pub fn probe_video(path: &Path, ffprobe: &str) -> Result<VideoInfo, String> {
+	let output = Command::new(ffprobe)
+		.args([
+			"-v",
+			"quiet",
+			"-print_format",
+			"json",
+			"-show_format",
+			"-show_streams",
+		])
+		.arg(path)
+		.output()
+		.map_err(|err| err.to_string())?;
+
+	if !output.status.success() {
+		return Err(format!("ffprobe exited with status: {}", output.status));
+	}
+
+	let ffprobe_output: FfprobeOutput = serde_json::from_slice(&output.stdout)
+		.map_err(|err| err.to_string())?;
+
+	let mut info = VideoInfo::default();
+
+	if let Some(format) = ffprobe_output.format {
+		info.container = format.format_name.unwrap_or_default();
+		info.duration = format
+			.duration
+			.and_then(|v| v.parse::<f64>().ok())
+			.unwrap_or_default();
+		info.bitrate = format
+			.bit_rate
+			.and_then(|v| v.parse::<u64>().ok())
+			.unwrap_or_default();
+	}
+
+	for stream in ffprobe_output.streams {
+		match stream.codec_type.as_deref() {
+			Some("video") if info.video_codec.is_empty() => {
+				info.video_codec = stream.codec_name.unwrap_or_default();
+				info.width = stream.width.unwrap_or_default();
+				info.height = stream.height.unwrap_or_default();
+				info.frame_rate = stream
+					.avg_frame_rate
+					.as_deref()
+					.and_then(parse_frame_rate)
+					.or_else(|| {
+						stream
+							.r_frame_rate
+							.as_deref()
+							.and_then(parse_frame_rate)
+					})
+					.unwrap_or_default();
+			}
+			Some("audio") if info.audio_codec.is_empty() => {
+				info.audio_codec = stream.codec_name.unwrap_or_default();
+			}
+			_ => {}
+		}
+	}
+
+	Ok(info)
+}
+
+// ffprobe reports frame rates as a fraction, e.g. `30000/1001`
+fn parse_frame_rate(s: &str) -> Option<f64> {
+	let (num, den) = s.split_once('/')?;
+	let num = num.parse::<f64>().ok()?;
+	let den = den.parse::<f64>().ok()?;
+	if den == 0.0 {
+		return None;
+	}
+	Some(num / den)
+}
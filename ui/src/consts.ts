@@ -34,6 +34,31 @@ export const BigFilesSearchMode = {
   SmallestFiles: 'SmallestFiles',
 } as const;
 
+export const LargestFoldersRankBy = {
+  RecursiveSize: 'recursiveSize',
+  DirectSize: 'directSize',
+  FileCount: 'fileCount',
+} as const;
+
+export const NamePortabilityTarget = {
+  FAT: 'fat',
+  ExFAT: 'exfat',
+  NTFS: 'ntfs',
+  SMB: 'smb',
+} as const;
+
+export const InvalidSymlinksTargetMatch = {
+  Name: 'name',
+  Size: 'size',
+  Hash: 'hash',
+} as const;
+
+export const StaleFilesTimeKind = {
+  Modified: 'modified',
+  Accessed: 'accessed',
+  Changed: 'changed',
+} as const;
+
 export const SimilarImagesHashAlgorithm = {
   Mean: 'Mean',
   Gradient: 'Gradient',
@@ -64,11 +89,48 @@ export const DuplicatesAvailableHashType = {
   XXH3: 'XXH3',
 } as const;
 
+export const SimilarVideosCropDetect = {
+  Letterbox: 'Letterbox',
+  Motion: 'Motion',
+  None: 'None',
+} as const;
+
 export const SimilarMusicAudioCheckType = {
   Tags: 'Tags',
   Fingerprint: 'Fingerprint',
 } as const;
 
+export const RenameConflictStrategy = {
+  Skip: 'skip',
+  Suffix: 'suffix',
+} as const;
+
+export const DEFAULT_EQUIVALENT_EXTENSIONS = [
+  'jpg, jpeg, jpe, jfif',
+  'tif, tiff',
+  'htm, html',
+  'mpg, mpeg',
+  'mid, midi',
+  'yml, yaml',
+].join('\n');
+
+export const DEFAULT_MIME_EXTENSIONS = [
+  'application/zip: docm, dotm, xlsm, pptm, apk, jar, xpi',
+  'video/mp4: m4b',
+].join('\n');
+
+export const DEFAULT_SIDECAR_MAP = [
+  'xmp: jpg, jpeg, png, tif, tiff, heic, dng, cr2, cr3, nef, arw, orf, rw2, raf',
+  'aae: jpg, jpeg, heic, png, mov',
+  'thm: mp4, mov, avi, mts',
+  'srt: mp4, mkv, avi, mov, webm, m4v',
+  'lrc: mp3, flac, m4a, ogg, opus, wav',
+  'nfo: mkv, mp4, avi, mov, m4v',
+].join('\n');
+
+export const DEFAULT_IGNORABLE_FILES =
+  '.DS_Store, Thumbs.db, desktop.ini, .directory';
+
 export function getDefaultSettings(): Settings {
   return {
     includedDirectories: [],
@@ -83,6 +145,8 @@ export function getDefaultSettings(): Settings {
     useCache: true,
     saveAlsoAsJson: false,
     moveDeletedFilesToTrash: true,
+    deleteSidecars: false,
+    sidecarMap: DEFAULT_SIDECAR_MAP,
     threadNumber: 1,
 
     duplicateImagePreview: true,
@@ -94,6 +158,19 @@ export function getDefaultSettings(): Settings {
     duplicatesSubCheckMethod: DuplicatesCheckMethod.Hash,
     duplicatesSubAvailableHashType: DuplicatesAvailableHashType.Blake3,
     duplicatesSubNameCaseSensitive: false,
+    duplicatesSubScanArchives: false,
+    badExtensionsSubEquivalentExtensions: DEFAULT_EQUIVALENT_EXTENSIONS,
+    badExtensionsSubCustomSignatures: '',
+    badExtensionsSubMimeExtensions: DEFAULT_MIME_EXTENSIONS,
+    duplicateFoldersSubIncludeSubsets: false,
+    emptyFoldersSubIgnorableFiles: DEFAULT_IGNORABLE_FILES,
+    emptyFoldersSubIgnoreZeroByteFiles: false,
+
+    emptyFilesSubEffectivelyEmpty: false,
+    emptyFilesSubWhitespace: true,
+    emptyFilesSubBom: true,
+    emptyFilesSubNul: true,
+    emptyFilesSubEmptyContainer: true,
 
     similarImagesHideHardLinks: true,
     similarImagesShowImagePreview: true,
@@ -103,14 +180,21 @@ export function getDefaultSettings(): Settings {
     similarImagesSubResizeAlgorithm: SimilarImagesResizeAlgorithm.Lanczos3,
     similarImagesSubIgnoreSameSize: false,
     similarImagesSubSimilarity: 10,
+    similarImagesSubRotationInvariant: false,
 
     biggestFilesSubMethod: BigFilesSearchMode.BiggestFiles,
     biggestFilesSubNumberOfFiles: 50,
+    largestFoldersSubRankBy: LargestFoldersRankBy.RecursiveSize,
 
     similarVideosHideHardLinks: true,
     similarVideosDeleteOutdatedEntries: true,
     similarVideosSubIgnoreSameSize: false,
     similarVideosSubSimilarity: 15,
+    similarVideosSubSkipForwardAmount: 15,
+    similarVideosSubVidHashDuration: 10,
+    similarVideosSubCropDetect: SimilarVideosCropDetect.Letterbox,
+    ffmpegPath: '',
+    ffprobePath: '',
 
     similarMusicDeleteOutdatedEntries: true,
     similarMusicSubAudioCheckType: SimilarMusicAudioCheckType.Tags,
@@ -125,10 +209,24 @@ export function getDefaultSettings(): Settings {
     similarMusicSubMaximumDifferenceValue: 3,
     similarMusicSubMinimalFragmentDurationValue: 5,
 
+    similarNamesSubThreshold: 80,
+
+    staleFilesSubTimeKind: StaleFilesTimeKind.Modified,
+    staleFilesSubDays: 730,
+
+    namePortabilitySubTarget: NamePortabilityTarget.ExFAT,
+
+    invalidSymlinksSubTargetMatch: InvalidSymlinksTargetMatch.Size,
+    invalidSymlinksSubOutsideTree: true,
+
     brokenFilesSubAudio: true,
     brokenFilesSubPdf: false,
     brokenFilesSubArchive: false,
     brokenFilesSubImage: false,
+    brokenFilesSubVideo: false,
+    brokenFilesSubDocument: false,
+    brokenFilesSubFont: false,
+    brokenFilesSubDeepArchive: false,
   };
 }
 
@@ -144,18 +242,55 @@ export function getDefaultPlatformSettings(): PlatformSettings {
 
 export const Tools = {
   DuplicateFiles: 'duplicateFiles',
+  DuplicateFolders: 'duplicateFolders',
   EmptyFolders: 'emptyFolders',
   BigFiles: 'bigFiles',
+  LargestFolders: 'largestFolders',
+  DiskUsage: 'diskUsage',
+  StaleFiles: 'staleFiles',
   EmptyFiles: 'emptyFiles',
   TemporaryFiles: 'temporaryFiles',
+  OrphanedSidecars: 'orphanedSidecars',
   SimilarImages: 'similarImages',
   SimilarVideos: 'similarVideos',
   MusicDuplicates: 'musicDuplicates',
+  SimilarNames: 'similarNames',
+  NumberedCopies: 'numberedCopies',
+  NameCollisions: 'nameCollisions',
   InvalidSymlinks: 'invalidSymlinks',
   BrokenFiles: 'brokenFiles',
   BadExtensions: 'badExtensions',
+  NamePortability: 'namePortability',
 } as const;
 
+export const SampleFileExtensions: Record<string, string[]> = {
+  [Tools.SimilarImages]: [
+    'jpg',
+    'jpeg',
+    'png',
+    'bmp',
+    'gif',
+    'tif',
+    'tiff',
+    'webp',
+    'heic',
+    'avif',
+  ],
+  [Tools.SimilarVideos]: [
+    'mp4',
+    'mkv',
+    'mov',
+    'avi',
+    'webm',
+    'wmv',
+    'flv',
+    'm4v',
+    'mpg',
+    'mpeg',
+  ],
+  [Tools.MusicDuplicates]: ['mp3', 'flac', 'wav', 'ogg', 'm4a', 'aac', 'opus'],
+};
+
 export function getDefaultProgress(): Progress {
   return {
     tool: '',
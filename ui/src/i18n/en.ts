@@ -18,6 +18,7 @@ export const en = {
   recursiveSearch: 'Recursive search',
   alsoSaveCacheAsJsonFile: 'Also save cache as JSON file',
   moveDeletedFilesToTrash: 'Move deleted files to trash',
+  deleteSidecarsWithFiles: 'Delete sidecars along with files',
   threadNumber: 'Thread number',
   threadNumberTip: 'You need to restart app to apply changes in thread number',
   minimalSizeOfCachedFiles: 'Minimal size of cached files',
@@ -31,28 +32,59 @@ export const en = {
   toggleTheme: 'Toggle theme',
   viewSourceCode: 'View source code',
   duplicateFiles: 'Duplicate Files',
+  duplicateFolders: 'Duplicate Folders',
   emptyFolders: 'Empty Folders',
   bigFiles: 'Big Files',
+  largestFolders: 'Largest Folders',
+  diskUsage: 'Disk Usage',
+  staleFiles: 'Stale Files',
   emptyFiles: 'Empty Files',
   temporaryFiles: 'Temporary Files',
+  orphanedSidecars: 'Orphaned Sidecars',
   similarImages: 'Similar Images',
   similarVideos: 'Similar Videos',
   musicDuplicates: 'Music Duplicates',
+  similarNames: 'Similar Names',
+  numberedCopies: 'Numbered Copies',
+  nameCollisions: 'Name Collisions',
   invalidSymlinks: 'Invalid Symlinks',
   brokenFiles: 'Broken Files',
   badExtensions: 'Bad Extensions',
+  namePortability: 'Name Portability',
   size: 'Size',
   fileName: 'File name',
   path: 'Path',
   modifiedDate: 'Modified date',
   folderName: 'Folder name',
+  fileCount: 'File count',
+  directSize: 'Direct size',
+  issues: 'Issues',
+  proposedName: 'Proposed name',
+  proposedTarget: 'Proposed target',
+  content: 'Content',
+  normalizationForm: 'Normalization form',
+  directFileCount: 'Direct file count',
   similarity: 'Similarity',
+  score: 'Score',
+  normalizedName: 'Normalized name',
+  status: 'Status',
+  kind: 'Kind',
+  percent: 'Percent',
+  age: 'Age',
+  lastUsedDate: 'Last used date',
   dimensions: 'Dimensions',
   title: 'Title',
   artist: 'Artist',
   year: 'Year',
   bitrate: 'Bitrate',
   length: 'Length',
+  duration: 'Duration',
+  codec: 'Codec',
+  frameRate: 'Frame rate',
+  container: 'Container',
+  distance: 'Distance',
+  matchInfo: 'Match info (approximate)',
+  transform: 'Transform',
   symlinkName: 'Symlink name',
   symlinkPath: 'Symlink path',
   destinationPath: 'Destination path',
@@ -82,7 +114,16 @@ export const en = {
   sizeAndName: 'Size and name',
   hashType: 'Hash type',
   caseSensitive: 'Case sensitive(only name modes)',
+  includeSubsetFolders: 'Also find folders contained in others',
   checkedFiles: 'Checked files',
+  checkedFolders: 'Checked folders',
+  rankBy: 'Rank by',
+  targetFilesystem: 'Target filesystem',
+  timeToCheck: 'Time to check',
+  modifiedTime: 'Modified time',
+  accessedTime: 'Accessed time',
+  changedTime: 'Changed time',
+  olderThanDays: 'Older than (days)',
   biggest: 'Biggest',
   smallest: 'Smallest',
   numberOfLines: 'Number of lines',
@@ -90,6 +131,27 @@ export const en = {
   resizeAlgorithm: 'Resize algorithm',
   ignoreSameSize: 'Ignore same size',
   maxDifference: 'Max difference',
+  similarityThreshold: 'Similarity threshold',
+  regroupWithoutRehashing: 'Regroup last scan without rehashing',
+  regroup: 'Regroup',
+  findSimilar: 'Find similar',
+  sampleFile: 'Sample file',
+  findCopies: 'Find copies',
+  openFolder: 'Open folder',
+  parentFolder: 'Parent folder',
+  matchRotatedAndMirrored: 'Match rotated and mirrored images',
+  skipForwardAmount: 'Skip forward amount(seconds)',
+  hashDuration: 'Hash duration(seconds)',
+  cropDetect: 'Crop detect',
+  ffmpegPath: 'ffmpeg path',
+  ffprobePath: 'ffprobe path',
+  detectAutomatically: 'Detect automatically',
+  checkFfmpeg: 'Check ffmpeg',
+  check: 'Check',
+  videoScanningUnavailable: 'Video scanning will not work',
+  letterbox: 'Letterbox',
+  motion: 'Motion',
+  none: 'None',
   audioCheckType: 'Audio check type',
   tags: 'Tags',
   fingerprint: 'Fingerprint',
@@ -103,6 +165,25 @@ export const en = {
   pdf: 'Pdf',
   archive: 'Archive',
   image: 'Image',
+  video: 'Video',
+  officeDocument: 'Office document',
+  font: 'Font',
+  deepArchiveCheck: 'Check nested and password-protected archives',
+  ignorableFiles: 'Ignorable files',
+  ignoreZeroByteFiles: 'Ignore zero-byte files',
+  effectivelyEmpty: 'Effectively empty',
+  contentToTreatAsEmpty: 'Content to treat as empty',
+  whitespace: 'Whitespace',
+  bom: 'BOM',
+  nulBytes: 'NUL bytes',
+  emptyZipOrJson: 'Empty zip or JSON',
+  reason: 'Reason',
+  sidecarMap: 'Sidecar to primary extensions',
+  primaryExtensions: 'Primary extensions',
+  scanInsideArchives: 'Scan inside zip, tar and 7z archives',
+  archiveStatus: 'Archive',
+  insideAnArchive: 'Inside an archive',
+  redundantAlsoInAnArchive: 'Redundant, also in an archive',
   ok: 'Ok',
   cancel: 'Cancel',
   select: 'Select',
@@ -134,6 +215,30 @@ export const en = {
   renamingFiles: 'Renaming files',
   renameConfirm:
     'This will rename extensions of selected <1>{{length}}</1> files to more proper. Are you want to continue?',
+  whenNameIsTaken: 'When the new name is taken',
+  skip: 'Skip',
+  addNumberSuffix: 'Add a number suffix',
+  removeIfIdentical: 'Remove the file if its content is identical',
+  fixAll: 'Fix all',
+  fixAllExtensionsConfirm:
+    'This will rename extensions of all <1>{{length}}</1> found files to the recommended ones, equivalent extensions are kept. Are you want to continue?',
+  equivalentExtensions: 'Equivalent extensions',
+  allowedExtensionsPerMime: 'Allowed extensions per MIME type',
+  customSignatures: 'Custom magic-byte signatures',
+  fixNames: 'Fix names',
+  renamingItems: 'Renaming items',
+  fixNamesConfirm:
+    'This will rename selected <1>{{length}}</1> items to their proposed names. Are you want to continue?',
+  repairSymlinks: 'Repair symlinks',
+  repairingSymlinks: 'Repairing symlinks',
+  repairSymlinksConfirm:
+    'This will point selected <1>{{length}}</1> symlinks to their proposed targets, all of them or none are changed. Are you want to continue?',
+  makeLinksRelative: 'Convert the links to relative ones',
+  matchNewTargetsBy: 'Match new targets by',
+  nameAndSize: 'Name and cached size',
+  nameAndHash: 'Name and cached hash',
+  reportLinksOutsideTree:
+    'Report links pointing outside the included directories',
   expand: 'Expand',
   collapse: 'Collapse',
   directories: 'Directories',
@@ -20,6 +20,7 @@ export const zh: Record<TranslationKeys, string> = {
   recursiveSearch: '递归搜索',
   alsoSaveCacheAsJsonFile: '同时保存缓存为JSON文件',
   moveDeletedFilesToTrash: '删除的文件移动到回收站',
+  deleteSidecarsWithFiles: '删除文件时一并删除附属文件',
   threadNumber: '线程数',
   threadNumberTip: '修改线程数需要重启才能生效',
   minimalSizeOfCachedFiles: '最小缓存文件大小',
@@ -33,28 +34,59 @@ export const zh: Record<TranslationKeys, string> = {
   toggleTheme: '切换主题',
   viewSourceCode: '查看源代码',
   duplicateFiles: '重复文件',
+  duplicateFolders: '重复文件夹',
   emptyFolders: '空文件夹',
   bigFiles: '大文件',
+  largestFolders: '大文件夹',
+  diskUsage: '磁盘占用',
+  staleFiles: '陈旧文件',
   emptyFiles: '空文件',
   temporaryFiles: '临时文件',
+  orphanedSidecars: '孤立附属文件',
   similarImages: '相似图片',
   similarVideos: '相似视频',
   musicDuplicates: '重复音频',
+  similarNames: '相似文件名',
+  numberedCopies: '编号副本',
+  nameCollisions: '文件名冲突',
   invalidSymlinks: '无效符号链接',
   brokenFiles: '损坏文件',
   badExtensions: '不正确扩展名',
+  namePortability: '文件名可移植性',
   size: '大小',
   fileName: '名称',
   path: '路径',
   modifiedDate: '修改日期',
   folderName: '名称',
+  fileCount: '文件数',
+  directSize: '直接大小',
+  issues: '问题',
+  proposedName: '建议名称',
+  proposedTarget: '建议目标',
+  content: '内容',
+  normalizationForm: '规范化形式',
+  directFileCount: '直接文件数',
   similarity: '相似度',
+  score: '得分',
+  normalizedName: '规范化名称',
+  status: '状态',
+  kind: '类型',
+  percent: '占比',
+  age: '闲置时长',
+  lastUsedDate: '最后使用日期',
   dimensions: '分辨率',
   title: '标题',
   artist: '艺术家',
   year: '日期',
   bitrate: '比特率',
   length: '时长',
+  duration: '时长',
+  codec: '编码',
+  frameRate: '帧率',
+  container: '封装格式',
+  distance: '汉明距离',
+  matchInfo: '匹配信息（近似）',
+  transform: '变换',
   symlinkName: '名称',
   symlinkPath: '路径',
   destinationPath: '目标路径',
@@ -84,7 +116,16 @@ export const zh: Record<TranslationKeys, string> = {
   sizeAndName: '大小和名称',
   hashType: '哈希类型',
   caseSensitive: '大小写敏感(仅名称模式)',
+  includeSubsetFolders: '同时查找被其他文件夹包含的文件夹',
   checkedFiles: '检查的文件',
+  checkedFolders: '检查的文件夹',
+  rankBy: '排序依据',
+  targetFilesystem: '目标文件系统',
+  timeToCheck: '检查的时间',
+  modifiedTime: '修改时间',
+  accessedTime: '访问时间',
+  changedTime: '变更时间',
+  olderThanDays: '早于(天)',
   biggest: '最大的',
   smallest: '最小的',
   numberOfLines: '行数(前多少个)',
@@ -92,6 +133,27 @@ export const zh: Record<TranslationKeys, string> = {
   resizeAlgorithm: '大小调整算法',
   ignoreSameSize: '忽略相同大小',
   maxDifference: '最大差异度',
+  similarityThreshold: '相似度阈值',
+  regroupWithoutRehashing: '不重新计算哈希, 重新分组上次扫描结果',
+  regroup: '重新分组',
+  findSimilar: '查找相似',
+  sampleFile: '样本文件',
+  findCopies: '查找副本',
+  openFolder: '打开文件夹',
+  parentFolder: '上级文件夹',
+  matchRotatedAndMirrored: '匹配旋转和镜像的图片',
+  skipForwardAmount: '跳过开头时长(秒)',
+  hashDuration: '哈希时长(秒)',
+  cropDetect: '裁剪检测',
+  ffmpegPath: 'ffmpeg 路径',
+  ffprobePath: 'ffprobe 路径',
+  detectAutomatically: '自动检测',
+  checkFfmpeg: '检查 ffmpeg',
+  check: '检查',
+  videoScanningUnavailable: '视频扫描无法使用',
+  letterbox: '黑边',
+  motion: '运动',
+  none: '无',
   audioCheckType: '音频检查方式',
   tags: '标签',
   fingerprint: '指纹',
@@ -105,6 +167,25 @@ export const zh: Record<TranslationKeys, string> = {
   pdf: 'Pdf',
   archive: '压缩包',
   image: '图片',
+  video: '视频',
+  officeDocument: '办公文档',
+  font: '字体',
+  deepArchiveCheck: '检查嵌套和加密的压缩包',
+  ignorableFiles: '可忽略的文件',
+  ignoreZeroByteFiles: '忽略零字节文件',
+  effectivelyEmpty: '实质为空',
+  contentToTreatAsEmpty: '视为空的内容',
+  whitespace: '空白字符',
+  bom: 'BOM',
+  nulBytes: 'NUL 字节',
+  emptyZipOrJson: '空 zip 或 JSON',
+  reason: '原因',
+  sidecarMap: '附属文件与主文件扩展名',
+  primaryExtensions: '主文件扩展名',
+  scanInsideArchives: '扫描 zip、tar 和 7z 压缩包内部',
+  archiveStatus: '压缩包',
+  insideAnArchive: '位于压缩包内',
+  redundantAlsoInAnArchive: '冗余，压缩包内也有',
   ok: '确定',
   cancel: '取消',
   select: '选择',
@@ -134,6 +215,29 @@ export const zh: Record<TranslationKeys, string> = {
   renamingFiles: '重命名拓展名',
   renameConfirm:
     '这将把所选的 <1>{{length}}</1> 个文件的扩展名更改为预计更合适的扩展名。您是否要继续?',
+  whenNameIsTaken: '新名称已存在时',
+  skip: '跳过',
+  addNumberSuffix: '添加数字后缀',
+  removeIfIdentical: '内容相同时删除该文件',
+  fixAll: '全部修复',
+  fixAllExtensionsConfirm:
+    '这将把找到的全部 <1>{{length}}</1> 个文件的扩展名更改为推荐的扩展名, 等效的扩展名会被保留。您是否要继续?',
+  equivalentExtensions: '等效扩展名',
+  allowedExtensionsPerMime: '各 MIME 类型允许的扩展名',
+  customSignatures: '自定义魔数签名',
+  fixNames: '修复名称',
+  renamingItems: '重命名项目',
+  fixNamesConfirm:
+    '这将把所选的 <1>{{length}}</1> 个项目重命名为建议的名称。您是否要继续?',
+  repairSymlinks: '修复符号链接',
+  repairingSymlinks: '修复符号链接',
+  repairSymlinksConfirm:
+    '这将把所选的 <1>{{length}}</1> 个符号链接指向建议的目标, 要么全部修改, 要么全部不修改。您是否要继续?',
+  makeLinksRelative: '转换为相对链接',
+  matchNewTargetsBy: '新目标匹配方式',
+  nameAndSize: '名称和缓存的大小',
+  nameAndHash: '名称和缓存的哈希',
+  reportLinksOutsideTree: '报告指向包含目录之外的链接',
   expand: '展开',
   collapse: '收起',
   directories: '目录',
@@ -1,5 +1,11 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { ImageInfo, PlatformSettings, ScanCmd, Settings } from '~/types';
+import type {
+  ImageInfo,
+  PlatformSettings,
+  RawDiskUsageNode,
+  ScanCmd,
+  Settings,
+} from '~/types';
 
 interface MoveFilesOptions {
   paths: string[];
@@ -7,12 +13,28 @@ interface MoveFilesOptions {
   copyMode: boolean;
   preserveStructure: boolean;
   overrideMode: boolean;
+  folderFingerprints: Record<string, string>;
 }
 
 interface DeleteFilesOptions {
   paths: string[];
   moveDeletedFilesToTrash: boolean;
   isEmptyFoldersTool: boolean;
+  deleteSidecars: boolean;
+  sidecarMap: string;
+  ignorableFiles: string;
+  ignoreZeroByteFiles: boolean;
+  excludedDirectories: string[];
+  excludedItems: string;
+  folderFingerprints: Record<string, string>;
+}
+
+interface FixBadExtensionsOptions {
+  paths: string[] | null;
+  equivalentExtensions: string;
+  conflictStrategy: string;
+  removeIdentical: boolean;
+  moveDeletedFilesToTrash: boolean;
 }
 
 interface SaveResultOptions {
@@ -25,6 +47,24 @@ interface RenameExtOptions {
     path: string;
     ext: string;
   }[];
+  conflictStrategy: string;
+  removeIdentical: boolean;
+  moveDeletedFilesToTrash: boolean;
+}
+
+interface RepairSymlinksOptions {
+  items: {
+    path: string;
+    target: string;
+  }[];
+  makeRelative: boolean;
+}
+
+interface FixNamesOptions {
+  items: {
+    path: string;
+    name: string;
+  }[];
 }
 
 export const ipc = {
@@ -67,4 +107,39 @@ export const ipc = {
   renameExt(options: RenameExtOptions) {
     return invoke('rename_ext', { options });
   },
+
+  fixBadExtensions(options: FixBadExtensionsOptions) {
+    return invoke('fix_bad_extensions', { options });
+  },
+
+  fixNames(options: FixNamesOptions) {
+    return invoke('fix_names', { options });
+  },
+
+  repairSymlinks(options: RepairSymlinksOptions) {
+    return invoke('repair_symlinks', { options });
+  },
+
+  regroupSimilarImages(similarity: number) {
+    return invoke('regroup_similar_images', { similarity });
+  },
+
+  getDiskUsageTree(
+    path: string | null,
+    depth: number,
+  ): Promise<RawDiskUsageNode[]> {
+    return invoke('get_disk_usage_tree', { path, depth });
+  },
+
+  findFileCopies(path: string, settings: Settings) {
+    return invoke('find_file_copies', { path, settings });
+  },
+
+  findSimilarToSample(samplePath: string, settings: Settings) {
+    return invoke('find_similar_to_sample', { samplePath, settings });
+  },
+
+  checkFfmpeg(settings: Settings) {
+    return invoke('check_ffmpeg', { settings });
+  },
 };
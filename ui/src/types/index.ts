@@ -24,6 +24,8 @@ export interface Settings {
   useCache: boolean;
   saveAlsoAsJson: boolean;
   moveDeletedFilesToTrash: boolean;
+  deleteSidecars: boolean;
+  sidecarMap: string;
   threadNumber: number;
 
   duplicateImagePreview: boolean;
@@ -35,6 +37,18 @@ export interface Settings {
   duplicatesSubCheckMethod: string;
   duplicatesSubAvailableHashType: string;
   duplicatesSubNameCaseSensitive: boolean;
+  duplicatesSubScanArchives: boolean;
+  badExtensionsSubEquivalentExtensions: string;
+  badExtensionsSubCustomSignatures: string;
+  badExtensionsSubMimeExtensions: string;
+  duplicateFoldersSubIncludeSubsets: boolean;
+  emptyFoldersSubIgnorableFiles: string;
+  emptyFoldersSubIgnoreZeroByteFiles: boolean;
+  emptyFilesSubEffectivelyEmpty: boolean;
+  emptyFilesSubWhitespace: boolean;
+  emptyFilesSubBom: boolean;
+  emptyFilesSubNul: boolean;
+  emptyFilesSubEmptyContainer: boolean;
 
   similarImagesHideHardLinks: boolean;
   similarImagesShowImagePreview: boolean;
@@ -44,14 +58,21 @@ export interface Settings {
   similarImagesSubResizeAlgorithm: string;
   similarImagesSubIgnoreSameSize: boolean;
   similarImagesSubSimilarity: number;
+  similarImagesSubRotationInvariant: boolean;
 
   biggestFilesSubMethod: string;
   biggestFilesSubNumberOfFiles: number;
+  largestFoldersSubRankBy: string;
 
   similarVideosHideHardLinks: boolean;
   similarVideosDeleteOutdatedEntries: boolean;
   similarVideosSubIgnoreSameSize: boolean;
   similarVideosSubSimilarity: number;
+  similarVideosSubSkipForwardAmount: number;
+  similarVideosSubVidHashDuration: number;
+  similarVideosSubCropDetect: string;
+  ffmpegPath: string;
+  ffprobePath: string;
 
   similarMusicDeleteOutdatedEntries: boolean;
   similarMusicSubAudioCheckType: string;
@@ -66,10 +87,23 @@ export interface Settings {
   similarMusicSubMaximumDifferenceValue: number;
   similarMusicSubMinimalFragmentDurationValue: number;
 
+  similarNamesSubThreshold: number;
+
+  staleFilesSubTimeKind: string;
+  staleFilesSubDays: number;
+
+  namePortabilitySubTarget: string;
+  invalidSymlinksSubTargetMatch: string;
+  invalidSymlinksSubOutsideTree: boolean;
+
   brokenFilesSubAudio: boolean;
   brokenFilesSubPdf: boolean;
   brokenFilesSubArchive: boolean;
   brokenFilesSubImage: boolean;
+  brokenFilesSubVideo: boolean;
+  brokenFilesSubDocument: boolean;
+  brokenFilesSubFont: boolean;
+  brokenFilesSubDeepArchive: boolean;
 }
 
 export interface PlatformSettings {
@@ -87,16 +121,25 @@ export type DirsType = Extract<
 
 export type ScanCmd =
   | 'scan_duplicate_files'
+  | 'scan_duplicate_folders'
   | 'scan_empty_folders'
   | 'scan_big_files'
+  | 'scan_largest_folders'
+  | 'scan_disk_usage'
+  | 'scan_stale_files'
   | 'scan_empty_files'
   | 'scan_temporary_files'
   | 'scan_similar_images'
   | 'scan_similar_videos'
   | 'scan_music_duplicates'
+  | 'scan_similar_names'
+  | 'scan_numbered_copies'
+  | 'scan_orphaned_sidecars'
+  | 'scan_name_collisions'
   | 'scan_invalid_symlinks'
   | 'scan_broken_files'
-  | 'scan_bad_extensions';
+  | 'scan_bad_extensions'
+  | 'scan_name_portability';
 
 export interface Progress {
   tool: ToolsValues | '';
@@ -130,28 +173,167 @@ export interface FileEntry extends BaseEntry<RawFileEntry> {
   modifiedDate: string;
 }
 
+export interface RawEmptyFileEntry {
+  path: string;
+  size: number;
+  modified_date: number;
+  reason:
+    | 'zeroBytes'
+    | 'whitespace'
+    | 'bom'
+    | 'nul'
+    | 'emptyArchive'
+    | 'emptyJson';
+}
+
+export interface EmptyFileEntry extends BaseEntry<RawEmptyFileEntry> {
+  size: string;
+  fileName: string;
+  reason: string;
+  modifiedDate: string;
+}
+
+export interface RawSidecarEntry {
+  path: string;
+  size: number;
+  modified_date: number;
+  primary_extensions: string[];
+}
+
+export interface SidecarEntry extends BaseEntry<RawSidecarEntry> {
+  size: string;
+  fileName: string;
+  primaryExtensions: string;
+  modifiedDate: string;
+}
+
+export interface RawFolderSizeEntry {
+  path: string;
+  size: number;
+  direct_size: number;
+  file_count: number;
+  direct_file_count: number;
+  modified_date: number;
+}
+
+export interface FolderSizeEntry extends BaseEntry<RawFolderSizeEntry> {
+  size: string;
+  directSize: string;
+  fileCount: string;
+  directFileCount: string;
+  folderName: string;
+  modifiedDate: string;
+}
+
+export interface RawDiskUsageNode {
+  path: string;
+  name: string;
+  is_dir: boolean;
+  size: number;
+  file_count: number;
+  modified_date: number;
+  has_children: boolean;
+  children: RawDiskUsageNode[];
+  rest_size: number;
+  rest_count: number;
+}
+
+export interface DiskUsageEntry extends BaseEntry<RawDiskUsageNode> {
+  name: string;
+  size: string;
+  percent: string;
+  fileCount: string;
+  modifiedDate: string;
+}
+
+export interface RawStaleEntry {
+  path: string;
+  is_dir: boolean;
+  size: number;
+  file_count: number;
+  last_used_date: number;
+  age_days: number;
+}
+
+export interface StaleEntry extends BaseEntry<RawStaleEntry> {
+  name: string;
+  size: string;
+  age: string;
+  fileCount: string;
+  lastUsedDate: string;
+}
+
+export type NameIssue =
+  | 'invalidChars'
+  | 'controlChars'
+  | 'trailingDotOrSpace'
+  | 'reservedName'
+  | 'nameTooLong'
+  | 'pathTooLong'
+  | 'fileTooLarge';
+
+export interface RawNameIssueEntry {
+  path: string;
+  is_dir: boolean;
+  size: number;
+  modified_date: number;
+  issues: NameIssue[];
+  proposed_name: string;
+}
+
+export interface NameIssueEntry extends BaseEntry<RawNameIssueEntry> {
+  name: string;
+  issues: string;
+  proposedName: string;
+  size: string;
+  modifiedDate: string;
+}
+
 export interface RawDuplicateEntry {
   path: string;
   modified_date: number;
   size: number;
   hash: string;
+  archive_status: 'none' | 'member' | 'redundant';
 }
 
 export interface DuplicateEntry extends BaseEntry<RawDuplicateEntry> {
   size: string;
   fileName: string;
+  archiveStatus: string;
   modifiedDate: string;
   isImage: boolean;
 }
 
+export interface RawDuplicateFolderEntry {
+  path: string;
+  size: number;
+  file_count: number;
+  modified_date: number;
+  fingerprint: string;
+}
+
+export interface DuplicateFolderEntry
+  extends BaseEntry<RawDuplicateFolderEntry> {
+  size: string;
+  folderName: string;
+  fileCount: string;
+  modifiedDate: string;
+}
+
 export interface RawFolderOrTemporaryFileEntry {
   path: string;
   modified_date: number;
 }
 
-export interface FolderEntry extends BaseEntry<RawFolderOrTemporaryFileEntry> {
+export interface RawFolderEntry extends RawFolderOrTemporaryFileEntry {
+  junk_file_count: number;
+}
+
+export interface FolderEntry extends BaseEntry<RawFolderEntry> {
   folderName: string;
   modifiedDate: string;
+  junkFileCount: string;
 }
 
 export interface TemporaryFileEntry
@@ -167,6 +349,9 @@ export interface RawImagesEntry {
   height: number;
   modified_date: number;
   similarity: string;
+  distance: number;
+  hash_size: number;
+  transform: string;
 }
 
 export interface ImagesEntry extends BaseEntry<RawImagesEntry> {
@@ -175,18 +360,34 @@ export interface ImagesEntry extends BaseEntry<RawImagesEntry> {
   modifiedDate: string;
   similarity: string;
   dimensions: string;
+  distance: string;
+  transform: string;
 }
 
 export interface RawVideosEntry {
   path: string;
   size: number;
   modified_date: number;
+  duration: number;
+  width: number;
+  height: number;
+  video_codec: string;
+  audio_codec: string;
+  bitrate: number;
+  frame_rate: number;
+  container: string;
 }
 
 export interface VideosEntry extends BaseEntry<RawVideosEntry> {
   fileName: string;
   size: string;
   modifiedDate: string;
+  duration: string;
+  dimensions: string;
+  codec: string;
+  bitrate: string;
+  frameRate: string;
+  container: string;
 }
 
 export interface RawMusicEntry {
@@ -199,6 +400,17 @@ export interface RawMusicEntry {
   length: string;
   genre: string;
   bitrate: number;
+  matched_tags: {
+    field: string;
+    value: string;
+  }[];
+  fingerprint_segments: {
+    compared_path: string;
+    offset: number;
+    compared_offset: number;
+    duration: number;
+    score: number;
+  }[];
 }
 
 export interface MusicEntry extends BaseEntry<RawMusicEntry> {
@@ -210,6 +422,59 @@ export interface MusicEntry extends BaseEntry<RawMusicEntry> {
   year: string;
   length: string;
   bitrate: string;
+  matchInfo: string;
+}
+
+export interface RawSimilarNameEntry {
+  path: string;
+  size: number;
+  modified_date: number;
+  normalized_name: string;
+  score: number;
+}
+
+export interface SimilarNameEntry extends BaseEntry<RawSimilarNameEntry> {
+  score: string;
+  size: string;
+  fileName: string;
+  normalizedName: string;
+  modifiedDate: string;
+}
+
+export interface RawNumberedCopyEntry {
+  path: string;
+  size: number;
+  modified_date: number;
+  base_path: string;
+  kind: 'base' | 'numberedCopy' | 'conflictedCopy' | 'syncConflict';
+  status: 'base' | 'identical' | 'different' | 'orphan';
+}
+
+export interface NumberedCopyEntry extends BaseEntry<RawNumberedCopyEntry> {
+  status: string;
+  size: string;
+  fileName: string;
+  kind: string;
+  modifiedDate: string;
+}
+
+export interface RawNameCollisionEntry {
+  path: string;
+  is_dir: boolean;
+  size: number;
+  modified_date: number;
+  normalization_form: string;
+  kind: 'case' | 'normalization' | 'caseAndNormalization';
+  content: 'identical' | 'different' | 'notCompared';
+}
+
+export interface NameCollisionEntry extends BaseEntry<RawNameCollisionEntry> {
+  name: string;
+  kind: string;
+  content: string;
+  normalizationForm: string;
+  size: string;
+  modifiedDate: string;
 }
 
 export interface RawSymlinksFileEntry {
@@ -220,6 +485,9 @@ export interface RawSymlinksFileEntry {
     destination_path: string;
     type_of_error: string;
   };
+  candidates: string[];
+  proposed_target: string;
+  target_match: string;
 }
 
 export interface SymlinksFileEntry extends BaseEntry<RawSymlinksFileEntry> {
@@ -227,6 +495,7 @@ export interface SymlinksFileEntry extends BaseEntry<RawSymlinksFileEntry> {
   modifiedDate: string;
   destinationPath: string;
   typeOfError: string;
+  proposedTarget: string;
 }
 
 export interface RawBrokenEntry {
@@ -260,7 +529,7 @@ export interface BadFileEntry extends BaseEntry<RawBadFileEntry> {
   properExtension: string;
 }
 
-interface ScanResult<C extends ScanCmd, L> {
+interface ScanResult<C extends ScanCmd | 'find_similar_to_sample', L> {
   cmd: C;
   list: L;
   message: string;
@@ -270,16 +539,48 @@ export type TupleWithRefItem<T> = [T | null, T[]];
 
 export type AllScanResult =
   | ScanResult<'scan_duplicate_files', TupleWithRefItem<RawDuplicateEntry>[]>
-  | ScanResult<'scan_empty_folders', RawFolderOrTemporaryFileEntry[]>
+  | ScanResult<
+      'scan_duplicate_folders',
+      TupleWithRefItem<RawDuplicateFolderEntry>[]
+    >
+  | ScanResult<'scan_empty_folders', RawFolderEntry[]>
   | ScanResult<'scan_big_files', RawFileEntry[]>
-  | ScanResult<'scan_empty_files', RawFileEntry[]>
+  | ScanResult<'scan_orphaned_sidecars', RawSidecarEntry[]>
+  | ScanResult<'scan_largest_folders', RawFolderSizeEntry[]>
+  | ScanResult<'scan_disk_usage', RawDiskUsageNode[]>
+  | ScanResult<'scan_stale_files', RawStaleEntry[]>
+  | ScanResult<'scan_empty_files', RawEmptyFileEntry[]>
   | ScanResult<'scan_temporary_files', RawFolderOrTemporaryFileEntry[]>
   | ScanResult<'scan_similar_images', TupleWithRefItem<RawImagesEntry>[]>
   | ScanResult<'scan_similar_videos', TupleWithRefItem<RawVideosEntry>[]>
   | ScanResult<'scan_music_duplicates', TupleWithRefItem<RawMusicEntry>[]>
+  | ScanResult<'scan_similar_names', TupleWithRefItem<RawSimilarNameEntry>[]>
+  | ScanResult<
+      'scan_numbered_copies',
+      TupleWithRefItem<RawNumberedCopyEntry>[]
+    >
+  | ScanResult<
+      'scan_name_collisions',
+      TupleWithRefItem<RawNameCollisionEntry>[]
+    >
   | ScanResult<'scan_invalid_symlinks', RawSymlinksFileEntry[]>
   | ScanResult<'scan_broken_files', RawBrokenEntry[]>
-  | ScanResult<'scan_bad_extensions', RawBadFileEntry[]>;
+  | ScanResult<'scan_bad_extensions', RawBadFileEntry[]>
+  | ScanResult<'scan_name_portability', RawNameIssueEntry[]>
+  | ScanResult<'find_similar_to_sample', []>;
+
+export interface FfmpegExeInfo {
+  path: string | null;
+  version: string | null;
+}
+
+export interface CheckFfmpegResult {
+  ffmpeg: FfmpegExeInfo;
+  ffprobe: FfmpegExeInfo;
+  missingDecoders: string[];
+  usable: boolean;
+  reason: string | null;
+}
 
 export interface ImageInfo {
   base64: string;
@@ -1,23 +1,47 @@
 import { format } from 'date-fns';
 import { filesize } from 'filesize';
+import { t } from '~/i18n';
+import type { TranslationKeys } from '~/i18n/en';
 
 import type {
   BadFileEntry,
   BrokenEntry,
+  DiskUsageEntry,
   DuplicateEntry,
+  EmptyFileEntry,
+  DuplicateFolderEntry,
   FileEntry,
   FolderEntry,
+  FolderSizeEntry,
   ImagesEntry,
   MusicEntry,
+  NameCollisionEntry,
+  SidecarEntry,
+  NameIssue,
+  NameIssueEntry,
+  NumberedCopyEntry,
   RawBadFileEntry,
   RawBrokenEntry,
+  RawDiskUsageNode,
   RawDuplicateEntry,
+  RawEmptyFileEntry,
+  RawDuplicateFolderEntry,
   RawFileEntry,
+  RawFolderEntry,
   RawFolderOrTemporaryFileEntry,
+  RawFolderSizeEntry,
   RawImagesEntry,
   RawMusicEntry,
+  RawNameCollisionEntry,
+  RawSidecarEntry,
+  RawNameIssueEntry,
+  RawNumberedCopyEntry,
+  RawSimilarNameEntry,
+  RawStaleEntry,
   RawSymlinksFileEntry,
   RawVideosEntry,
+  SimilarNameEntry,
+  StaleEntry,
   SymlinksFileEntry,
   TemporaryFileEntry,
   TupleWithRefItem,
@@ -38,6 +62,23 @@ function fmtDate(v: number): string {
   return format(v * 1000, 'yyyy/MM/dd HH:mm:ss');
 }
 
+function fmtDuration(v: number): string {
+  const total = Math.round(v);
+  const hours = Math.floor(total / 3600);
+  const minutes = Math.floor((total % 3600) / 60);
+  const seconds = total % 60;
+  return [hours, minutes, seconds]
+    .map((n) => n.toString().padStart(2, '0'))
+    .join(':');
+}
+
+function fmtBitrate(v: number): string {
+  if (!v) {
+    return '';
+  }
+  return `${Math.round(v / 1000)} kbps`;
+}
+
 function isImage(fileName: string): boolean {
   const imageExtensions = [
     'bmp',
@@ -70,19 +111,37 @@ function sortTupleWithRefItemList<T extends TupleWithRefItem<{ size: number }>>(
   });
 }
 
+const archiveStatusLabelKeys: Record<
+  RawDuplicateEntry['archive_status'],
+  TranslationKeys | null
+> = {
+  none: null,
+  member: 'insideAnArchive',
+  redundant: 'redundantAlsoInAnArchive',
+};
+
+function fmtArchiveStatus(status: RawDuplicateEntry['archive_status']) {
+  const key = archiveStatusLabelKeys[status];
+  return key ? t(key) : '';
+}
+
+// Archive members can't be deleted on their own, they are shown like
+// reference items
 function convertDuplicateEntry(
   item: RawDuplicateEntry,
   isRef: boolean,
   groupId?: number,
 ): DuplicateEntry {
+  const isMember = item.archive_status === 'member';
   return {
     size: fmtFileSize(item.size),
     fileName: pathBaseName(item.path),
     path: item.path,
+    archiveStatus: fmtArchiveStatus(item.archive_status),
     modifiedDate: fmtDate(item.modified_date),
-    isRef,
+    isRef: isRef || isMember,
     hidden: false,
-    isImage: isImage(item.path),
+    isImage: !isMember && isImage(item.path),
     rawData: item,
     groupId,
   };
@@ -106,14 +165,51 @@ export function convertDuplicateEntries(
   });
 }
 
-export function convertFolderEntries(
-  list: RawFolderOrTemporaryFileEntry[],
-): FolderEntry[] {
+function convertDuplicateFolderEntry(
+  item: RawDuplicateFolderEntry,
+  isRef: boolean,
+  groupId?: number,
+): DuplicateFolderEntry {
+  return {
+    size: fmtFileSize(item.size),
+    folderName: pathBaseName(item.path),
+    path: item.path,
+    fileCount: item.file_count.toString(),
+    modifiedDate: fmtDate(item.modified_date),
+    isRef,
+    hidden: false,
+    rawData: item,
+    groupId,
+  };
+}
+
+export function convertDuplicateFolderEntries(
+  list: TupleWithRefItem<RawDuplicateFolderEntry>[],
+): DuplicateFolderEntry[][] {
+  sortTupleWithRefItemList(list);
+  let groupId = 1;
+  return list.map((tuple) => {
+    const [ref, items] = tuple;
+    const convertedItems = items.map((item) =>
+      convertDuplicateFolderEntry(item, false, groupId),
+    );
+    if (ref) {
+      convertedItems.unshift(convertDuplicateFolderEntry(ref, true));
+    }
+    groupId += 1;
+    return convertedItems;
+  });
+}
+
+export function convertFolderEntries(list: RawFolderEntry[]): FolderEntry[] {
   return list.map((item) => {
     return {
       folderName: pathBaseName(item.path),
       path: item.path,
       modifiedDate: fmtDate(item.modified_date),
+      junkFileCount: item.junk_file_count
+        ? item.junk_file_count.toString()
+        : '',
       rawData: item,
     };
   });
@@ -131,6 +227,105 @@ export function convertFileEntries(list: RawFileEntry[]): FileEntry[] {
   });
 }
 
+const emptyReasonLabels: Record<RawEmptyFileEntry['reason'], string> = {
+  zeroBytes: 'Zero bytes',
+  whitespace: 'Only whitespace',
+  bom: 'Only a BOM',
+  nul: 'Only NUL bytes',
+  emptyArchive: 'Empty archive',
+  emptyJson: 'Empty JSON',
+};
+
+export function convertEmptyFileEntries(
+  list: RawEmptyFileEntry[],
+): EmptyFileEntry[] {
+  return list.map((item) => {
+    return {
+      size: fmtFileSize(item.size),
+      fileName: pathBaseName(item.path),
+      path: item.path,
+      reason: emptyReasonLabels[item.reason],
+      modifiedDate: fmtDate(item.modified_date),
+      rawData: item,
+    };
+  });
+}
+
+export function convertSidecarEntries(
+  list: RawSidecarEntry[],
+): SidecarEntry[] {
+  return list.map((item) => {
+    return {
+      size: fmtFileSize(item.size),
+      fileName: pathBaseName(item.path),
+      path: item.path,
+      primaryExtensions: item.primary_extensions.join(', '),
+      modifiedDate: fmtDate(item.modified_date),
+      rawData: item,
+    };
+  });
+}
+
+export function convertFolderSizeEntries(
+  list: RawFolderSizeEntry[],
+): FolderSizeEntry[] {
+  return list.map((item) => {
+    return {
+      size: fmtFileSize(item.size),
+      directSize: fmtFileSize(item.direct_size),
+      fileCount: item.file_count.toString(),
+      directFileCount: item.direct_file_count.toString(),
+      folderName: pathBaseName(item.path),
+      path: item.path,
+      modifiedDate: fmtDate(item.modified_date),
+      rawData: item,
+    };
+  });
+}
+
+function convertDiskUsageNode(
+  item: RawDiskUsageNode,
+  total: number,
+): DiskUsageEntry {
+  const percent = total ? (item.size / total) * 100 : 0;
+  return {
+    name: item.name,
+    size: fmtFileSize(item.size),
+    percent: `${percent.toFixed(1)}%`,
+    fileCount: item.file_count.toString(),
+    path: item.path,
+    modifiedDate: fmtDate(item.modified_date),
+    rawData: item,
+  };
+}
+
+// Shows the children of a single node, and a single included directory is
+// opened right away. Percentages are relative to the shown level
+export function convertDiskUsageNodes(
+  list: RawDiskUsageNode[],
+): DiskUsageEntry[] {
+  if (list.length === 1 && list[0].is_dir) {
+    const [node] = list;
+    return node.children.map((item) => convertDiskUsageNode(item, node.size));
+  }
+  const total = list.reduce((sum, item) => sum + item.size, 0);
+  return list.map((item) => convertDiskUsageNode(item, total));
+}
+
+export function convertStaleEntries(list: RawStaleEntry[]): StaleEntry[] {
+  return list.map((item) => {
+    return {
+      name: pathBaseName(item.path),
+      size: fmtFileSize(item.size),
+      age: `${item.age_days} days`,
+      fileCount: item.file_count.toString(),
+      path: item.path,
+      lastUsedDate: fmtDate(item.last_used_date),
+      rawData: item,
+    };
+  });
+}
+
 export function convertTemporaryFileEntries(
   list: RawFolderOrTemporaryFileEntry[],
 ): TemporaryFileEntry[] {
@@ -156,6 +351,8 @@ function convertImagesEntry(
     modifiedDate: fmtDate(item.modified_date),
     similarity: item.similarity,
     dimensions: `${item.width}x${item.height}`,
+    distance: `${item.distance}/${item.hash_size * item.hash_size}`,
+    transform: item.transform,
     isRef,
     hidden: false,
     rawData: item,
@@ -191,6 +388,12 @@ function convertVideosEntry(
     fileName: pathBaseName(item.path),
     path: item.path,
     modifiedDate: fmtDate(item.modified_date),
+    duration: fmtDuration(item.duration),
+    dimensions: `${item.width}x${item.height}`,
+    codec: [item.video_codec, item.audio_codec].filter(Boolean).join(' / '),
+    bitrate: fmtBitrate(item.bitrate),
+    frameRate: item.frame_rate ? item.frame_rate.toFixed(2) : '',
+    container: item.container,
     isRef,
     hidden: false,
     rawData: item,
@@ -216,6 +419,15 @@ export function convertVideosEntries(
   });
 }
 
+function fmtMusicMatchInfo(item: RawMusicEntry): string {
+  const tags = item.matched_tags.map((tag) => `${tag.field}: ${tag.value}`);
+  const segments = item.fingerprint_segments.map(
+    (segment) =>
+      `${segment.offset.toFixed(1)}s~${pathBaseName(segment.compared_path)} ${segment.compared_offset.toFixed(1)}s (${segment.duration.toFixed(1)}s, ${segment.score.toFixed(2)})`,
+  );
+  return [...tags, ...segments].join('; ');
+}
+
 function convertMusicEntry(
   item: RawMusicEntry,
   isRef: boolean,
@@ -231,6 +443,7 @@ function convertMusicEntry(
     year: item.year,
     length: item.length,
     bitrate: item.bitrate.toString(),
+    matchInfo: fmtMusicMatchInfo(item),
     isRef,
     hidden: false,
     rawData: item,
@@ -256,6 +469,141 @@ export function convertMusicEntries(
   });
 }
 
+function convertSimilarNameEntry(
+  item: RawSimilarNameEntry,
+  isRef: boolean,
+  groupId?: number,
+): SimilarNameEntry {
+  return {
+    score: `${item.score}%`,
+    size: fmtFileSize(item.size),
+    fileName: pathBaseName(item.path),
+    path: item.path,
+    normalizedName: item.normalized_name,
+    modifiedDate: fmtDate(item.modified_date),
+    isRef,
+    hidden: false,
+    rawData: item,
+    groupId,
+  };
+}
+
+// Groups are already ranked by name similarity, they are not sorted by size
+export function convertSimilarNameEntries(
+  list: TupleWithRefItem<RawSimilarNameEntry>[],
+): SimilarNameEntry[][] {
+  let groupId = 1;
+  return list.map((tuple) => {
+    const [ref, items] = tuple;
+    const convertedItems = items.map((item) =>
+      convertSimilarNameEntry(item, false, groupId),
+    );
+    if (ref) {
+      convertedItems.unshift(convertSimilarNameEntry(ref, true));
+    }
+    groupId += 1;
+    return convertedItems;
+  });
+}
+
+const copyStatusLabels: Record<RawNumberedCopyEntry['status'], string> = {
+  base: 'Base file',
+  identical: 'Identical',
+  different: 'Conflict',
+  orphan: 'Orphan',
+};
+
+const copyKindLabels: Record<RawNumberedCopyEntry['kind'], string> = {
+  base: '',
+  numberedCopy: 'Numbered copy',
+  conflictedCopy: 'Conflicted copy',
+  syncConflict: 'Sync conflict',
+};
+
+function convertNumberedCopyEntry(
+  item: RawNumberedCopyEntry,
+  isRef: boolean,
+  groupId?: number,
+): NumberedCopyEntry {
+  return {
+    status: copyStatusLabels[item.status],
+    size: fmtFileSize(item.size),
+    fileName: pathBaseName(item.path),
+    path: item.path,
+    kind: copyKindLabels[item.kind],
+    modifiedDate: fmtDate(item.modified_date),
+    isRef,
+    hidden: false,
+    rawData: item,
+    groupId,
+  };
+}
+
+export function convertNumberedCopyEntries(
+  list: TupleWithRefItem<RawNumberedCopyEntry>[],
+): NumberedCopyEntry[][] {
+  let groupId = 1;
+  return list.map((tuple) => {
+    const [ref, items] = tuple;
+    const convertedItems = items.map((item) =>
+      convertNumberedCopyEntry(item, false, groupId),
+    );
+    if (ref) {
+      convertedItems.unshift(convertNumberedCopyEntry(ref, true));
+    }
+    groupId += 1;
+    return convertedItems;
+  });
+}
+
+const collisionKindLabels: Record<RawNameCollisionEntry['kind'], string> = {
+  case: 'Case',
+  normalization: 'Unicode normalization',
+  caseAndNormalization: 'Case and Unicode normalization',
+};
+
+const collisionContentLabels: Record<
+  RawNameCollisionEntry['content'],
+  string
+> = {
+  identical: 'Identical',
+  different: 'Different',
+  notCompared: 'Not compared',
+};
+
+function convertNameCollisionEntry(
+  item: RawNameCollisionEntry,
+  groupId: number,
+): NameCollisionEntry {
+  return {
+    name: pathBaseName(item.path),
+    kind: collisionKindLabels[item.kind],
+    content: collisionContentLabels[item.content],
+    normalizationForm: item.normalization_form,
+    size: item.is_dir ? '' : fmtFileSize(item.size),
+    path: item.path,
+    modifiedDate: fmtDate(item.modified_date),
+    isRef: false,
+    hidden: false,
+    rawData: item,
+    groupId,
+  };
+}
+
+export function convertNameCollisionEntries(
+  list: TupleWithRefItem<RawNameCollisionEntry>[],
+): NameCollisionEntry[][] {
+  let groupId = 1;
+  return list.map((tuple) => {
+    const [_, items] = tuple;
+    const convertedItems = items.map((item) =>
+      convertNameCollisionEntry(item, groupId),
+    );
+    groupId += 1;
+    return convertedItems;
+  });
+}
+
 export function convertSymlinksFileEntries(
   list: RawSymlinksFileEntry[],
 ): SymlinksFileEntry[] {
@@ -266,9 +614,24 @@ export function convertSymlinksFileEntries(
     if (v === 'NonExistentFile') {
       return 'Non existent file';
     }
+    if (v === 'OutsideTree') {
+      return 'Outside scanned tree';
+    }
     return v;
   };
 
+  // Several candidates are only counted, the link can't be repaired
+  // automatically then
+  const displayProposedTarget = (item: RawSymlinksFileEntry) => {
+    if (item.proposed_target) {
+      return `${item.proposed_target} (${item.target_match})`;
+    }
+    if (item.candidates.length > 1) {
+      return `${item.candidates.length} candidates`;
+    }
+    return '';
+  };
+
   return list.map((item) => {
     return {
       path: item.path,
@@ -276,6 +639,7 @@ export function convertSymlinksFileEntries(
       modifiedDate: fmtDate(item.modified_date),
       destinationPath: item.symlink_info.destination_path,
       typeOfError: displayTypeOfError(item.symlink_info.type_of_error),
+      proposedTarget: displayProposedTarget(item),
       rawData: item,
     };
   });
@@ -307,3 +671,29 @@ export function convertBadFileEntries(list: RawBadFileEntry[]): BadFileEntry[] {
     };
   });
 }
+
+const nameIssueLabels: Record<NameIssue, string> = {
+  invalidChars: 'Invalid characters',
+  controlChars: 'Control characters',
+  trailingDotOrSpace: 'Trailing dot or space',
+  reservedName: 'Reserved name',
+  nameTooLong: 'Name too long',
+  pathTooLong: 'Path too long',
+  fileTooLarge: 'File too large',
+};
+
+export function convertNameIssueEntries(
+  list: RawNameIssueEntry[],
+): NameIssueEntry[] {
+  return list.map((item) => {
+    return {
+      name: pathBaseName(item.path),
+      issues: item.issues.map((issue) => nameIssueLabels[issue]).join(', '),
+      proposedName: item.proposed_name,
+      size: item.is_dir ? '' : fmtFileSize(item.size),
+      path: item.path,
+      modifiedDate: fmtDate(item.modified_date),
+      rawData: item,
+    };
+  });
+}
@@ -65,10 +65,28 @@ export function baseCompareFn(
   const { id, desc } = columnSort;
   let comparison = 0;
 
-  if (['size', 'modified_date', 'bitrate'].includes(id)) {
+  if (
+    [
+      'size',
+      'modified_date',
+      'bitrate',
+      'duration',
+      'frame_rate',
+      'file_count',
+      'direct_size',
+      'direct_file_count',
+      'score',
+      'age_days',
+      'last_used_date',
+    ].includes(id)
+  ) {
     comparison = a.rawData[id] - b.rawData[id];
   } else if (id === 'length') {
     comparison = toSeconds(a[id]) - toSeconds(b[id]);
+  } else if (id === 'percent') {
+    comparison = a.rawData.size - b.rawData.size;
+  } else if (id === 'distance') {
+    comparison = a.rawData.distance - b.rawData.distance;
   } else if (id === 'dimensions') {
     const dimensionsA = a.rawData.width * a.rawData.height;
     const dimensionsB = b.rawData.width * b.rawData.height;
@@ -106,6 +124,24 @@ export function insertHiddenRows<T extends BaseEntry>(groups: T[][]): T[] {
   return result;
 }
 
+// Duplicate folders are checked against their fingerprint from the scan
+// before they are deleted or moved
+export function getFolderFingerprints(
+  tableData: BaseEntry[] | BaseEntry[][],
+  paths: string[],
+): Record<string, string> {
+  const pathsSet = new Set(paths);
+  const fingerprints: Record<string, string> = {};
+  const items = is2DArray(tableData) ? tableData.flat() : tableData;
+  for (const item of items) {
+    const { fingerprint } = item.rawData;
+    if (typeof fingerprint === 'string' && pathsSet.has(item.path)) {
+      fingerprints[item.path] = fingerprint;
+    }
+  }
+  return fingerprints;
+}
+
 export function removeTableDataItemsByPaths(
   tableData: BaseEntry[] | BaseEntry[][],
   paths: string[],
@@ -11,6 +11,7 @@ import { Tools } from '~/consts';
 import { useListenEffect, useT } from '~/hooks';
 import { ipc } from '~/ipc';
 import {
+  getFolderFingerprints,
   getRowSelectionKeys,
   removeTableDataItemsByPaths,
 } from '~/utils/table-helper';
@@ -31,7 +32,7 @@ export function DeleteFiles({ disabled }: DeleteFilesProps) {
   const setLogs = useSetAtom(logsAtom);
   const settings = useAtomValue(settingsAtom);
   const currentTool = useAtomValue(currentToolAtom);
-  const setTableData = useSetAtom(currentTableDataAtom);
+  const [tableData, setTableData] = useAtom(currentTableDataAtom);
   const [rowSelection, setRowSelection] = useAtom(currentRowSelectionAtom);
 
   useListenEffect('delete-files-result', (result: DeleteFilesResult) => {
@@ -69,6 +70,13 @@ export function DeleteFiles({ disabled }: DeleteFilesProps) {
       paths,
       moveDeletedFilesToTrash: settings.moveDeletedFilesToTrash,
       isEmptyFoldersTool: currentTool === Tools.EmptyFolders,
+      deleteSidecars: settings.deleteSidecars,
+      sidecarMap: settings.sidecarMap,
+      ignorableFiles: settings.emptyFoldersSubIgnorableFiles,
+      ignoreZeroByteFiles: settings.emptyFoldersSubIgnoreZeroByteFiles,
+      excludedDirectories: settings.excludedDirectories,
+      excludedItems: settings.excludedItems,
+      folderFingerprints: getFolderFingerprints(tableData, paths),
     });
   };
 
@@ -0,0 +1,85 @@
+import { useAtom, useAtomValue, useSetAtom } from 'jotai';
+import { ArrowUpIcon, FolderInputIcon } from 'lucide-react';
+import { logsAtom } from '~/atom/primitive';
+import { currentRowSelectionAtom, currentTableDataAtom } from '~/atom/table';
+import { OperationButton } from '~/components';
+import { useT } from '~/hooks';
+import { ipc } from '~/ipc';
+import type { DiskUsageEntry, RawDiskUsageNode } from '~/types';
+import { convertDiskUsageNodes } from '~/utils/convert';
+import { getRowSelectionKeys } from '~/utils/table-helper';
+
+interface DiskUsageNavigationProps {
+  disabled: boolean;
+}
+
+function pathDirName(path: string): string | null {
+  const match = path.match(/^(.*[^\\/])[\\/]+[^\\/]+[\\/]?$/);
+  return match ? match[1] : null;
+}
+
+// Subtrees are loaded from the tree kept in memory after a scan, the table
+// always shows the children of one folder
+export function DiskUsageNavigation({ disabled }: DiskUsageNavigationProps) {
+  const [tableData, setTableData] = useAtom(currentTableDataAtom);
+  const [rowSelection, setRowSelection] = useAtom(currentRowSelectionAtom);
+  const setLogs = useSetAtom(logsAtom);
+  const t = useT();
+
+  const entries = tableData as DiskUsageEntry[];
+  const selected = getRowSelectionKeys(rowSelection);
+  const selectedEntry =
+    selected.length === 1
+      ? entries.find((entry) => entry.path === selected[0])
+      : undefined;
+  const currentDir = entries.length ? pathDirName(entries[0].path) : null;
+
+  const showNodes = (nodes: RawDiskUsageNode[]) => {
+    setTableData(convertDiskUsageNodes(nodes));
+    setRowSelection({});
+  };
+
+  const handleOpen = async () => {
+    if (!selectedEntry?.rawData.has_children) {
+      return;
+    }
+    try {
+      showNodes(await ipc.getDiskUsageTree(selectedEntry.path, 1));
+    } catch (err) {
+      setLogs(String(err));
+    }
+  };
+
+  const handleUp = async () => {
+    if (!currentDir) {
+      return;
+    }
+    const parentDir = pathDirName(currentDir);
+    try {
+      showNodes(await ipc.getDiskUsageTree(parentDir, 1));
+    } catch {
+      // Above the included directories, go back to them
+      try {
+        showNodes(await ipc.getDiskUsageTree(null, 1));
+      } catch (err) {
+        setLogs(String(err));
+      }
+    }
+  };
+
+  return (
+    <>
+      <OperationButton
+        disabled={disabled || !selectedEntry?.rawData.has_children}
+        onClick={handleOpen}
+      >
+        <FolderInputIcon />
+        {t('openFolder')}
+      </OperationButton>
+      <OperationButton disabled={disabled || !currentDir} onClick={handleUp}>
+        <ArrowUpIcon />
+        {t('parentFolder')}
+      </OperationButton>
+    </>
+  );
+}
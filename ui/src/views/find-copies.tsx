@@ -0,0 +1,48 @@
+import { open as openFileDialog } from '@tauri-apps/plugin-dialog';
+import { useAtom, useAtomValue } from 'jotai';
+import { CopyIcon } from 'lucide-react';
+import { currentToolAtom, progressAtom } from '~/atom/primitive';
+import { settingsAtom } from '~/atom/settings';
+import { currentRowSelectionAtom } from '~/atom/table';
+import { OperationButton } from '~/components';
+import { useT } from '~/hooks';
+import { ipc } from '~/ipc';
+import { getRowSelectionKeys } from '~/utils/table-helper';
+
+interface FindCopiesProps {
+  disabled: boolean;
+}
+
+export function FindCopies({ disabled }: FindCopiesProps) {
+  const currentTool = useAtomValue(currentToolAtom);
+  const settings = useAtomValue(settingsAtom);
+  const rowSelection = useAtomValue(currentRowSelectionAtom);
+  const [progress, setProgress] = useAtom(progressAtom);
+  const t = useT();
+
+  const handleClick = async () => {
+    if (progress.tool) {
+      return;
+    }
+    const selected = getRowSelectionKeys(rowSelection);
+    const path =
+      selected.length === 1
+        ? selected[0]
+        : await openFileDialog({ multiple: false, directory: false });
+    if (!path) {
+      return;
+    }
+    setProgress({ ...progress, tool: currentTool });
+    ipc.findFileCopies(path, settings);
+  };
+
+  return (
+    <OperationButton
+      disabled={disabled || !settings.includedDirectories.length}
+      onClick={handleClick}
+    >
+      <CopyIcon />
+      {t('findCopies')}
+    </OperationButton>
+  );
+}
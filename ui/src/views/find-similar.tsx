@@ -0,0 +1,51 @@
+import { open as openFileDialog } from '@tauri-apps/plugin-dialog';
+import { useAtom, useAtomValue } from 'jotai';
+import { FileSearchIcon } from 'lucide-react';
+import { currentToolAtom, progressAtom } from '~/atom/primitive';
+import { settingsAtom } from '~/atom/settings';
+import { OperationButton } from '~/components';
+import { SampleFileExtensions } from '~/consts';
+import { useT } from '~/hooks';
+import { ipc } from '~/ipc';
+
+interface FindSimilarProps {
+  disabled: boolean;
+}
+
+export function FindSimilar({ disabled }: FindSimilarProps) {
+  const currentTool = useAtomValue(currentToolAtom);
+  const settings = useAtomValue(settingsAtom);
+  const [progress, setProgress] = useAtom(progressAtom);
+  const t = useT();
+
+  const handleClick = async () => {
+    if (progress.tool) {
+      return;
+    }
+    const samplePath = await openFileDialog({
+      multiple: false,
+      directory: false,
+      filters: [
+        {
+          name: t('sampleFile'),
+          extensions: SampleFileExtensions[currentTool],
+        },
+      ],
+    });
+    if (!samplePath) {
+      return;
+    }
+    setProgress({ ...progress, tool: currentTool });
+    ipc.findSimilarToSample(samplePath, settings);
+  };
+
+  return (
+    <OperationButton
+      disabled={disabled || !settings.includedDirectories.length}
+      onClick={handleClick}
+    >
+      <FileSearchIcon />
+      {t('findSimilar')}
+    </OperationButton>
+  );
+}
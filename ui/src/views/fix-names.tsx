@@ -0,0 +1,107 @@
+import { useAtom, useSetAtom } from 'jotai';
+import { WandSparklesIcon } from 'lucide-react';
+import { useState } from 'react';
+import { Trans } from 'react-i18next';
+import { logsAtom } from '~/atom/primitive';
+import { currentRowSelectionAtom, currentTableDataAtom } from '~/atom/table';
+import { OperationButton } from '~/components';
+import { AlertDialog } from '~/components/alert-dialog';
+import { useListenEffect, useT } from '~/hooks';
+import { ipc } from '~/ipc';
+import type { NameIssueEntry } from '~/types';
+import {
+  getRowSelectionKeys,
+  removeTableDataItemsByPaths,
+} from '~/utils/table-helper';
+
+interface FixNamesProps {
+  disabled: boolean;
+}
+
+interface FixNamesResult {
+  successPaths: string[];
+  errors: string[];
+}
+
+export function FixNames({ disabled }: FixNamesProps) {
+  const [open, setOpen] = useState(false);
+  const [loading, setLoading] = useState(false);
+  const setLogs = useSetAtom(logsAtom);
+  const [tableData, setTableData] = useAtom(currentTableDataAtom);
+  const [rowSelection, setRowSelection] = useAtom(currentRowSelectionAtom);
+  const t = useT();
+
+  useListenEffect('fix-names-result', (result: FixNamesResult) => {
+    setLoading(false);
+    setOpen(false);
+    const { successPaths, errors } = result;
+    setLogs(
+      [`Successfully renamed ${successPaths.length} items`, ...errors].join(
+        '\n',
+      ),
+    );
+    if (successPaths.length) {
+      setTableData((oldTableData) =>
+        removeTableDataItemsByPaths(oldTableData, successPaths),
+      );
+    }
+    setRowSelection({});
+  });
+
+  // Items whose issues a rename can't fix have no proposed name
+  const pathsSet = new Set(getRowSelectionKeys(rowSelection));
+  const selectedItems = (tableData as NameIssueEntry[]).filter(
+    (item) => pathsSet.has(item.path) && item.rawData.proposed_name,
+  );
+
+  const handleOpenChange = (v: boolean) => {
+    if (loading) {
+      return;
+    }
+    setOpen(v);
+  };
+
+  const handleOk = () => {
+    if (loading) {
+      return;
+    }
+    setLoading(true);
+    ipc.fixNames({
+      items: selectedItems.map((item) => ({
+        path: item.path,
+        name: item.rawData.proposed_name,
+      })),
+    });
+  };
+
+  return (
+    <>
+      <OperationButton
+        disabled={disabled || !selectedItems.length}
+        onClick={() => setOpen(true)}
+      >
+        <WandSparklesIcon />
+        {t('fixNames')}
+      </OperationButton>
+      <AlertDialog
+        open={open}
+        onOpenChange={handleOpenChange}
+        title={t('renamingItems')}
+        okLoading={loading}
+        description={
+          <span>
+            <Trans
+              i18nKey="fixNamesConfirm"
+              values={{ length: selectedItems.length }}
+            >
+              This will rename selected
+              <span className="text-primary p-1" /> items to their proposed
+              names. Are you want to continue?
+            </Trans>
+          </span>
+        }
+        onOk={handleOk}
+      />
+    </>
+  );
+}
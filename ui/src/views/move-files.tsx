@@ -11,6 +11,7 @@ import { Form, FormItem } from '~/components/form';
 import { useListenEffect, useT } from '~/hooks';
 import { ipc } from '~/ipc';
 import {
+  getFolderFingerprints,
   getRowSelectionKeys,
   removeTableDataItemsByPaths,
 } from '~/utils/table-helper';
@@ -45,7 +46,7 @@ export function MoveFiles({ disabled }: MoveFilesProps) {
   const [loading, setLoading] = useState(false);
   const [openFileDialogLoading, setOpenFileDialogLoading] = useState(false);
   const setLogs = useSetAtom(logsAtom);
-  const setTableData = useSetAtom(currentTableDataAtom);
+  const [tableData, setTableData] = useAtom(currentTableDataAtom);
   const [rowSelection, setRowSelection] = useAtom(currentRowSelectionAtom);
   const t = useT();
 
@@ -94,7 +95,12 @@ export function MoveFiles({ disabled }: MoveFilesProps) {
       return;
     }
     setLoading(true);
-    ipc.moveFiles({ paths, destination, ...options });
+    ipc.moveFiles({
+      paths,
+      destination,
+      ...options,
+      folderFingerprints: getFolderFingerprints(tableData, paths),
+    });
   };
 
   return (
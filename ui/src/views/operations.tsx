@@ -3,12 +3,23 @@ import { currentToolAtom, progressAtom } from '~/atom/primitive';
 import { currentTableDataAtom } from '~/atom/table';
 import { Tools } from '~/consts';
 import { DeleteFiles } from './delete-files';
+import { DiskUsageNavigation } from './disk-usage-navigation';
+import { FindCopies } from './find-copies';
+import { FindSimilar } from './find-similar';
+import { FixNames } from './fix-names';
 import { MoveFiles } from './move-files';
 import { RenameExt } from './rename-ext';
+import { RepairSymlinks } from './repair-symlinks';
 import { SelectionMenu } from './row-selection-menu';
 import { SaveResult } from './save-result';
 import { ScanButton } from './scan-button';
 
+const sampleSearchTools = new Set<string>([
+  Tools.SimilarImages,
+  Tools.SimilarVideos,
+  Tools.MusicDuplicates,
+]);
+
 export function Operations() {
   const progress = useAtomValue(progressAtom);
   const tableData = useAtomValue(currentTableDataAtom);
@@ -19,11 +30,26 @@ export function Operations() {
   return (
     <div className="flex gap-1">
       <ScanButton />
+      {currentTool === Tools.DuplicateFiles && (
+        <FindCopies disabled={!!progress.tool} />
+      )}
+      {sampleSearchTools.has(currentTool) && (
+        <FindSimilar disabled={!!progress.tool} />
+      )}
+      {currentTool === Tools.DiskUsage && (
+        <DiskUsageNavigation disabled={disabled} />
+      )}
       <SelectionMenu disabled={disabled} />
       <MoveFiles disabled={disabled} />
       <DeleteFiles disabled={disabled} />
       <SaveResult disabled={disabled} />
       {currentTool === Tools.BadExtensions && <RenameExt disabled={disabled} />}
+      {currentTool === Tools.NamePortability && (
+        <FixNames disabled={disabled} />
+      )}
+      {currentTool === Tools.InvalidSymlinks && (
+        <RepairSymlinks disabled={disabled} />
+      )}
     </div>
   );
 }
@@ -1,14 +1,16 @@
-import { useAtom, useSetAtom } from 'jotai';
-import { TextCursorInputIcon } from 'lucide-react';
+import { useAtom, useAtomValue, useSetAtom } from 'jotai';
+import { TextCursorInputIcon, WandSparklesIcon } from 'lucide-react';
 import { useState } from 'react';
 import { Trans } from 'react-i18next';
 import { logsAtom } from '~/atom/primitive';
+import { settingsAtom } from '~/atom/settings';
 import { currentRowSelectionAtom, currentTableDataAtom } from '~/atom/table';
-import { OperationButton } from '~/components';
+import { OperationButton, Select, Switch } from '~/components';
 import { AlertDialog } from '~/components/alert-dialog';
+import { Form, FormItem } from '~/components/form';
+import { RenameConflictStrategy } from '~/consts';
 import { useListenEffect, useT } from '~/hooks';
 import { ipc } from '~/ipc';
-import { is2DArray } from '~/utils/common';
 import {
   getRowSelectionKeys,
   removeTableDataItemsByPaths,
@@ -18,35 +20,80 @@ interface RenameExtProps {
   disabled: boolean;
 }
 
-interface RenameExtResult {
+interface Options {
+  conflictStrategy: string;
+  removeIdentical: boolean;
+}
+
+interface Conflict {
+  path: string;
+  target: string;
+  action: 'suffixed' | 'skipped' | 'removedIdentical';
+  newPath: string;
+}
+
+interface FixBadExtensionsResult {
   successPaths: string[];
   errors: string[];
+  renamed: { path: string; newPath: string }[];
+  conflicts: Conflict[];
+  equivalentPaths: string[];
+}
+
+function getDefaultOptions(): Options {
+  return {
+    conflictStrategy: RenameConflictStrategy.Skip,
+    removeIdentical: false,
+  };
+}
+
+function fmtConflict(conflict: Conflict): string {
+  const { path, target, action, newPath } = conflict;
+  if (action === 'suffixed') {
+    return `\`${path}\` Renamed to \`${newPath}\`, \`${target}\` already exists`;
+  }
+  if (action === 'removedIdentical') {
+    return `\`${path}\` Removed, \`${target}\` already exists with identical content`;
+  }
+  return `\`${path}\` Skipped, \`${target}\` already exists`;
 }
 
 export function RenameExt({ disabled }: RenameExtProps) {
+  const [options, setOptions] = useState<Options>(getDefaultOptions);
+  const [fixAll, setFixAll] = useState(false);
   const [open, setOpen] = useState(false);
   const [loading, setLoading] = useState(false);
   const setLogs = useSetAtom(logsAtom);
+  const settings = useAtomValue(settingsAtom);
   const [tableData, setTableData] = useAtom(currentTableDataAtom);
   const [rowSelection, setRowSelection] = useAtom(currentRowSelectionAtom);
   const t = useT();
 
-  useListenEffect('rename-ext-result', (result: RenameExtResult) => {
-    setLoading(false);
-    setOpen(false);
-    const { successPaths, errors } = result;
-    setLogs(
-      [`Successfully renamed ${successPaths.length} files`, ...errors].join(
-        '\n',
-      ),
-    );
-    if (successPaths.length) {
-      setTableData((oldTableData) =>
-        removeTableDataItemsByPaths(oldTableData, successPaths),
+  useListenEffect(
+    'fix-bad-extensions-result',
+    (result: FixBadExtensionsResult) => {
+      setLoading(false);
+      setOpen(false);
+      setOptions(getDefaultOptions());
+      const { successPaths, errors, renamed, conflicts, equivalentPaths } =
+        result;
+      setLogs(
+        [
+          `Successfully renamed ${successPaths.length} files`,
+          ...renamed.map((item) => `\`${item.path}\` -> \`${item.newPath}\``),
+          ...conflicts.map(fmtConflict),
+          `Kept ${equivalentPaths.length} files with an equivalent extension`,
+          ...errors,
+        ].join('\n'),
       );
-    }
-    setRowSelection({});
-  });
+      if (successPaths.length) {
+        setTableData((oldTableData) =>
+          removeTableDataItemsByPaths(oldTableData, successPaths),
+        );
+      }
+      setRowSelection({});
+    },
+  );
 
   const paths = getRowSelectionKeys(rowSelection);
 
@@ -54,26 +101,26 @@ export function RenameExt({ disabled }: RenameExtProps) {
     if (loading) {
       return;
     }
+    setOptions(getDefaultOptions());
     setOpen(v);
   };
 
+  const handleOpen = (all: boolean) => {
+    setFixAll(all);
+    setOpen(true);
+  };
+
+  // The recommended extensions are taken from the last scan on the backend
   const handleOk = () => {
     if (loading) {
       return;
     }
     setLoading(true);
-    const pathsSet = new Set(paths);
-    const selectedItems = is2DArray(tableData)
-      ? tableData.flatMap((group) =>
-          group.filter((item) => pathsSet.has(item.path)),
-        )
-      : tableData.filter((item) => pathsSet.has(item.path));
-
-    ipc.renameExt({
-      items: selectedItems.map((item) => ({
-        path: item.path,
-        ext: item.rawData.proper_extension,
-      })),
+    ipc.fixBadExtensions({
+      paths: fixAll ? null : paths,
+      equivalentExtensions: settings.badExtensionsSubEquivalentExtensions,
+      ...options,
+      moveDeletedFilesToTrash: settings.moveDeletedFilesToTrash,
     });
   };
 
@@ -81,27 +128,73 @@ export function RenameExt({ disabled }: RenameExtProps) {
     <>
       <OperationButton
         disabled={disabled || !paths.length}
-        onClick={() => setOpen(true)}
+        onClick={() => handleOpen(false)}
       >
         <TextCursorInputIcon />
         {t('rename')}
       </OperationButton>
+      <OperationButton disabled={disabled} onClick={() => handleOpen(true)}>
+        <WandSparklesIcon />
+        {t('fixAll')}
+      </OperationButton>
       <AlertDialog
         open={open}
         onOpenChange={handleOpenChange}
         title={t('renamingFiles')}
         okLoading={loading}
         description={
-          <span>
-            <Trans i18nKey="renameConfirm" values={{ length: paths.length }}>
-              This will rename extensions of selected
-              <span className="text-primary p-1" /> files to more proper. Are
-              you want to continue?
-            </Trans>
-          </span>
+          fixAll ? (
+            <span>
+              <Trans
+                i18nKey="fixAllExtensionsConfirm"
+                values={{ length: tableData.length }}
+              >
+                This will rename extensions of all
+                <span className="text-primary p-1" /> found files to the
+                recommended ones, equivalent extensions are kept. Are you want
+                to continue?
+              </Trans>
+            </span>
+          ) : (
+            <span>
+              <Trans i18nKey="renameConfirm" values={{ length: paths.length }}>
+                This will rename extensions of selected
+                <span className="text-primary p-1" /> files to more proper. Are
+                you want to continue?
+              </Trans>
+            </span>
+          )
         }
         onOk={handleOk}
-      />
+      >
+        <Form
+          value={options}
+          onChange={(v) => setOptions({ ...options, ...v })}
+        >
+          <FormItem
+            name="conflictStrategy"
+            label={t('whenNameIsTaken')}
+            comp="select"
+          >
+            <Select
+              options={[
+                { label: t('skip'), value: RenameConflictStrategy.Skip },
+                {
+                  label: t('addNumberSuffix'),
+                  value: RenameConflictStrategy.Suffix,
+                },
+              ]}
+            />
+          </FormItem>
+          <FormItem
+            name="removeIdentical"
+            label={t('removeIfIdentical')}
+            comp="switch"
+          >
+            <Switch />
+          </FormItem>
+        </Form>
+      </AlertDialog>
     </>
   );
 }
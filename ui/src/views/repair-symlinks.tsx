@@ -0,0 +1,141 @@
+import { useAtom, useSetAtom } from 'jotai';
+import { LinkIcon } from 'lucide-react';
+import { useState } from 'react';
+import { Trans } from 'react-i18next';
+import { logsAtom } from '~/atom/primitive';
+import { currentRowSelectionAtom, currentTableDataAtom } from '~/atom/table';
+import { OperationButton, Switch } from '~/components';
+import { AlertDialog } from '~/components/alert-dialog';
+import { Form, FormItem } from '~/components/form';
+import { useListenEffect, useT } from '~/hooks';
+import { ipc } from '~/ipc';
+import type { SymlinksFileEntry } from '~/types';
+import {
+  getRowSelectionKeys,
+  removeTableDataItemsByPaths,
+} from '~/utils/table-helper';
+
+interface RepairSymlinksProps {
+  disabled: boolean;
+}
+
+interface Options {
+  makeRelative: boolean;
+}
+
+interface RepairSymlinksResult {
+  successPaths: string[];
+  errors: string[];
+}
+
+function getDefaultOptions(): Options {
+  return {
+    makeRelative: false,
+  };
+}
+
+export function RepairSymlinks({ disabled }: RepairSymlinksProps) {
+  const [options, setOptions] = useState<Options>(getDefaultOptions);
+  const [open, setOpen] = useState(false);
+  const [loading, setLoading] = useState(false);
+  const setLogs = useSetAtom(logsAtom);
+  const [tableData, setTableData] = useAtom(currentTableDataAtom);
+  const [rowSelection, setRowSelection] = useAtom(currentRowSelectionAtom);
+  const t = useT();
+
+  useListenEffect('repair-symlinks-result', (result: RepairSymlinksResult) => {
+    setLoading(false);
+    setOpen(false);
+    setOptions(getDefaultOptions());
+    const { successPaths, errors } = result;
+    setLogs(
+      [`Successfully repaired ${successPaths.length} symlinks`, ...errors].join(
+        '\n',
+      ),
+    );
+    if (successPaths.length) {
+      setTableData((oldTableData) =>
+        removeTableDataItemsByPaths(oldTableData, successPaths),
+      );
+    }
+    setRowSelection({});
+  });
+
+  // Without a proposed target only working links can be converted to
+  // relative ones, they keep their current target
+  const pathsSet = new Set(getRowSelectionKeys(rowSelection));
+  const selectedItems = (tableData as SymlinksFileEntry[]).filter(
+    (item) =>
+      pathsSet.has(item.path) &&
+      (item.rawData.proposed_target ||
+        (options.makeRelative &&
+          item.rawData.symlink_info.type_of_error === 'OutsideTree')),
+  );
+
+  const handleOpenChange = (v: boolean) => {
+    if (loading) {
+      return;
+    }
+    setOptions(getDefaultOptions());
+    setOpen(v);
+  };
+
+  const handleOk = () => {
+    if (loading || !selectedItems.length) {
+      return;
+    }
+    setLoading(true);
+    ipc.repairSymlinks({
+      items: selectedItems.map((item) => ({
+        path: item.path,
+        target: item.rawData.proposed_target,
+      })),
+      ...options,
+    });
+  };
+
+  return (
+    <>
+      <OperationButton
+        disabled={disabled || !pathsSet.size}
+        onClick={() => setOpen(true)}
+      >
+        <LinkIcon />
+        {t('repairSymlinks')}
+      </OperationButton>
+      <AlertDialog
+        open={open}
+        onOpenChange={handleOpenChange}
+        title={t('repairingSymlinks')}
+        okLoading={loading}
+        description={
+          <span>
+            <Trans
+              i18nKey="repairSymlinksConfirm"
+              values={{ length: selectedItems.length }}
+            >
+              This will point selected
+              <span className="text-primary p-1" /> symlinks to their proposed
+              targets, all of them or none are changed. Are you want to
+              continue?
+            </Trans>
+          </span>
+        }
+        onOk={handleOk}
+      >
+        <Form
+          value={options}
+          onChange={(v) => setOptions({ ...options, ...v })}
+        >
+          <FormItem
+            name="makeRelative"
+            label={t('makeLinksRelative')}
+            comp="switch"
+          >
+            <Switch />
+          </FormItem>
+        </Form>
+      </AlertDialog>
+    </>
+  );
+}
@@ -21,9 +21,13 @@ import { getPathsFromEntries, getRowSelectionKeys } from '~/utils/table-helper';
 
 const toolsWithExtraSelection = new Set<string>([
   Tools.DuplicateFiles,
+  Tools.DuplicateFolders,
   Tools.SimilarImages,
   Tools.SimilarVideos,
   Tools.MusicDuplicates,
+  Tools.SimilarNames,
+  Tools.NumberedCopies,
+  Tools.NameCollisions,
 ]);
 
 export function SelectionMenu({ disabled }: { disabled: boolean }) {
@@ -68,7 +72,8 @@ export function SelectionMenu({ disabled }: { disabled: boolean }) {
         </OperationButton>
       </DropdownMenuTrigger>
       <DropdownMenuContent side="top">
-        {currentTool === Tools.SimilarImages && (
+        {(currentTool === Tools.SimilarImages ||
+          currentTool === Tools.SimilarVideos) && (
           <>
             <DropdownMenuItem
               onClick={() => handleExtraSelecttion('resolution', 'asc')}
@@ -15,11 +15,21 @@ import type { AllScanResult, ProgressData, ScanCmd } from '~/types';
 import {
   convertBadFileEntries,
   convertBorkenEntries,
+  convertDiskUsageNodes,
   convertDuplicateEntries,
+  convertEmptyFileEntries,
+  convertDuplicateFolderEntries,
   convertFileEntries,
   convertFolderEntries,
+  convertFolderSizeEntries,
   convertImagesEntries,
   convertMusicEntries,
+  convertNameCollisionEntries,
+  convertNameIssueEntries,
+  convertNumberedCopyEntries,
+  convertSidecarEntries,
+  convertSimilarNameEntries,
+  convertStaleEntries,
   convertSymlinksFileEntries,
   convertTemporaryFileEntries,
   convertVideosEntries,
@@ -27,30 +37,49 @@ import {
 
 const scanCmdMap: Record<string, ScanCmd> = {
   [Tools.DuplicateFiles]: 'scan_duplicate_files',
+  [Tools.DuplicateFolders]: 'scan_duplicate_folders',
   [Tools.EmptyFolders]: 'scan_empty_folders',
   [Tools.BigFiles]: 'scan_big_files',
+  [Tools.LargestFolders]: 'scan_largest_folders',
+  [Tools.DiskUsage]: 'scan_disk_usage',
+  [Tools.StaleFiles]: 'scan_stale_files',
   [Tools.EmptyFiles]: 'scan_empty_files',
   [Tools.TemporaryFiles]: 'scan_temporary_files',
+  [Tools.OrphanedSidecars]: 'scan_orphaned_sidecars',
   [Tools.SimilarImages]: 'scan_similar_images',
   [Tools.SimilarVideos]: 'scan_similar_videos',
   [Tools.MusicDuplicates]: 'scan_music_duplicates',
+  [Tools.SimilarNames]: 'scan_similar_names',
+  [Tools.NumberedCopies]: 'scan_numbered_copies',
+  [Tools.NameCollisions]: 'scan_name_collisions',
   [Tools.InvalidSymlinks]: 'scan_invalid_symlinks',
   [Tools.BrokenFiles]: 'scan_broken_files',
   [Tools.BadExtensions]: 'scan_bad_extensions',
+  [Tools.NamePortability]: 'scan_name_portability',
 };
 
-const convertFnMap: Record<ScanCmd, (v: any[]) => any[]> = {
+const convertFnMap: Record<AllScanResult['cmd'], (v: any[]) => any[]> = {
   scan_duplicate_files: convertDuplicateEntries,
+  scan_duplicate_folders: convertDuplicateFolderEntries,
   scan_empty_folders: convertFolderEntries,
   scan_big_files: convertFileEntries,
-  scan_empty_files: convertFileEntries,
+  scan_largest_folders: convertFolderSizeEntries,
+  scan_disk_usage: convertDiskUsageNodes,
+  scan_stale_files: convertStaleEntries,
+  scan_empty_files: convertEmptyFileEntries,
   scan_temporary_files: convertTemporaryFileEntries,
   scan_similar_images: convertImagesEntries,
   scan_similar_videos: convertVideosEntries,
   scan_music_duplicates: convertMusicEntries,
+  scan_similar_names: convertSimilarNameEntries,
+  scan_numbered_copies: convertNumberedCopyEntries,
+  scan_orphaned_sidecars: convertSidecarEntries,
+  scan_name_collisions: convertNameCollisionEntries,
   scan_invalid_symlinks: convertSymlinksFileEntries,
   scan_broken_files: convertBorkenEntries,
   scan_bad_extensions: convertBadFileEntries,
+  scan_name_portability: convertNameIssueEntries,
+  find_similar_to_sample: (v) => v,
 };
 
 export function ScanButton() {
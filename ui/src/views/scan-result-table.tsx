@@ -1,5 +1,6 @@
 import type { ColumnDef, Row } from '@tanstack/react-table';
 import { useAtom, useAtomValue } from 'jotai';
+import { FileIcon, FolderIcon } from 'lucide-react';
 import { currentToolAtom } from '~/atom/primitive';
 import { settingsAtom } from '~/atom/settings';
 import {
@@ -13,11 +14,21 @@ import { Tools } from '~/consts';
 import type {
   BadFileEntry,
   BrokenEntry,
+  DiskUsageEntry,
   DuplicateEntry,
+  EmptyFileEntry,
+  DuplicateFolderEntry,
   FileEntry,
   FolderEntry,
+  FolderSizeEntry,
   ImagesEntry,
   MusicEntry,
+  NameCollisionEntry,
+  SidecarEntry,
+  NameIssueEntry,
+  NumberedCopyEntry,
+  SimilarNameEntry,
+  StaleEntry,
   SymlinksFileEntry,
   TemporaryFileEntry,
   ToolsValues,
@@ -32,6 +43,7 @@ const ColumnMinSizeMap = {
   path: 102,
   modifiedDate: 163,
   folderName: 154,
+  fileCount: 120,
   similarity: 133,
   dimensions: 149,
   title: 101,
@@ -39,12 +51,37 @@ const ColumnMinSizeMap = {
   year: 101,
   bitrate: 116,
   length: 118,
+  duration: 118,
+  codec: 110,
+  frameRate: 130,
+  container: 130,
+  distance: 118,
+  transform: 130,
+  matchInfo: 140,
   symlinkName: 164,
   symlinkPath: 157,
   destinationPath: 181,
   typeOfError: 158,
+  proposedTarget: 170,
+  ignorableFiles: 150,
   currentExtension: 190,
   properExtension: 184,
+  score: 100,
+  normalizedName: 180,
+  status: 110,
+  kind: 110,
+  percent: 100,
+  age: 100,
+  lastUsedDate: 163,
+  directSize: 120,
+  directFileCount: 150,
+  issues: 120,
+  proposedName: 160,
+  content: 120,
+  normalizationForm: 190,
+  reason: 140,
+  primaryExtensions: 160,
+  archiveStatus: 190,
 } as const;
 
 const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
@@ -69,6 +106,47 @@ const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
       minSize: ColumnMinSizeMap.path,
       cell: PathCell,
     },
+    {
+      accessorKey: 'archiveStatus',
+      header: 'archiveStatus',
+      size: ColumnMinSizeMap.archiveStatus,
+      minSize: ColumnMinSizeMap.archiveStatus,
+    },
+    {
+      accessorKey: 'modifiedDate',
+      header: 'modifiedDate',
+      size: ColumnMinSizeMap.modifiedDate,
+      minSize: ColumnMinSizeMap.modifiedDate,
+      id: 'modified_date',
+    },
+  ]),
+  [Tools.DuplicateFolders]: createColumns<DuplicateFolderEntry>([
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: 110,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'folderName',
+      header: 'folderName',
+      size: 180,
+      minSize: ColumnMinSizeMap.folderName,
+    },
+    {
+      accessorKey: 'fileCount',
+      header: 'fileCount',
+      size: ColumnMinSizeMap.fileCount,
+      minSize: ColumnMinSizeMap.fileCount,
+      id: 'file_count',
+    },
+    {
+      accessorKey: 'path',
+      header: 'path',
+      size: 320,
+      minSize: ColumnMinSizeMap.path,
+      cell: PathCell,
+    },
     {
       accessorKey: 'modifiedDate',
       header: 'modifiedDate',
@@ -87,9 +165,15 @@ const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
     {
       accessorKey: 'path',
       header: 'path',
-      size: 430,
+      size: 330,
       minSize: ColumnMinSizeMap.path,
     },
+    {
+      accessorKey: 'junkFileCount',
+      header: 'ignorableFiles',
+      size: ColumnMinSizeMap.ignorableFiles,
+      minSize: ColumnMinSizeMap.ignorableFiles,
+    },
     {
       accessorKey: 'modifiedDate',
       header: 'modifiedDate',
@@ -125,17 +209,196 @@ const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
       id: 'modified_date',
     },
   ]),
-  [Tools.EmptyFiles]: createColumns<FileEntry>([
+  [Tools.LargestFolders]: createColumns<FolderSizeEntry>([
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: 110,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'directSize',
+      header: 'directSize',
+      size: ColumnMinSizeMap.directSize,
+      minSize: ColumnMinSizeMap.directSize,
+      id: 'direct_size',
+    },
+    {
+      accessorKey: 'fileCount',
+      header: 'fileCount',
+      size: ColumnMinSizeMap.fileCount,
+      minSize: ColumnMinSizeMap.fileCount,
+      id: 'file_count',
+    },
+    {
+      accessorKey: 'directFileCount',
+      header: 'directFileCount',
+      size: ColumnMinSizeMap.directFileCount,
+      minSize: ColumnMinSizeMap.directFileCount,
+      id: 'direct_file_count',
+    },
+    {
+      accessorKey: 'folderName',
+      header: 'folderName',
+      size: 180,
+      minSize: ColumnMinSizeMap.folderName,
+    },
+    {
+      accessorKey: 'path',
+      header: 'path',
+      size: 320,
+      minSize: ColumnMinSizeMap.path,
+      cell: PathCell,
+    },
+    {
+      accessorKey: 'modifiedDate',
+      header: 'modifiedDate',
+      size: ColumnMinSizeMap.modifiedDate,
+      minSize: ColumnMinSizeMap.modifiedDate,
+      id: 'modified_date',
+    },
+  ]),
+  [Tools.DiskUsage]: createColumns<DiskUsageEntry>([
+    {
+      accessorKey: 'name',
+      header: 'name',
+      size: 220,
+      minSize: ColumnMinSizeMap.fileName,
+      cell: DiskUsageNameCell,
+    },
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: 110,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'percent',
+      header: 'percent',
+      size: ColumnMinSizeMap.percent,
+      minSize: ColumnMinSizeMap.percent,
+    },
+    {
+      accessorKey: 'fileCount',
+      header: 'fileCount',
+      size: ColumnMinSizeMap.fileCount,
+      minSize: ColumnMinSizeMap.fileCount,
+      id: 'file_count',
+    },
+    {
+      accessorKey: 'path',
+      header: 'path',
+      size: 320,
+      minSize: ColumnMinSizeMap.path,
+      cell: PathCell,
+    },
+    {
+      accessorKey: 'modifiedDate',
+      header: 'modifiedDate',
+      size: ColumnMinSizeMap.modifiedDate,
+      minSize: ColumnMinSizeMap.modifiedDate,
+      id: 'modified_date',
+    },
+  ]),
+  [Tools.StaleFiles]: createColumns<StaleEntry>([
+    {
+      accessorKey: 'name',
+      header: 'name',
+      size: 220,
+      minSize: ColumnMinSizeMap.fileName,
+      cell: StaleNameCell,
+    },
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: 110,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'age',
+      header: 'age',
+      size: 110,
+      minSize: ColumnMinSizeMap.age,
+      id: 'age_days',
+    },
+    {
+      accessorKey: 'fileCount',
+      header: 'fileCount',
+      size: ColumnMinSizeMap.fileCount,
+      minSize: ColumnMinSizeMap.fileCount,
+      id: 'file_count',
+    },
+    {
+      accessorKey: 'path',
+      header: 'path',
+      size: 320,
+      minSize: ColumnMinSizeMap.path,
+      cell: PathCell,
+    },
+    {
+      accessorKey: 'lastUsedDate',
+      header: 'lastUsedDate',
+      size: ColumnMinSizeMap.lastUsedDate,
+      minSize: ColumnMinSizeMap.lastUsedDate,
+      id: 'last_used_date',
+    },
+  ]),
+  [Tools.EmptyFiles]: createColumns<EmptyFileEntry>([
+    {
+      accessorKey: 'fileName',
+      header: 'fileName',
+      size: 180,
+      minSize: ColumnMinSizeMap.fileName,
+    },
+    {
+      accessorKey: 'reason',
+      header: 'reason',
+      size: ColumnMinSizeMap.reason,
+      minSize: ColumnMinSizeMap.reason,
+    },
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: ColumnMinSizeMap.size,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'path',
+      header: 'path',
+      size: 330,
+      minSize: ColumnMinSizeMap.path,
+    },
+    {
+      accessorKey: 'modifiedDate',
+      header: 'modifiedDate',
+      size: ColumnMinSizeMap.modifiedDate,
+      minSize: ColumnMinSizeMap.modifiedDate,
+      id: 'modified_date',
+    },
+  ]),
+  [Tools.OrphanedSidecars]: createColumns<SidecarEntry>([
     {
       accessorKey: 'fileName',
       header: 'fileName',
       size: 180,
       minSize: ColumnMinSizeMap.fileName,
     },
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: ColumnMinSizeMap.size,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'primaryExtensions',
+      header: 'primaryExtensions',
+      size: 180,
+      minSize: ColumnMinSizeMap.primaryExtensions,
+    },
     {
       accessorKey: 'path',
       header: 'path',
-      size: 430,
+      size: 330,
       minSize: ColumnMinSizeMap.path,
     },
     {
@@ -186,6 +449,18 @@ const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
       size: ColumnMinSizeMap.dimensions,
       minSize: ColumnMinSizeMap.dimensions,
     },
+    {
+      accessorKey: 'distance',
+      header: 'distance',
+      size: ColumnMinSizeMap.distance,
+      minSize: ColumnMinSizeMap.distance,
+    },
+    {
+      accessorKey: 'transform',
+      header: 'transform',
+      size: ColumnMinSizeMap.transform,
+      minSize: ColumnMinSizeMap.transform,
+    },
     {
       accessorKey: 'fileName',
       header: 'fileName',
@@ -221,6 +496,43 @@ const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
       size: 180,
       minSize: ColumnMinSizeMap.fileName,
     },
+    {
+      accessorKey: 'duration',
+      header: 'duration',
+      size: ColumnMinSizeMap.duration,
+      minSize: ColumnMinSizeMap.duration,
+    },
+    {
+      accessorKey: 'dimensions',
+      header: 'dimensions',
+      size: ColumnMinSizeMap.dimensions,
+      minSize: ColumnMinSizeMap.dimensions,
+    },
+    {
+      accessorKey: 'codec',
+      header: 'codec',
+      size: ColumnMinSizeMap.codec,
+      minSize: ColumnMinSizeMap.codec,
+    },
+    {
+      accessorKey: 'bitrate',
+      header: 'bitrate',
+      size: ColumnMinSizeMap.bitrate,
+      minSize: ColumnMinSizeMap.bitrate,
+    },
+    {
+      accessorKey: 'frameRate',
+      header: 'frameRate',
+      size: ColumnMinSizeMap.frameRate,
+      minSize: ColumnMinSizeMap.frameRate,
+      id: 'frame_rate',
+    },
+    {
+      accessorKey: 'container',
+      header: 'container',
+      size: ColumnMinSizeMap.container,
+      minSize: ColumnMinSizeMap.container,
+    },
     {
       accessorKey: 'path',
       header: 'path',
@@ -279,6 +591,12 @@ const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
       size: ColumnMinSizeMap.length,
       minSize: ColumnMinSizeMap.length,
     },
+    {
+      accessorKey: 'matchInfo',
+      header: 'matchInfo',
+      size: 200,
+      minSize: ColumnMinSizeMap.matchInfo,
+    },
     {
       accessorKey: 'path',
       header: 'path',
@@ -294,6 +612,133 @@ const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
       id: 'modified_date',
     },
   ]),
+  [Tools.SimilarNames]: createColumns<SimilarNameEntry>([
+    {
+      accessorKey: 'score',
+      header: 'score',
+      size: ColumnMinSizeMap.score,
+      minSize: ColumnMinSizeMap.score,
+    },
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: 110,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'fileName',
+      header: 'fileName',
+      size: 180,
+      minSize: ColumnMinSizeMap.fileName,
+    },
+    {
+      accessorKey: 'normalizedName',
+      header: 'normalizedName',
+      size: 180,
+      minSize: ColumnMinSizeMap.normalizedName,
+    },
+    {
+      accessorKey: 'path',
+      header: 'path',
+      size: 320,
+      minSize: ColumnMinSizeMap.path,
+      cell: PathCell,
+    },
+    {
+      accessorKey: 'modifiedDate',
+      header: 'modifiedDate',
+      size: ColumnMinSizeMap.modifiedDate,
+      minSize: ColumnMinSizeMap.modifiedDate,
+      id: 'modified_date',
+    },
+  ]),
+  [Tools.NumberedCopies]: createColumns<NumberedCopyEntry>([
+    {
+      accessorKey: 'status',
+      header: 'status',
+      size: 120,
+      minSize: ColumnMinSizeMap.status,
+    },
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: 110,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'fileName',
+      header: 'fileName',
+      size: 180,
+      minSize: ColumnMinSizeMap.fileName,
+    },
+    {
+      accessorKey: 'kind',
+      header: 'kind',
+      size: 140,
+      minSize: ColumnMinSizeMap.kind,
+    },
+    {
+      accessorKey: 'path',
+      header: 'path',
+      size: 320,
+      minSize: ColumnMinSizeMap.path,
+      cell: PathCell,
+    },
+    {
+      accessorKey: 'modifiedDate',
+      header: 'modifiedDate',
+      size: ColumnMinSizeMap.modifiedDate,
+      minSize: ColumnMinSizeMap.modifiedDate,
+      id: 'modified_date',
+    },
+  ]),
+  [Tools.NameCollisions]: createColumns<NameCollisionEntry>([
+    {
+      accessorKey: 'name',
+      header: 'name',
+      size: 180,
+      minSize: ColumnMinSizeMap.fileName,
+      cell: NameCollisionNameCell,
+    },
+    {
+      accessorKey: 'kind',
+      header: 'kind',
+      size: 160,
+      minSize: ColumnMinSizeMap.kind,
+    },
+    {
+      accessorKey: 'content',
+      header: 'content',
+      size: ColumnMinSizeMap.content,
+      minSize: ColumnMinSizeMap.content,
+    },
+    {
+      accessorKey: 'normalizationForm',
+      header: 'normalizationForm',
+      size: ColumnMinSizeMap.normalizationForm,
+      minSize: ColumnMinSizeMap.normalizationForm,
+    },
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: 110,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'path',
+      header: 'path',
+      size: 320,
+      minSize: ColumnMinSizeMap.path,
+      cell: PathCell,
+    },
+    {
+      accessorKey: 'modifiedDate',
+      header: 'modifiedDate',
+      size: ColumnMinSizeMap.modifiedDate,
+      minSize: ColumnMinSizeMap.modifiedDate,
+      id: 'modified_date',
+    },
+  ]),
   [Tools.InvalidSymlinks]: createColumns<SymlinksFileEntry>(
     [
       {
@@ -320,6 +765,12 @@ const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
         size: ColumnMinSizeMap.typeOfError,
         minSize: ColumnMinSizeMap.typeOfError,
       },
+      {
+        accessorKey: 'proposedTarget',
+        header: 'proposedTarget',
+        size: 220,
+        minSize: ColumnMinSizeMap.proposedTarget,
+      },
       {
         accessorKey: 'modifiedDate',
         header: 'modifiedDate',
@@ -396,6 +847,47 @@ const ColumnsMap: Record<ToolsValues, ColumnDef<any>[]> = {
       id: 'modified_date',
     },
   ]),
+  [Tools.NamePortability]: createColumns<NameIssueEntry>([
+    {
+      accessorKey: 'name',
+      header: 'name',
+      size: 180,
+      minSize: ColumnMinSizeMap.fileName,
+      cell: NameIssueNameCell,
+    },
+    {
+      accessorKey: 'issues',
+      header: 'issues',
+      size: 200,
+      minSize: ColumnMinSizeMap.issues,
+    },
+    {
+      accessorKey: 'proposedName',
+      header: 'proposedName',
+      size: 180,
+      minSize: ColumnMinSizeMap.proposedName,
+    },
+    {
+      accessorKey: 'size',
+      header: 'size',
+      size: 110,
+      minSize: ColumnMinSizeMap.size,
+    },
+    {
+      accessorKey: 'path',
+      header: 'path',
+      size: 320,
+      minSize: ColumnMinSizeMap.path,
+      cell: PathCell,
+    },
+    {
+      accessorKey: 'modifiedDate',
+      header: 'modifiedDate',
+      size: ColumnMinSizeMap.modifiedDate,
+      minSize: ColumnMinSizeMap.modifiedDate,
+      id: 'modified_date',
+    },
+  ]),
 };
 
 export function ScanResultTable({ className }: { className?: string }) {
@@ -463,3 +955,55 @@ function SimilarImagesFileNameCell({ row }: { row: Row<ImagesEntry> }) {
 
   return fileName;
 }
+
+function NameCollisionNameCell({ row }: { row: Row<NameCollisionEntry> }) {
+  const { hidden, name, rawData } = row.original;
+  const Icon = rawData.is_dir ? FolderIcon : FileIcon;
+
+  if (hidden) {
+    return null;
+  }
+
+  return (
+    <div className="flex items-center gap-1 truncate">
+      <Icon className="size-4 flex-shrink-0" />
+      {name}
+    </div>
+  );
+}
+
+function NameIssueNameCell({ row }: { row: Row<NameIssueEntry> }) {
+  const { name, rawData } = row.original;
+  const Icon = rawData.is_dir ? FolderIcon : FileIcon;
+
+  return (
+    <div className="flex items-center gap-1 truncate">
+      <Icon className="size-4 flex-shrink-0" />
+      {name}
+    </div>
+  );
+}
+
+function StaleNameCell({ row }: { row: Row<StaleEntry> }) {
+  const { name, rawData } = row.original;
+  const Icon = rawData.is_dir ? FolderIcon : FileIcon;
+
+  return (
+    <div className="flex items-center gap-1 truncate">
+      <Icon className="size-4 flex-shrink-0" />
+      {name}
+    </div>
+  );
+}
+
+function DiskUsageNameCell({ row }: { row: Row<DiskUsageEntry> }) {
+  const { name, rawData } = row.original;
+  const Icon = rawData.is_dir ? FolderIcon : FileIcon;
+
+  return (
+    <div className="flex items-center gap-1 truncate">
+      <Icon className="size-4 flex-shrink-0" />
+      {name}
+    </div>
+  );
+}
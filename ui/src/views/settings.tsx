@@ -20,6 +20,7 @@ import { setLanguageAtom } from '~/atom/language';
 import { initCurrentPresetAtom } from '~/atom/preset';
 import {
   languageAtom,
+  logsAtom,
   platformSettingsAtom,
   themeAtom,
 } from '~/atom/primitive';
@@ -49,7 +50,9 @@ import {
 } from '~/components/shadcn/dialog';
 import { Tabs, TabsList, TabsTrigger } from '~/components/shadcn/tabs';
 import { DARK_MODE_MEDIA, Languages, MAXIMUM_FILE_SIZE, Theme } from '~/consts';
-import { useOnceEffect, useT } from '~/hooks';
+import { useListenEffect, useOnceEffect, useT } from '~/hooks';
+import { ipc } from '~/ipc';
+import type { CheckFfmpegResult } from '~/types';
 import { cn } from '~/utils/cn';
 import { PresetSelect } from './preset-select';
 
@@ -290,6 +293,13 @@ function AdvancedSettings({
             >
               <Switch />
             </FormItem>
+            <FormItem
+              name="deleteSidecars"
+              label={t('deleteSidecarsWithFiles')}
+              comp="switch"
+            >
+              <Switch />
+            </FormItem>
             <FormItem
               name="threadNumber"
               label={
@@ -395,6 +405,29 @@ function AdvancedSettings({
           </SectionContent>
           <SectionHeader icon={VideoIcon}>{t('similarVideos')}</SectionHeader>
           <SectionContent>
+            <FormItem
+              name="ffmpegPath"
+              label={t('ffmpegPath')}
+              comp="textarea"
+            >
+              <Textarea
+                rows={1}
+                className="w-[60%] dark:bg-background"
+                placeholder={t('detectAutomatically')}
+              />
+            </FormItem>
+            <FormItem
+              name="ffprobePath"
+              label={t('ffprobePath')}
+              comp="textarea"
+            >
+              <Textarea
+                rows={1}
+                className="w-[60%] dark:bg-background"
+                placeholder={t('detectAutomatically')}
+              />
+            </FormItem>
+            <CheckFfmpeg />
             <FormItem
               name="similarVideosHideHardLinks"
               label={t('hideHardLinks')}
@@ -426,6 +459,47 @@ function AdvancedSettings({
   );
 }
 
+function CheckFfmpeg() {
+  const t = useT();
+  const settings = useAtomValue(settingsAtom);
+  const setLogs = useSetAtom(logsAtom);
+  const [loading, setLoading] = useState(false);
+
+  useListenEffect('check-ffmpeg-result', (result: CheckFfmpegResult) => {
+    setLoading(false);
+    const lines = [
+      `ffmpeg: ${result.ffmpeg.path ?? '-'} (${result.ffmpeg.version ?? '-'})`,
+      `ffprobe: ${result.ffprobe.path ?? '-'} (${result.ffprobe.version ?? '-'})`,
+    ];
+    if (result.reason) {
+      lines.push(result.reason);
+      toastError(t('videoScanningUnavailable'), result.reason);
+    }
+    setLogs(lines.join('\n'));
+  });
+
+  const handleCheck = () => {
+    setLoading(true);
+    ipc.checkFfmpeg(settings).catch((err) => {
+      setLoading(false);
+      toastError(t('opreationFailed'), err);
+    });
+  };
+
+  return (
+    <RawFormItem label={t('checkFfmpeg')}>
+      <Button
+        variant="outline"
+        size="sm"
+        disabled={loading}
+        onClick={handleCheck}
+      >
+        {t('check')}
+      </Button>
+    </RawFormItem>
+  );
+}
+
 function SectionContent({
   children,
   className,
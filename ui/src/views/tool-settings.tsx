@@ -1,7 +1,8 @@
 import { useAtom, useAtomValue } from 'jotai';
-import { currentToolAtom } from '~/atom/primitive';
+import { currentToolAtom, progressAtom } from '~/atom/primitive';
 import { settingsAtom } from '~/atom/settings';
 import {
+  Button,
   InputNumber,
   LabelCheckbox,
   ScrollArea,
@@ -9,35 +10,51 @@ import {
   Slider,
   SliderValue,
   Switch,
+  Textarea,
 } from '~/components';
 import { Form, FormItem, RawFormItem } from '~/components/form';
 import {
   BigFilesSearchMode,
   DuplicatesAvailableHashType,
   DuplicatesCheckMethod,
+  InvalidSymlinksTargetMatch,
+  LargestFoldersRankBy,
+  NamePortabilityTarget,
   SimilarImagesHashAlgorithm,
   SimilarImagesResizeAlgorithm,
   SimilarMusicAudioCheckType,
+  SimilarVideosCropDetect,
+  StaleFilesTimeKind,
   Tools,
 } from '~/consts';
 import { useT } from '~/hooks';
 import type { TranslationKeys } from '~/i18n/en';
+import { ipc } from '~/ipc';
 
 const toolsWithoutSettings = new Set<string>([
-  Tools.EmptyFolders,
-  Tools.EmptyFiles,
+  Tools.DiskUsage,
   Tools.TemporaryFiles,
-  Tools.InvalidSymlinks,
-  Tools.BadExtensions,
 ]);
 
 const settingsCompMap: Record<string, () => React.JSX.Element> = {
   [Tools.DuplicateFiles]: DuplicateFilesSettings,
+  [Tools.DuplicateFolders]: DuplicateFoldersSettings,
+  [Tools.EmptyFolders]: EmptyFoldersSettings,
+  [Tools.EmptyFiles]: EmptyFilesSettings,
+  [Tools.OrphanedSidecars]: OrphanedSidecarsSettings,
   [Tools.BigFiles]: BigFilesSettings,
+  [Tools.LargestFolders]: LargestFoldersSettings,
+  [Tools.StaleFiles]: StaleFilesSettings,
+  [Tools.NamePortability]: NamePortabilitySettings,
+  [Tools.InvalidSymlinks]: InvalidSymlinksSettings,
   [Tools.SimilarImages]: SimilarImagesSettings,
   [Tools.SimilarVideos]: SimilarVideosSettings,
   [Tools.MusicDuplicates]: MusicDuplicatesSettings,
+  [Tools.SimilarNames]: SimilarNamesSettings,
+  [Tools.NumberedCopies]: HashTypeSettings,
+  [Tools.NameCollisions]: HashTypeSettings,
   [Tools.BrokenFiles]: BrokenFilesSettings,
+  [Tools.BadExtensions]: BadExtensionsSettings,
 };
 
 export function ToolSettings() {
@@ -80,6 +97,7 @@ function Fallback() {
 
 function DuplicateFilesSettings() {
   const t = useT();
+  const settings = useAtomValue(settingsAtom);
 
   return (
     <>
@@ -122,10 +140,138 @@ function DuplicateFilesSettings() {
       >
         <Switch />
       </FormItem>
+      {settings.duplicatesSubCheckMethod === DuplicatesCheckMethod.Hash && (
+        <FormItem
+          name="duplicatesSubScanArchives"
+          label={t('scanInsideArchives')}
+          comp="switch"
+        >
+          <Switch />
+        </FormItem>
+      )}
+    </>
+  );
+}
+
+function DuplicateFoldersSettings() {
+  const t = useT();
+
+  return (
+    <>
+      <FormItem
+        name="duplicatesSubAvailableHashType"
+        label={t('hashType')}
+        comp="select"
+      >
+        <Select
+          className="w-[75%]"
+          options={[
+            { label: 'Blake3', value: DuplicatesAvailableHashType.Blake3 },
+            { label: 'CRC32', value: DuplicatesAvailableHashType.CRC32 },
+            { label: 'XXH3', value: DuplicatesAvailableHashType.XXH3 },
+          ]}
+        />
+      </FormItem>
+      <FormItem
+        name="duplicateFoldersSubIncludeSubsets"
+        label={t('includeSubsetFolders')}
+        comp="switch"
+      >
+        <Switch />
+      </FormItem>
     </>
   );
 }
 
+function EmptyFilesSettings() {
+  const t = useT();
+  const settings = useAtomValue(settingsAtom);
+
+  return (
+    <>
+      <FormItem
+        name="emptyFilesSubEffectivelyEmpty"
+        label={t('effectivelyEmpty')}
+        comp="switch"
+      >
+        <Switch />
+      </FormItem>
+      {settings.emptyFilesSubEffectivelyEmpty && (
+        <RawFormItem label={t('contentToTreatAsEmpty')}>
+          <div className="flex flex-wrap gap-4">
+            <FormItem name="emptyFilesSubWhitespace" comp="checkbox">
+              <LabelCheckbox label={t('whitespace')} />
+            </FormItem>
+            <FormItem name="emptyFilesSubBom" comp="checkbox">
+              <LabelCheckbox label={t('bom')} />
+            </FormItem>
+            <FormItem name="emptyFilesSubNul" comp="checkbox">
+              <LabelCheckbox label={t('nulBytes')} />
+            </FormItem>
+            <FormItem name="emptyFilesSubEmptyContainer" comp="checkbox">
+              <LabelCheckbox label={t('emptyZipOrJson')} />
+            </FormItem>
+          </div>
+        </RawFormItem>
+      )}
+    </>
+  );
+}
+
+function EmptyFoldersSettings() {
+  const t = useT();
+
+  return (
+    <>
+      <FormItem
+        name="emptyFoldersSubIgnorableFiles"
+        label={t('ignorableFiles')}
+        comp="textarea"
+      >
+        <Textarea rows={3} className="font-mono" />
+      </FormItem>
+      <FormItem
+        name="emptyFoldersSubIgnoreZeroByteFiles"
+        label={t('ignoreZeroByteFiles')}
+        comp="switch"
+      >
+        <Switch />
+      </FormItem>
+    </>
+  );
+}
+
+function OrphanedSidecarsSettings() {
+  const t = useT();
+
+  return (
+    <FormItem name="sidecarMap" label={t('sidecarMap')} comp="textarea">
+      <Textarea rows={6} className="font-mono" />
+    </FormItem>
+  );
+}
+
+function HashTypeSettings() {
+  const t = useT();
+
+  return (
+    <FormItem
+      name="duplicatesSubAvailableHashType"
+      label={t('hashType')}
+      comp="select"
+    >
+      <Select
+        className="w-[75%]"
+        options={[
+          { label: 'Blake3', value: DuplicatesAvailableHashType.Blake3 },
+          { label: 'CRC32', value: DuplicatesAvailableHashType.CRC32 },
+          { label: 'XXH3', value: DuplicatesAvailableHashType.XXH3 },
+        ]}
+      />
+    </FormItem>
+  );
+}
+
 function BigFilesSettings() {
   const t = useT();
 
@@ -155,10 +301,150 @@ function BigFilesSettings() {
   );
 }
 
+function LargestFoldersSettings() {
+  const t = useT();
+
+  return (
+    <>
+      <FormItem
+        name="biggestFilesSubMethod"
+        label={t('checkedFolders')}
+        comp="select"
+      >
+        <Select
+          className="w-[75%]"
+          options={[
+            { label: t('biggest'), value: BigFilesSearchMode.BiggestFiles },
+            { label: t('smallest'), value: BigFilesSearchMode.SmallestFiles },
+          ]}
+        />
+      </FormItem>
+      <FormItem
+        name="largestFoldersSubRankBy"
+        label={t('rankBy')}
+        comp="select"
+      >
+        <Select
+          className="w-[75%]"
+          options={[
+            { label: t('size'), value: LargestFoldersRankBy.RecursiveSize },
+            { label: t('directSize'), value: LargestFoldersRankBy.DirectSize },
+            { label: t('fileCount'), value: LargestFoldersRankBy.FileCount },
+          ]}
+        />
+      </FormItem>
+      <FormItem
+        name="biggestFilesSubNumberOfFiles"
+        label={t('numberOfLines')}
+        comp="input-number"
+      >
+        <InputNumber className="w-[75%]" minValue={1} />
+      </FormItem>
+    </>
+  );
+}
+
+function StaleFilesSettings() {
+  const t = useT();
+
+  return (
+    <>
+      <FormItem
+        name="staleFilesSubTimeKind"
+        label={t('timeToCheck')}
+        comp="select"
+      >
+        <Select
+          className="w-[75%]"
+          options={[
+            { label: t('modifiedTime'), value: StaleFilesTimeKind.Modified },
+            { label: t('accessedTime'), value: StaleFilesTimeKind.Accessed },
+            { label: t('changedTime'), value: StaleFilesTimeKind.Changed },
+          ]}
+        />
+      </FormItem>
+      <FormItem
+        name="staleFilesSubDays"
+        label={t('olderThanDays')}
+        comp="input-number"
+      >
+        <InputNumber className="w-[75%]" minValue={1} />
+      </FormItem>
+    </>
+  );
+}
+
+function NamePortabilitySettings() {
+  const t = useT();
+
+  return (
+    <FormItem
+      name="namePortabilitySubTarget"
+      label={t('targetFilesystem')}
+      comp="select"
+    >
+      <Select
+        className="w-[75%]"
+        options={[
+          { label: 'FAT32', value: NamePortabilityTarget.FAT },
+          { label: 'exFAT', value: NamePortabilityTarget.ExFAT },
+          { label: 'NTFS', value: NamePortabilityTarget.NTFS },
+          { label: 'SMB', value: NamePortabilityTarget.SMB },
+        ]}
+      />
+    </FormItem>
+  );
+}
+
+function InvalidSymlinksSettings() {
+  const t = useT();
+
+  return (
+    <>
+      <FormItem
+        name="invalidSymlinksSubTargetMatch"
+        label={t('matchNewTargetsBy')}
+        comp="select"
+      >
+        <Select
+          className="w-[75%]"
+          options={[
+            { label: t('name'), value: InvalidSymlinksTargetMatch.Name },
+            {
+              label: t('nameAndSize'),
+              value: InvalidSymlinksTargetMatch.Size,
+            },
+            {
+              label: t('nameAndHash'),
+              value: InvalidSymlinksTargetMatch.Hash,
+            },
+          ]}
+        />
+      </FormItem>
+      <FormItem
+        name="invalidSymlinksSubOutsideTree"
+        label={t('reportLinksOutsideTree')}
+        comp="switch"
+      >
+        <Switch />
+      </FormItem>
+    </>
+  );
+}
+
 function SimilarImagesSettings() {
   const settings = useAtomValue(settingsAtom);
+  const [progress, setProgress] = useAtom(progressAtom);
   const t = useT();
 
+  const handleRegroup = () => {
+    if (progress.tool) {
+      return;
+    }
+    setProgress({ ...progress, tool: Tools.SimilarImages });
+    ipc.regroupSimilarImages(settings.similarImagesSubSimilarity);
+  };
+
   return (
     <>
       <FormItem
@@ -207,6 +493,13 @@ function SimilarImagesSettings() {
       >
         <Switch />
       </FormItem>
+      <FormItem
+        name="similarImagesSubRotationInvariant"
+        label={t('matchRotatedAndMirrored')}
+        comp="switch"
+      >
+        <Switch />
+      </FormItem>
       <FormItem
         name="similarImagesSubSimilarity"
         label={t('maxDifference')}
@@ -223,6 +516,16 @@ function SimilarImagesSettings() {
           </div>
         )}
       </FormItem>
+      <RawFormItem label={t('regroupWithoutRehashing')}>
+        <Button
+          variant="outline"
+          size="sm"
+          disabled={!!progress.tool}
+          onClick={handleRegroup}
+        >
+          {t('regroup')}
+        </Button>
+      </RawFormItem>
     </>
   );
 }
@@ -256,6 +559,33 @@ function SimilarVideosSettings() {
       >
         <Switch />
       </FormItem>
+      <FormItem
+        name="similarVideosSubSkipForwardAmount"
+        label={t('skipForwardAmount')}
+        comp="input-number"
+      >
+        <InputNumber className="w-[75%]" minValue={0} maxValue={300} />
+      </FormItem>
+      <FormItem
+        name="similarVideosSubVidHashDuration"
+        label={t('hashDuration')}
+        comp="input-number"
+      >
+        <InputNumber className="w-[75%]" minValue={2} maxValue={60} />
+      </FormItem>
+      <FormItem
+        name="similarVideosSubCropDetect"
+        label={t('cropDetect')}
+        comp="select"
+      >
+        <Select
+          className="w-[60%]"
+          options={Object.values(SimilarVideosCropDetect).map((value) => ({
+            label: t(value.toLowerCase() as TranslationKeys),
+            value,
+          }))}
+        />
+      </FormItem>
     </>
   );
 }
@@ -360,25 +690,108 @@ function MusicDuplicatesSettings() {
   );
 }
 
+function SimilarNamesSettings() {
+  const settings = useAtomValue(settingsAtom);
+  const t = useT();
+
+  return (
+    <FormItem
+      name="similarNamesSubThreshold"
+      label={t('similarityThreshold')}
+      comp="slider"
+    >
+      {(slotProps) => (
+        <div className="flex items-center gap-2 w-[75%]">
+          <Slider min={50} max={100} id={slotProps.name} {...slotProps} />
+          <SliderValue
+            className="w-11"
+            value={settings.similarNamesSubThreshold}
+            max={100}
+          />
+        </div>
+      )}
+    </FormItem>
+  );
+}
+
 function BrokenFilesSettings() {
   const t = useT();
+  const settings = useAtomValue(settingsAtom);
 
   return (
-    <RawFormItem label={t('typeOfFilesToCheck')}>
-      <div className="flex gap-4">
-        <FormItem name="brokenFilesSubAudio" comp="checkbox">
-          <LabelCheckbox label={t('audio')} />
-        </FormItem>
-        <FormItem name="brokenFilesSubPdf" comp="checkbox">
-          <LabelCheckbox label={t('pdf')} />
-        </FormItem>
-        <FormItem name="brokenFilesSubArchive" comp="checkbox">
-          <LabelCheckbox label={t('archive')} />
-        </FormItem>
-        <FormItem name="brokenFilesSubImage" comp="checkbox">
-          <LabelCheckbox label={t('image')} />
+    <>
+      <RawFormItem label={t('typeOfFilesToCheck')}>
+        <div className="flex flex-wrap gap-4">
+          <FormItem name="brokenFilesSubAudio" comp="checkbox">
+            <LabelCheckbox label={t('audio')} />
+          </FormItem>
+          <FormItem name="brokenFilesSubPdf" comp="checkbox">
+            <LabelCheckbox label={t('pdf')} />
+          </FormItem>
+          <FormItem name="brokenFilesSubArchive" comp="checkbox">
+            <LabelCheckbox label={t('archive')} />
+          </FormItem>
+          <FormItem name="brokenFilesSubImage" comp="checkbox">
+            <LabelCheckbox label={t('image')} />
+          </FormItem>
+          <FormItem name="brokenFilesSubVideo" comp="checkbox">
+            <LabelCheckbox label={t('video')} />
+          </FormItem>
+          <FormItem name="brokenFilesSubDocument" comp="checkbox">
+            <LabelCheckbox label={t('officeDocument')} />
+          </FormItem>
+          <FormItem name="brokenFilesSubFont" comp="checkbox">
+            <LabelCheckbox label={t('font')} />
+          </FormItem>
+        </div>
+      </RawFormItem>
+      {settings.brokenFilesSubArchive && (
+        <FormItem
+          name="brokenFilesSubDeepArchive"
+          label={t('deepArchiveCheck')}
+          comp="switch"
+        >
+          <Switch />
         </FormItem>
-      </div>
-    </RawFormItem>
+      )}
+    </>
+  );
+}
+
+function BadExtensionsSettings() {
+  const t = useT();
+
+  return (
+    <>
+      <FormItem
+        name="badExtensionsSubMimeExtensions"
+        label={t('allowedExtensionsPerMime')}
+        comp="textarea"
+      >
+        <Textarea
+          rows={4}
+          className="font-mono"
+          placeholder="application/zip: docm, apk"
+        />
+      </FormItem>
+      <FormItem
+        name="badExtensionsSubCustomSignatures"
+        label={t('customSignatures')}
+        comp="textarea"
+      >
+        <Textarea
+          rows={4}
+          className="font-mono"
+          placeholder="acp, acpx: 41 43 4D 45 @0"
+        />
+      </FormItem>
+      <FormItem
+        name="badExtensionsSubEquivalentExtensions"
+        label={t('equivalentExtensions')}
+        comp="textarea"
+      >
+        <Textarea rows={4} className="font-mono" />
+      </FormItem>
+    </>
   );
 }
@@ -1,16 +1,25 @@
 import { useAtom, useAtomValue, useSetAtom } from 'jotai';
 import {
+  CaseSensitiveIcon,
   ClockIcon,
+  CopyIcon,
   FileQuestionIcon,
   FilesIcon,
   FileWarningIcon,
   FileXIcon,
+  FolderClosedIcon,
   FolderOpenIcon,
+  FolderTreeIcon,
+  FoldersIcon,
   HardDriveIcon,
+  HourglassIcon,
   ImageIcon,
   LinkIcon,
   LoaderCircleIcon,
   MusicIcon,
+  PaperclipIcon,
+  TypeIcon,
+  UsbIcon,
   VideoIcon,
 } from 'lucide-react';
 import { currentToolAtom, progressAtom } from '~/atom/primitive';
@@ -34,16 +43,25 @@ const toolIcons: Record<
   React.ComponentType<{ className?: string }>
 > = {
   [Tools.DuplicateFiles]: FilesIcon,
+  [Tools.DuplicateFolders]: FoldersIcon,
   [Tools.EmptyFolders]: FolderOpenIcon,
   [Tools.BigFiles]: HardDriveIcon,
+  [Tools.LargestFolders]: FolderClosedIcon,
+  [Tools.DiskUsage]: FolderTreeIcon,
+  [Tools.StaleFiles]: HourglassIcon,
   [Tools.EmptyFiles]: FileXIcon,
   [Tools.TemporaryFiles]: ClockIcon,
+  [Tools.OrphanedSidecars]: PaperclipIcon,
   [Tools.SimilarImages]: ImageIcon,
   [Tools.SimilarVideos]: VideoIcon,
   [Tools.MusicDuplicates]: MusicIcon,
+  [Tools.SimilarNames]: TypeIcon,
+  [Tools.NumberedCopies]: CopyIcon,
+  [Tools.NameCollisions]: CaseSensitiveIcon,
   [Tools.InvalidSymlinks]: LinkIcon,
   [Tools.BrokenFiles]: FileWarningIcon,
   [Tools.BadExtensions]: FileQuestionIcon,
+  [Tools.NamePortability]: UsbIcon,
 };
 
 export function ToolTabs() {